@@ -85,6 +85,13 @@ impl Entry {
     pub fn sequence(&self) -> PyResult<&str> {
         Ok(&self.base_entry.get_sequence())
     }
+
+    /// Returns the raw header, empty if the entry was not read from a file
+    ///
+    #[getter]
+    pub fn header(&self) -> PyResult<&str> {
+        Ok(self.base_entry.get_header())
+    }
 }
 
 
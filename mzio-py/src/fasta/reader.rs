@@ -1,4 +1,5 @@
 // std imports
+use std::io::BufRead;
 use std::path::PathBuf;
 
 // 3rd party modules
@@ -6,12 +7,14 @@ use pyo3::prelude::*;
 use anyhow::Result;
 use mzio::fasta::reader::Reader as BaseReader;
 
+use fallible_iterator::FallibleIterator;
+
 // internal imports
 use crate::fasta::entry::Entry;
 
 #[pyclass]
 pub struct Reader {
-    base_reader: BaseReader
+    base_reader: BaseReader<Box<dyn BufRead + Send>>
 }
 
 #[pymethods]
@@ -20,7 +23,7 @@ impl Reader {
     fn new(fasta_file_path: PathBuf, buffer_size: usize) -> Result<Self> {
         match BaseReader::new(&fasta_file_path, buffer_size) {
             Ok(base_reader) => Ok(Self{base_reader}),
-            Err(err) => Err(err)
+            Err(err) => Err(err.into())
         }
     }
 
@@ -28,10 +31,10 @@ impl Reader {
         slf
     }
 
-    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<Entry> {
-        match slf.base_reader.next() {
-            Some(base_entry) => Some(Entry::from(base_entry)),
-            None => None
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> Result<Option<Entry>> {
+        match slf.base_reader.next()? {
+            Some(base_entry) => Ok(Some(Entry::from(base_entry))),
+            None => Ok(None)
         }
     }
 }
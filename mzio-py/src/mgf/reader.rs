@@ -1,4 +1,5 @@
 // std imports
+use std::io::BufRead;
 use std::path::PathBuf;
 
 // 3rd party modules
@@ -13,7 +14,7 @@ use crate::mgf::spectrum::Spectrum;
 
 #[pyclass]
 pub struct Reader {
-    base_reader: BaseReader
+    base_reader: BaseReader<Box<dyn BufRead + Send>>
 }
 
 #[pymethods]
@@ -23,7 +24,7 @@ impl Reader {
     fn new(mgf_file_path: PathBuf, buffer_size: usize) -> Result<Self> {
         match BaseReader::new(&mgf_file_path, buffer_size) {
             Ok(base_reader) => Ok(Self{base_reader}),
-            Err(err) => Err(err)
+            Err(err) => Err(err.into())
         }
     }
 
@@ -31,10 +32,10 @@ impl Reader {
         slf
     }
 
-    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<Spectrum> {
-        match slf.base_reader.next().ok()? {
-            Some(base_spectrum) => Some(Spectrum::from(base_spectrum)),
-            None => None
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> Result<Option<Spectrum>> {
+        match slf.base_reader.next()? {
+            Some(base_spectrum) => Ok(Some(Spectrum::from(base_spectrum))),
+            None => Ok(None)
         }
     }
 }
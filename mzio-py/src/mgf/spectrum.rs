@@ -1,6 +1,6 @@
 // 3rd party imports
 use pyo3::prelude::*;
-use mzio::mgf::spectrum::Spectrum as BaseSpectrum;
+use mzio::mgf::spectrum::{Precursor as BasePrecursor, Spectrum as BaseSpectrum};
 
 
 /// Wrapper for the rust implementation spectrum
@@ -18,19 +18,19 @@ impl Spectrum {
     /// 
     /// * `title` - Specturm title
     /// * `precursor_mz` -  Precursor mass
-    /// * `precursor_charge` -  Precursor charge
+    /// * `precursor_charges` -  Precursor charges
     /// * `retention_time` -  Retention time
     /// * `mz_list` - M/Z list
     /// * `intensity_list` -  Intensity list
     /// 
     #[new]
-    fn new(title: String, precursor_mz: f64, precursor_charge: Option<i8>,
+    fn new(title: String, precursor_mz: f64, precursor_charges: Vec<i8>,
         retention_time: Option<f64>, mz_list: Vec<f64>, intensity_list: Vec<f32>) -> Self {
         Self {
             base_spectrum: BaseSpectrum::new(
                 title,
-                precursor_mz,
-                precursor_charge,
+                vec![BasePrecursor::new(precursor_mz, None)],
+                precursor_charges,
                 retention_time,
                 mz_list,
                 intensity_list,
@@ -52,11 +52,18 @@ impl Spectrum {
         Ok(self.base_spectrum.get_precursor_mz())
     }
 
-    /// Returns the precursor charge
+    /// Returns the precursor intensity
     ///
     #[getter]
-    pub fn precursor_charge(&self) -> PyResult<Option<i8>> {
-        Ok(*self.base_spectrum.get_precursor_charge())
+    pub fn precursor_intensity(&self) -> PyResult<Option<f64>> {
+        Ok(self.base_spectrum.get_precursor_intensity())
+    }
+
+    /// Returns the precursor charges
+    ///
+    #[getter]
+    pub fn precursor_charges(&self) -> PyResult<Vec<i8>> {
+        Ok(self.base_spectrum.get_precursor_charges().to_vec())
     }
 
     /// Returns the retention time
@@ -79,6 +86,27 @@ impl Spectrum {
     pub fn intensities(&self) -> PyResult<Vec<f32>> {
         Ok(self.base_spectrum.get_intensity_list().to_vec())
     }
+
+    /// Returns the fragment charges of the peaks, empty if no peak has a charge
+    ///
+    #[getter]
+    pub fn peak_charges(&self) -> PyResult<Vec<Option<i8>>> {
+        Ok(self.base_spectrum.get_peak_charge_list().to_vec())
+    }
+
+    /// Returns the annotations of the peaks, empty if no peak is annotated
+    ///
+    #[getter]
+    pub fn peak_annotations(&self) -> PyResult<Vec<Option<String>>> {
+        Ok(self.base_spectrum.get_peak_annotation_list().to_vec())
+    }
+
+    /// Returns the parameters without dedicated field as ordered (key, value) pairs
+    ///
+    #[getter]
+    pub fn additional_params(&self) -> PyResult<Vec<(String, String)>> {
+        Ok(self.base_spectrum.get_additional_params().clone())
+    }
 }
 
 
@@ -1,4 +1,6 @@
 // std imports
+use std::fs::File;
+use std::io::BufWriter;
 use std::path::PathBuf;
 
 // 3rd party modules
@@ -12,7 +14,7 @@ use crate::mgf::spectrum::Spectrum;
 
 #[pyclass]
 pub struct Writer {
-    base_writer: BaseWriter
+    base_writer: BaseWriter<BufWriter<File>>
 }
 
 #[pymethods]
@@ -27,14 +29,14 @@ impl Writer {
     pub fn new(mgf_file_path: PathBuf) -> PyResult<Self> {
         match BaseWriter::new(&mgf_file_path) {
             Ok(base_writer) => Ok(Self{base_writer}),
-            Err(err) => Err(err.into())
+            Err(err) => Err(anyhow::Error::from(err).into())
         }
     }
 
     pub fn write_spectrum(&mut self, spectrum: &Spectrum) -> Result<usize> {
         match self.base_writer.write_spectrum(spectrum.into()) {
             Ok(written_bytes) => Ok(written_bytes),
-            Err(err) => Err(err)
+            Err(err) => Err(err.into())
         }
     }
 
@@ -42,7 +44,7 @@ impl Writer {
     pub fn flush(&mut self) -> Result<()> {
         match self.base_writer.flush() {
             Ok(_) => Ok(()),
-            Err(err) => Err(err)
+            Err(err) => Err(err.into())
         }
     }
 }
@@ -0,0 +1,360 @@
+//! Transparent (de)compression of input and output streams.
+//!
+//! Compressed input is detected by its magic bytes, so the readers can open
+//! compressed and plain files alike. Each compression format is behind its own
+//! cargo feature (`gzip`, `bzip2`, `xz`, `zstd`).
+
+// std imports
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter};
+use std::io::prelude::*;
+use std::path::Path;
+
+// internal imports
+use crate::error::{Error, Result};
+
+/// Magic bytes of gzip streams
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+/// Magic bytes of bzip2 streams
+const BZIP2_MAGIC: &[u8] = b"BZh";
+/// Magic bytes of xz streams
+const XZ_MAGIC: &[u8] = &[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00];
+/// Magic bytes of zstd frames
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Default compression level used by the encoders
+#[cfg(any(feature = "gzip", feature = "bzip2", feature = "xz"))]
+const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+
+/// Supported compression formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd
+}
+
+impl Compression {
+    /// Detects the compression by the magic bytes at the beginning of a stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `magic_bytes` - First bytes of the stream (at least 6 bytes for a reliable detection)
+    ///
+    pub fn detect(magic_bytes: &[u8]) -> Self {
+        if magic_bytes.starts_with(GZIP_MAGIC) {
+            Self::Gzip
+        } else if magic_bytes.starts_with(BZIP2_MAGIC) {
+            Self::Bzip2
+        } else if magic_bytes.starts_with(XZ_MAGIC) {
+            Self::Xz
+        } else if magic_bytes.starts_with(ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    /// Guesses the compression from the file extension, e.g. `.gz` or `.zst`.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to file
+    ///
+    pub fn from_path(file_path: &Path) -> Self {
+        match file_path.extension().and_then(|extension| extension.to_str()) {
+            Some("gz") | Some("gzip") => Self::Gzip,
+            Some("bz2") | Some("bzip2") => Self::Bzip2,
+            Some("xz") => Self::Xz,
+            Some("zst") | Some("zstd") => Self::Zstd,
+            _ => Self::None
+        }
+    }
+
+    /// Returns the name of the cargo feature needed for this compression.
+    ///
+    fn get_feature_name(&self) -> &str {
+        match self {
+            Self::None => "",
+            Self::Gzip => "gzip",
+            Self::Bzip2 => "bzip2",
+            Self::Xz => "xz",
+            Self::Zstd => "zstd"
+        }
+    }
+}
+
+/// Opens the given file and wraps it in a decoder if the content is compressed.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to a plain or compressed file
+/// * `buffer_size` - Size of the read buffers
+///
+pub fn open_reader(file_path: &Path, buffer_size: usize) -> Result<Box<dyn BufRead + Send>> {
+    let file: File = File::open(file_path).map_err(|err| Error::io_with_path(err, file_path))?;
+    decompress_reader(BufReader::with_capacity(buffer_size, file), buffer_size)
+        .map_err(|err| match err {
+            Error::Io { source, .. } => Error::io_with_path(source, file_path),
+            _ => err
+        })
+}
+
+/// Wraps the given reader in a decoder if the content is compressed,
+/// otherwise the reader is returned as is.
+///
+/// # Arguments
+///
+/// * `reader` - Buffered reader providing plain or compressed content
+/// * `buffer_size` - Size of the read buffer for the decompressed content
+///
+pub fn decompress_reader<R>(mut reader: R, buffer_size: usize) -> Result<Box<dyn BufRead + Send>>
+where
+    R: BufRead + Send + 'static
+{
+    let compression = Compression::detect(reader.fill_buf()?);
+    match compression {
+        Compression::None => Ok(Box::new(reader)),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Ok(Box::new(BufReader::with_capacity(
+            buffer_size,
+            flate2::bufread::MultiGzDecoder::new(reader)
+        ))),
+        #[cfg(feature = "bzip2")]
+        Compression::Bzip2 => Ok(Box::new(BufReader::with_capacity(
+            buffer_size,
+            bzip2::bufread::MultiBzDecoder::new(reader)
+        ))),
+        #[cfg(feature = "xz")]
+        Compression::Xz => Ok(Box::new(BufReader::with_capacity(
+            buffer_size,
+            xz2::bufread::XzDecoder::new_multi_decoder(reader)
+        ))),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(Box::new(BufReader::with_capacity(
+            buffer_size,
+            zstd::stream::read::Decoder::with_buffer(reader)?
+        ))),
+        #[allow(unreachable_patterns)]
+        _ => {
+            let _ = buffer_size;
+            Err(Error::UnsupportedFormat {
+                message: format!(
+                    "input is {:?} compressed, but mzio was built without the `{}` feature",
+                    compression,
+                    compression.get_feature_name()
+                )
+            })
+        }
+    }
+}
+
+/// Encoder writing plain or compressed output to the wrapped writer.
+/// Call `finish()` to complete the compressed stream and get the wrapped writer back.
+pub enum Encoder<W: Write> {
+    None(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzEncoder<W>),
+    #[cfg(feature = "xz")]
+    Xz(xz2::write::XzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>)
+}
+
+impl Encoder<BufWriter<File>> {
+    /// Creates the given file and wraps it in an encoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to file
+    /// * `compression` - Compression of the written content
+    ///
+    pub fn create(file_path: &Path, compression: Compression) -> Result<Self> {
+        let file: File = File::create(file_path).map_err(|err| Error::io_with_path(err, file_path))?;
+        Self::new(BufWriter::new(file), compression)
+    }
+}
+
+impl<W: Write> Encoder<W> {
+    /// Wraps the given writer in an encoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Write target for the compressed content
+    /// * `compression` - Compression of the written content
+    ///
+    pub fn new(writer: W, compression: Compression) -> Result<Self> {
+        match compression {
+            Compression::None => Ok(Self::None(writer)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Self::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::new(DEFAULT_COMPRESSION_LEVEL)
+            ))),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Ok(Self::Bzip2(bzip2::write::BzEncoder::new(
+                writer,
+                bzip2::Compression::new(DEFAULT_COMPRESSION_LEVEL)
+            ))),
+            #[cfg(feature = "xz")]
+            Compression::Xz => Ok(Self::Xz(xz2::write::XzEncoder::new(
+                writer,
+                DEFAULT_COMPRESSION_LEVEL
+            ))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Self::Zstd(zstd::stream::write::Encoder::new(
+                writer,
+                zstd::DEFAULT_COMPRESSION_LEVEL
+            )?)),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::UnsupportedFormat {
+                message: format!(
+                    "{:?} compression requested, but mzio was built without the `{}` feature",
+                    compression,
+                    compression.get_feature_name()
+                )
+            })
+        }
+    }
+
+    /// Completes the compressed stream, flushes the wrapped writer and returns it,
+    /// so buffered write errors are not lost on drop.
+    ///
+    pub fn finish(self) -> Result<W> {
+        let mut writer = match self {
+            Self::None(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Self::Gzip(encoder) => encoder.finish()?,
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(encoder) => encoder.finish()?,
+            #[cfg(feature = "xz")]
+            Self::Xz(encoder) => encoder.finish()?,
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.finish()?
+        };
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::None(writer) => writer.write(buf),
+            #[cfg(feature = "gzip")]
+            Self::Gzip(encoder) => encoder.write(buf),
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(encoder) => encoder.write(buf),
+            #[cfg(feature = "xz")]
+            Self::Xz(encoder) => encoder.write(buf),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.write(buf)
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::None(writer) => writer.flush(),
+            #[cfg(feature = "gzip")]
+            Self::Gzip(encoder) => encoder.flush(),
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2(encoder) => encoder.flush(),
+            #[cfg(feature = "xz")]
+            Self::Xz(encoder) => encoder.flush(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(encoder) => encoder.flush()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_CONTENT: &str = ">sp|P27748|ACOX_CUPNH Acetoin catabolism protein X\nMGHAAGASAQIAPVVGIIANPISARDIRRVIANANSLQLADRVNIVLRLLAALASCGVER\n";
+
+    /// Compresses the test content, decompresses it again and compares it with the original one.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - Compression to test
+    ///
+    fn assert_round_trip(compression: Compression) {
+        let mut encoder = Encoder::new(Vec::new(), compression).unwrap();
+        encoder.write_all(TEST_CONTENT.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert_eq!(Compression::detect(&compressed), compression);
+
+        let mut decompressed = String::new();
+        decompress_reader(std::io::Cursor::new(compressed), 1024).unwrap()
+            .read_to_string(&mut decompressed).unwrap();
+        assert_eq!(decompressed, TEST_CONTENT);
+    }
+
+    /// Write target failing on every write
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    /// Checks that finish() reports write errors of buffered content.
+    fn test_finish_propagates_write_errors() {
+        let mut encoder = Encoder::new(std::io::BufWriter::new(FailingWriter), Compression::None).unwrap();
+        encoder.write_all(TEST_CONTENT.as_bytes()).unwrap();
+        assert!(encoder.finish().is_err());
+    }
+
+    #[test]
+    /// Tests the detection of the compression by file extension.
+    fn test_compression_from_path() {
+        assert_eq!(Compression::from_path(Path::new("uniprot.fasta.gz")), Compression::Gzip);
+        assert_eq!(Compression::from_path(Path::new("spectra.mgf.zst")), Compression::Zstd);
+        assert_eq!(Compression::from_path(Path::new("spectra.mgf")), Compression::None);
+    }
+
+    #[test]
+    /// Plain content is passed through.
+    fn test_no_compression() {
+        assert_round_trip(Compression::None);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    /// Compresses and decompresses gzip content.
+    fn test_gzip() {
+        assert_round_trip(Compression::Gzip);
+    }
+
+    #[cfg(feature = "bzip2")]
+    #[test]
+    /// Compresses and decompresses bzip2 content.
+    fn test_bzip2() {
+        assert_round_trip(Compression::Bzip2);
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    /// Compresses and decompresses xz content.
+    fn test_xz() {
+        assert_round_trip(Compression::Xz);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    /// Compresses and decompresses Zstandard content.
+    fn test_zstd() {
+        assert_round_trip(Compression::Zstd);
+    }
+}
@@ -0,0 +1,240 @@
+//! Error type shared by all readers and writers of mzio.
+
+// std imports
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
+
+// 3rd party imports
+use thiserror::Error as ThisError;
+
+/// Result with mzio's error type
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Position of an error within the input.
+/// Each part is optional, e.g. the path is unknown when reading from memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Position {
+    file_path: Option<PathBuf>,
+    line_number: Option<usize>,
+    byte_offset: Option<u64>
+}
+
+impl Position {
+    /// Creates a new position
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the file
+    /// * `line_number` - Line number (1-based)
+    /// * `byte_offset` - Byte offset of the line start
+    ///
+    pub fn new(file_path: Option<PathBuf>, line_number: Option<usize>, byte_offset: Option<u64>) -> Self {
+        Self {
+            file_path,
+            line_number,
+            byte_offset
+        }
+    }
+
+    /// Creates a position only referring to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the file
+    ///
+    pub fn from_path(file_path: &Path) -> Self {
+        Self::new(Some(file_path.to_path_buf()), None, None)
+    }
+
+    /// Returns the file path
+    ///
+    pub fn get_file_path(&self) -> Option<&Path> {
+        self.file_path.as_deref()
+    }
+
+    /// Returns the line number (1-based)
+    ///
+    pub fn get_line_number(&self) -> Option<usize> {
+        self.line_number
+    }
+
+    /// Returns the byte offset of the line start
+    ///
+    pub fn get_byte_offset(&self) -> Option<u64> {
+        self.byte_offset
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(file_path) = &self.file_path {
+            parts.push(file_path.display().to_string());
+        }
+        if let Some(line_number) = self.line_number {
+            parts.push(format!("line {}", line_number));
+        }
+        if let Some(byte_offset) = self.byte_offset {
+            parts.push(format!("byte {}", byte_offset));
+        }
+        if parts.is_empty() {
+            write!(f, "unknown position")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+/// Errors of mzio's readers and writers
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("I/O error ({position}): {source}")]
+    Io {
+        source: io::Error,
+        position: Position
+    },
+    #[error("malformed header `{header}` ({position})")]
+    MalformedHeader {
+        header: String,
+        position: Position
+    },
+    #[error("content `{line}` without preceding header ({position})")]
+    MissingHeader {
+        line: String,
+        position: Position
+    },
+    #[error("{value_name} value is missing ({position})")]
+    MissingPeakValue {
+        value_name: &'static str,
+        position: Position
+    },
+    #[error("cannot parse `{value}` as float ({position})")]
+    FloatParse {
+        value: String,
+        position: Position
+    },
+    #[error("cannot parse `{value}` as charge ({position})")]
+    ChargeParse {
+        value: String,
+        position: Position
+    },
+    #[error("cannot parse `{value}` as integer ({position}): {source}")]
+    IntParse {
+        value: String,
+        source: ParseIntError,
+        position: Position
+    },
+    #[error("reached EOF before END IONS, spectrum is incomplete ({position})")]
+    UnterminatedSpectrum {
+        position: Position
+    },
+    #[error("invalid index: {message} ({position})")]
+    InvalidIndex {
+        message: String,
+        position: Position
+    },
+    #[error("malformed XML: {message} ({position})")]
+    Xml {
+        message: String,
+        position: Position
+    },
+    #[error("cannot decode binary data: {message} ({position})")]
+    BinaryData {
+        message: String,
+        position: Position
+    },
+    #[error("invalid content: {message} ({position})")]
+    InvalidContent {
+        message: String,
+        position: Position
+    },
+    #[error("unsupported format: {message}")]
+    UnsupportedFormat {
+        message: String
+    }
+}
+
+impl Error {
+    /// Returns the position of the error, if known.
+    ///
+    pub fn get_position(&self) -> Option<&Position> {
+        match self {
+            Self::Io { position, .. } |
+            Self::MalformedHeader { position, .. } |
+            Self::MissingHeader { position, .. } |
+            Self::MissingPeakValue { position, .. } |
+            Self::FloatParse { position, .. } |
+            Self::ChargeParse { position, .. } |
+            Self::IntParse { position, .. } |
+            Self::InvalidIndex { position, .. } |
+            Self::Xml { position, .. } |
+            Self::BinaryData { position, .. } |
+            Self::InvalidContent { position, .. } |
+            Self::UnterminatedSpectrum { position } => Some(position),
+            Self::UnsupportedFormat { .. } => None
+        }
+    }
+
+    /// Creates an I/O error for the given file, e.g. when opening it failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Original I/O error
+    /// * `file_path` - Path to the file
+    ///
+    pub(crate) fn io_with_path(source: io::Error, file_path: &Path) -> Self {
+        Self::Io {
+            source,
+            position: Position::from_path(file_path)
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Self::Io {
+            source,
+            position: Position::default()
+        }
+    }
+}
+
+/// Parses a float, reporting the value and position on failure.
+///
+/// # Arguments
+///
+/// * `value` - String to parse
+/// * `position` - Closure returning the current position
+///
+pub(crate) fn parse_float<T, F>(value: &str, position: F) -> Result<T>
+where
+    T: fast_float::FastFloat,
+    F: FnOnce() -> Position
+{
+    fast_float::parse(value).map_err(|_| Error::FloatParse {
+        value: value.to_string(),
+        position: position()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Tests the formatting of positions in error messages.
+    fn test_error_display() {
+        let error = Error::MalformedHeader {
+            header: ">foo".to_string(),
+            position: Position::new(Some(PathBuf::from("proteins.fasta")), Some(3), Some(120))
+        };
+        assert_eq!(error.to_string(), "malformed header `>foo` (proteins.fasta, line 3, byte 120)");
+
+        let error = Error::UnterminatedSpectrum {
+            position: Position::default()
+        };
+        assert_eq!(error.to_string(), "reached EOF before END IONS, spectrum is incomplete (unknown position)");
+    }
+}
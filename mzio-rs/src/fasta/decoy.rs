@@ -0,0 +1,404 @@
+//! Decoy generation for target-decoy searches.
+
+// std imports
+use std::collections::HashMap;
+use std::io::prelude::*;
+
+// internal imports
+use crate::error::Result;
+use crate::fasta::entry::Entry;
+use crate::fasta::writer::Writer;
+
+/// Default prefix for decoy accessions
+pub const DEFAULT_DECOY_PREFIX: &str = "DECOY_";
+
+/// Default cleavage residues kept in place by the pseudo-reversal (trypsin)
+pub const DEFAULT_CLEAVAGE_RESIDUES: &str = "KR";
+
+/// Maximum number of walks through the de Bruijn graph before falling back to the reversed sequence
+const MAX_DE_BRUIJN_WALKS: usize = 10;
+
+/// Method used to create the decoy sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecoyMethod {
+    /// Reverses the whole sequence
+    Reverse,
+    /// Reverses the segments between cleavage residues, keeping the cleavage residues
+    /// in place, so decoy peptides keep the masses and termini of their targets
+    PseudoReverse {
+        cleavage_residues: String
+    },
+    /// Shuffles the sequence. The shuffle depends only on the seed and the accession,
+    /// so results are reproducible regardless of the entry order.
+    Shuffle {
+        seed: u64
+    },
+    /// Walks a random Eulerian path through the de Bruijn graph of the sequence,
+    /// i.e. the decoy has the same k-mer composition as the target but in a different order.
+    /// Sequences without a different walk, e.g. without repeated (k-1)-mers, are reversed.
+    DeBruijn {
+        k: usize,
+        seed: u64
+    }
+}
+
+/// Creates decoy entries from target entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecoyGenerator {
+    method: DecoyMethod,
+    accession_prefix: String
+}
+
+impl DecoyGenerator {
+    /// Creates a new decoy generator using `DEFAULT_DECOY_PREFIX`
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - Method used to create the decoy sequence
+    ///
+    pub fn new(method: DecoyMethod) -> Self {
+        Self {
+            method,
+            accession_prefix: DEFAULT_DECOY_PREFIX.to_string()
+        }
+    }
+
+    /// Sets the prefix added to the decoy accessions, e.g. `rev_`
+    ///
+    /// # Arguments
+    ///
+    /// * `accession_prefix` - Accession prefix
+    ///
+    pub fn with_accession_prefix(mut self, accession_prefix: &str) -> Self {
+        self.accession_prefix = accession_prefix.to_string();
+        self
+    }
+
+    /// Returns the decoy method
+    ///
+    pub fn get_method(&self) -> &DecoyMethod {
+        &self.method
+    }
+
+    /// Returns the accession prefix
+    ///
+    pub fn get_accession_prefix(&self) -> &str {
+        &self.accession_prefix
+    }
+
+    /// Returns true if the given accession is a decoy accession
+    ///
+    /// # Arguments
+    ///
+    /// * `accession` - Accession
+    ///
+    pub fn is_decoy_accession(&self, accession: &str) -> bool {
+        accession.starts_with(&self.accession_prefix)
+    }
+
+    /// Creates the decoy sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - Target sequence
+    /// * `accession` - Target accession, used to derive the seed of random methods
+    ///
+    pub fn create_decoy_sequence(&self, sequence: &str, accession: &str) -> String {
+        match &self.method {
+            DecoyMethod::Reverse => sequence.chars().rev().collect(),
+            DecoyMethod::PseudoReverse { cleavage_residues } => pseudo_reverse(sequence, cleavage_residues),
+            DecoyMethod::Shuffle { seed } => {
+                let mut rng = Rng::new(seed ^ fnv1a(accession));
+                let mut residues: Vec<char> = sequence.chars().collect();
+                rng.shuffle(&mut residues);
+                residues.into_iter().collect()
+            },
+            DecoyMethod::DeBruijn { k, seed } => {
+                let mut rng = Rng::new(seed ^ fnv1a(accession));
+                de_bruijn_walk(sequence, *k, &mut rng)
+            }
+        }
+    }
+
+    /// Creates the decoy entry. The accession is prefixed in the entry and in the raw header,
+    /// all other header information is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - Target entry
+    ///
+    pub fn create_decoy(&self, entry: &Entry) -> Entry {
+        let decoy_accession = format!("{}{}", self.accession_prefix, entry.get_accession());
+        let header = if entry.get_accession().is_empty() {
+            entry.get_header().to_string()
+        } else {
+            entry.get_header().replacen(entry.get_accession(), &decoy_accession, 1)
+        };
+        Entry::new(
+            entry.get_database().to_string(),
+            decoy_accession,
+            entry.get_entry_name().to_string(),
+            entry.get_protein_name().to_string(),
+            entry.get_keyword_attributes().clone(),
+            self.create_decoy_sequence(entry.get_sequence(), entry.get_accession())
+        ).with_header(header)
+    }
+
+    /// Writes the decoys of the given entries.
+    /// If `concatenate` is set, each target is written followed by its decoy,
+    /// resulting in a concatenated target-decoy database.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - FASTA writer
+    /// * `entries` - Iterator of target entries
+    /// * `concatenate` - Write the targets as well
+    /// * `sort_keyword_attributes` - See `Writer::write_entry()`
+    ///
+    pub fn write_decoys<'b, W, I>(&self, writer: &mut Writer<W>, entries: I, concatenate: bool,
+        sort_keyword_attributes: bool) -> Result<usize>
+    where
+        W: Write,
+        I: Iterator<Item = &'b Entry>,
+    {
+        let mut written_bytes: usize = 0;
+        for entry in entries {
+            if concatenate {
+                written_bytes += writer.write_entry(entry, sort_keyword_attributes)?;
+            }
+            written_bytes += writer.write_entry(&self.create_decoy(entry), sort_keyword_attributes)?;
+        }
+        Ok(written_bytes)
+    }
+}
+
+/// Reverses the segments between the given cleavage residues, keeping the cleavage residues in place,
+/// e.g. `PEPTIDEKMYSEQR` => `EDITPEPKQESYMR`
+///
+/// # Arguments
+///
+/// * `sequence` - Target sequence
+/// * `cleavage_residues` - Residues kept in place
+///
+fn pseudo_reverse(sequence: &str, cleavage_residues: &str) -> String {
+    let mut decoy = String::with_capacity(sequence.len());
+    let mut segment: Vec<char> = Vec::new();
+    for residue in sequence.chars() {
+        if cleavage_residues.contains(residue) {
+            decoy.extend(segment.drain(..).rev());
+            decoy.push(residue);
+        } else {
+            segment.push(residue);
+        }
+    }
+    decoy.extend(segment.drain(..).rev());
+    decoy
+}
+
+/// Creates a sequence with the same k-mer composition as the given one by walking a random
+/// Eulerian path (Hierholzer) through its de Bruijn graph. The path starts at the first (k-1)-mer,
+/// or at a random one if the graph is an Eulerian circuit (first and last (k-1)-mer are equal).
+/// If none of `MAX_DE_BRUIJN_WALKS` walks differs from the target, e.g. because all (k-1)-mers
+/// are unique, the reversed sequence is returned. Sequences shorter than k are reversed as well.
+///
+/// # Arguments
+///
+/// * `sequence` - Target sequence
+/// * `k` - k-mer length, at least 2
+/// * `rng` - Random number generator
+///
+fn de_bruijn_walk(sequence: &str, k: usize, rng: &mut Rng) -> String {
+    let residues: Vec<char> = sequence.chars().collect();
+    let k = k.max(2);
+    if residues.len() < k {
+        return residues.into_iter().rev().collect();
+    }
+    // Nodes are the (k-1)-mers, identified by their first occurrence, edges are the k-mers
+    let mut node_ids: HashMap<&[char], usize> = HashMap::new();
+    let mut nodes: Vec<&[char]> = Vec::new();
+    for window in residues.windows(k - 1) {
+        let next_id = node_ids.len();
+        if *node_ids.entry(window).or_insert(next_id) == next_id {
+            nodes.push(window);
+        }
+    }
+    let mut edges: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for window in residues.windows(k) {
+        edges[node_ids[&window[..k - 1]]].push(node_ids[&window[1..]]);
+    }
+    // In a circuit every node with an outgoing edge is a valid start
+    let starts: Vec<usize> = if node_ids[&residues[residues.len() - k + 1..]] == 0 {
+        (0..nodes.len()).filter(|node| !edges[*node].is_empty()).collect()
+    } else {
+        vec![0]
+    };
+    for _ in 0..MAX_DE_BRUIJN_WALKS {
+        let mut unused_edges = edges.clone();
+        for targets in unused_edges.iter_mut() {
+            rng.shuffle(targets);
+        }
+        let start = starts[(rng.next_u64() % starts.len() as u64) as usize];
+        // Hierholzer, iterative
+        let mut path: Vec<usize> = Vec::with_capacity(residues.len() - k + 2);
+        let mut stack: Vec<usize> = vec![start];
+        while let Some(&node) = stack.last() {
+            match unused_edges[node].pop() {
+                Some(target) => stack.push(target),
+                None => path.push(stack.pop().unwrap())
+            }
+        }
+        path.reverse();
+        let mut decoy: String = nodes[path[0]].iter().collect();
+        decoy.extend(path[1..].iter().map(|node| nodes[*node][k - 2]));
+        if decoy != sequence {
+            return decoy;
+        }
+    }
+    residues.into_iter().rev().collect()
+}
+
+/// 64 bit FNV-1a hash, which is stable across platforms and Rust versions
+///
+/// # Arguments
+///
+/// * `value` - Hashed value
+///
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Small seedable random number generator (SplitMix64), sufficient for shuffling sequences
+struct Rng {
+    state: u64
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self {
+            state: seed
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Fisher-Yates shuffle
+    fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            values.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::path::Path;
+
+    use fallible_iterator::FallibleIterator;
+
+    use crate::fasta::reader::Reader;
+
+    const FASTA_FILE_PATH_STR: &str = "./test_files/fasta/partial_mouse.fasta";
+    const SEQUENCE: &str = "MGHAPKLLEEVRTIQPSTR";
+
+    fn sorted(sequence: &str) -> Vec<char> {
+        let mut residues: Vec<char> = sequence.chars().collect();
+        residues.sort_unstable();
+        residues
+    }
+
+    #[test]
+    /// Reverses sequences, keeping cleavage residues in place for pseudo-reversal.
+    fn test_reverse_and_pseudo_reverse() {
+        let generator = DecoyGenerator::new(DecoyMethod::Reverse);
+        assert_eq!(generator.create_decoy_sequence(SEQUENCE, "P1"), "RTSPQITRVEELLKPAHGM");
+
+        let generator = DecoyGenerator::new(DecoyMethod::PseudoReverse {
+            cleavage_residues: DEFAULT_CLEAVAGE_RESIDUES.to_string()
+        });
+        assert_eq!(generator.create_decoy_sequence(SEQUENCE, "P1"), "PAHGMKVEELLRTSPQITR");
+        assert_eq!(generator.create_decoy_sequence("PEPTIDE", "P1"), "EDITPEP");
+    }
+
+    #[test]
+    /// Shuffles sequences reproducibly, keeping the amino acid composition.
+    fn test_shuffle() {
+        let generator = DecoyGenerator::new(DecoyMethod::Shuffle { seed: 42 });
+        let decoy = generator.create_decoy_sequence(SEQUENCE, "P1");
+        assert_ne!(decoy, SEQUENCE);
+        assert_eq!(sorted(&decoy), sorted(SEQUENCE));
+        // Reproducible
+        assert_eq!(decoy, generator.create_decoy_sequence(SEQUENCE, "P1"));
+        assert_ne!(decoy, DecoyGenerator::new(DecoyMethod::Shuffle { seed: 43 }).create_decoy_sequence(SEQUENCE, "P1"));
+    }
+
+    #[test]
+    /// Creates de Bruijn decoys, keeping the k-mer composition and differing from the targets.
+    fn test_de_bruijn() {
+        let k = 2;
+        let generator = DecoyGenerator::new(DecoyMethod::DeBruijn { k, seed: 7 });
+        let decoy = generator.create_decoy_sequence(SEQUENCE, "P1");
+        assert_eq!(decoy.len(), SEQUENCE.len());
+        assert_eq!(decoy, generator.create_decoy_sequence(SEQUENCE, "P1"));
+        // Same k-mer composition
+        let kmers = |sequence: &str| {
+            let residues: Vec<char> = sequence.chars().collect();
+            let mut kmers: Vec<String> = residues.windows(k).map(|window| window.iter().collect()).collect();
+            kmers.sort_unstable();
+            kmers
+        };
+        assert_eq!(kmers(&decoy), kmers(SEQUENCE));
+        assert_ne!(decoy, SEQUENCE);
+        assert_eq!(generator.create_decoy_sequence("M", "P1"), "M");
+        // Without repeated (k-1)-mers there is only the target path, so the sequence is reversed
+        assert_eq!(generator.create_decoy_sequence("MGHAK", "P1"), "KAHGM");
+        // Each decoy differs from its target
+        let entries: Vec<Entry> = Reader::new(Path::new(FASTA_FILE_PATH_STR), 1024).unwrap()
+            .collect()
+            .unwrap();
+        for k in [2, 3, 4, 6] {
+            let generator = DecoyGenerator::new(DecoyMethod::DeBruijn { k, seed: 7 });
+            for entry in entries.iter() {
+                let decoy = generator.create_decoy_sequence(entry.get_sequence(), entry.get_accession());
+                assert_ne!(decoy, entry.get_sequence());
+                assert_eq!(sorted(&decoy), sorted(entry.get_sequence()));
+            }
+        }
+    }
+
+    #[test]
+    /// Creates decoy entries with prefixed accessions and writes targets and decoys.
+    fn test_creating_and_writing_decoys() {
+        let target = Entry::new(
+            "sp".to_string(),
+            "P27748".to_string(),
+            "ACOX_CUPNH".to_string(),
+            "Acetoin catabolism protein X".to_string(),
+            HashMap::new(),
+            "MLKR".to_string()
+        ).with_header("sp|P27748|ACOX_CUPNH Acetoin catabolism protein X".to_string());
+        let generator = DecoyGenerator::new(DecoyMethod::Reverse).with_accession_prefix("rev_");
+        let decoy = generator.create_decoy(&target);
+        assert_eq!(decoy.get_accession(), "rev_P27748");
+        assert_eq!(decoy.get_header(), "sp|rev_P27748|ACOX_CUPNH Acetoin catabolism protein X");
+        assert!(generator.is_decoy_accession(decoy.get_accession()));
+
+        let mut writer = Writer::from_writer(Vec::new());
+        generator.write_decoys(&mut writer, [target].iter(), true, true).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            ">sp|P27748|ACOX_CUPNH Acetoin catabolism protein X\nMLKR\n\
+            >sp|rev_P27748|ACOX_CUPNH Acetoin catabolism protein X\nRKLM\n"
+        );
+    }
+}
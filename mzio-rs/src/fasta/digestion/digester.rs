@@ -0,0 +1,354 @@
+// std imports
+use std::collections::BTreeSet;
+
+// internal imports
+use crate::fasta::digestion::enzyme::Enzyme;
+use crate::fasta::digestion::peptide::Peptide;
+use crate::fasta::entry::Entry;
+
+/// Default number of missed cleavages
+pub const DEFAULT_MAX_MISSED_CLEAVAGES: usize = 2;
+
+/// Default minimum peptide length
+pub const DEFAULT_MIN_LENGTH: usize = 6;
+
+/// Default maximum peptide length
+pub const DEFAULT_MAX_LENGTH: usize = 50;
+
+/// Specificity of the digestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Specificity {
+    /// Both peptide termini are cleavage sites (or protein termini)
+    Full,
+    /// At least one peptide terminus is a cleavage site (or protein terminus)
+    Semi
+}
+
+/// Digests protein sequences into peptides
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digester {
+    enzyme: Enzyme,
+    specificity: Specificity,
+    max_missed_cleavages: usize,
+    min_length: usize,
+    max_length: usize,
+    apply_proline_rule: bool,
+    methionine_excision: bool
+}
+
+impl Digester {
+    /// Creates a new fully specific digester with
+    /// `DEFAULT_MAX_MISSED_CLEAVAGES`, `DEFAULT_MIN_LENGTH`, `DEFAULT_MAX_LENGTH`,
+    /// the proline rule applied and without N-terminal methionine excision.
+    ///
+    /// # Arguments
+    ///
+    /// * `enzyme` - Enzyme
+    ///
+    pub fn new(enzyme: Enzyme) -> Self {
+        Self {
+            enzyme,
+            specificity: Specificity::Full,
+            max_missed_cleavages: DEFAULT_MAX_MISSED_CLEAVAGES,
+            min_length: DEFAULT_MIN_LENGTH,
+            max_length: DEFAULT_MAX_LENGTH,
+            apply_proline_rule: true,
+            methionine_excision: false
+        }
+    }
+
+    /// Sets the specificity
+    ///
+    /// # Arguments
+    ///
+    /// * `specificity` - Specificity
+    ///
+    pub fn with_specificity(mut self, specificity: Specificity) -> Self {
+        self.specificity = specificity;
+        self
+    }
+
+    /// Sets the maximum number of missed cleavages.
+    /// Ignored by unspecific enzymes.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_missed_cleavages` - Maximum number of missed cleavages
+    ///
+    pub fn with_max_missed_cleavages(mut self, max_missed_cleavages: usize) -> Self {
+        self.max_missed_cleavages = max_missed_cleavages;
+        self
+    }
+
+    /// Sets the minimum peptide length
+    ///
+    /// # Arguments
+    ///
+    /// * `min_length` - Minimum length (inclusive)
+    ///
+    pub fn with_min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Sets the maximum peptide length
+    ///
+    /// # Arguments
+    ///
+    /// * `max_length` - Maximum length (inclusive)
+    ///
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Enables or disables the proline rule, i.e. the enzyme's restriction residues
+    ///
+    /// # Arguments
+    ///
+    /// * `apply_proline_rule` - Apply the proline rule
+    ///
+    pub fn with_proline_rule(mut self, apply_proline_rule: bool) -> Self {
+        self.apply_proline_rule = apply_proline_rule;
+        self
+    }
+
+    /// Enables or disables the N-terminal methionine excision.
+    /// If enabled, proteins starting with M are digested with and without the initial methionine.
+    ///
+    /// # Arguments
+    ///
+    /// * `methionine_excision` - Excise the N-terminal methionine
+    ///
+    pub fn with_methionine_excision(mut self, methionine_excision: bool) -> Self {
+        self.methionine_excision = methionine_excision;
+        self
+    }
+
+    /// Returns the enzyme
+    ///
+    pub fn get_enzyme(&self) -> &Enzyme {
+        &self.enzyme
+    }
+
+    /// Returns the specificity
+    ///
+    pub fn get_specificity(&self) -> Specificity {
+        self.specificity
+    }
+
+    /// Returns the maximum number of missed cleavages
+    ///
+    pub fn get_max_missed_cleavages(&self) -> usize {
+        self.max_missed_cleavages
+    }
+
+    /// Returns the minimum peptide length
+    ///
+    pub fn get_min_length(&self) -> usize {
+        self.min_length
+    }
+
+    /// Returns the maximum peptide length
+    ///
+    pub fn get_max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Returns true if the proline rule is applied
+    ///
+    pub fn is_proline_rule_applied(&self) -> bool {
+        self.apply_proline_rule
+    }
+
+    /// Returns true if the N-terminal methionine is excised
+    ///
+    pub fn is_methionine_excised(&self) -> bool {
+        self.methionine_excision
+    }
+
+    /// Digests the sequence of the given entry
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - FASTA entry
+    ///
+    pub fn digest_entry(&self, entry: &Entry) -> Vec<Peptide> {
+        self.digest(entry.get_sequence())
+    }
+
+    /// Digests the given sequence. Peptides are unique by position and sorted by start and end.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - Amino acid sequence
+    ///
+    pub fn digest(&self, sequence: &str) -> Vec<Peptide> {
+        let residues = sequence.as_bytes();
+        if residues.is_empty() {
+            return Vec::new();
+        }
+        let cleavage_sites = self.enzyme.get_cleavage_sites(residues, self.apply_proline_rule);
+
+        // Positions a peptide may start or end at.
+        // The methionine excision site is an alternative start only, so the methionine is no peptide.
+        let mut starts: BTreeSet<usize> = cleavage_sites.iter().copied().collect();
+        starts.insert(0);
+        if self.methionine_excision && residues.len() > 1 && residues[0] == b'M' {
+            starts.insert(1);
+        }
+        let mut ends: BTreeSet<usize> = cleavage_sites.iter().copied().collect();
+        ends.insert(residues.len());
+
+        let count_missed_cleavages = |start: usize, end: usize| -> usize {
+            if self.enzyme.is_unspecific() {
+                return 0;
+            }
+            cleavage_sites.partition_point(|&site| site < end) - cleavage_sites.partition_point(|&site| site <= start)
+        };
+
+        // The number of missed cleavages grows with the length, so the loops stop at the first span exceeding it
+        let mut spans: BTreeSet<(usize, usize)> = BTreeSet::new();
+        for &start in starts.iter() {
+            let max_end = residues.len().min(start + self.max_length);
+            let candidate_ends: Vec<usize> = match self.specificity {
+                Specificity::Full => ends.range(start + 1..=max_end).copied().collect(),
+                Specificity::Semi => (start + 1..=max_end).collect()
+            };
+            for end in candidate_ends {
+                if count_missed_cleavages(start, end) > self.max_missed_cleavages {
+                    break;
+                }
+                spans.insert((start, end));
+            }
+        }
+        if self.specificity == Specificity::Semi {
+            for &end in ends.iter() {
+                for start in (end.saturating_sub(self.max_length)..end).rev() {
+                    if count_missed_cleavages(start, end) > self.max_missed_cleavages {
+                        break;
+                    }
+                    spans.insert((start, end));
+                }
+            }
+        }
+
+        spans.into_iter()
+            .filter(|(start, end)| (self.min_length..=self.max_length).contains(&(end - start)))
+            .filter_map(|(start, end)| Some(Peptide::new(
+                sequence.get(start..end)?.to_string(),
+                start,
+                end,
+                count_missed_cleavages(start, end)
+            )))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_sequences(peptides: &[Peptide]) -> Vec<&str> {
+        peptides.iter().map(|peptide| peptide.get_sequence()).collect()
+    }
+
+    #[test]
+    /// Digests with trypsin, with and without the proline rule and missed cleavages.
+    fn test_tryptic_digestion() {
+        let digester = Digester::new(Enzyme::trypsin())
+            .with_min_length(1)
+            .with_max_missed_cleavages(0);
+        let peptides = digester.digest("MAKPEKRLLR");
+        // No cleavage before P
+        assert_eq!(get_sequences(&peptides), vec!["MAKPEK", "R", "LLR"]);
+        assert_eq!(peptides[2].get_start(), 7);
+        assert_eq!(peptides[2].get_end(), 10);
+
+        let peptides = digester.clone().with_proline_rule(false).digest("MAKPEKRLLR");
+        assert_eq!(get_sequences(&peptides), vec!["MAK", "PEK", "R", "LLR"]);
+
+        let peptides = digester.with_max_missed_cleavages(1).with_min_length(3).digest("MAKPEKRLLR");
+        assert_eq!(get_sequences(&peptides), vec!["MAKPEK", "MAKPEKR", "RLLR", "LLR"]);
+        assert_eq!(peptides[1].get_missed_cleavages(), 1);
+    }
+
+    #[test]
+    /// Digests with Asp-N, Glu-C and without enzyme and looks up enzymes by name.
+    fn test_other_enzymes() {
+        let digester = Digester::new(Enzyme::asp_n()).with_min_length(1).with_max_missed_cleavages(0);
+        assert_eq!(get_sequences(&digester.digest("AADCCDEE")), vec!["AA", "DCC", "DEE"]);
+
+        let digester = Digester::new(Enzyme::glu_c()).with_min_length(1).with_max_missed_cleavages(0);
+        assert_eq!(get_sequences(&digester.digest("AAEPCEGG")), vec!["AAEPCE", "GG"]);
+
+        let digester = Digester::new(Enzyme::no_enzyme()).with_min_length(2).with_max_length(3);
+        assert_eq!(get_sequences(&digester.digest("ABCD")), vec!["AB", "ABC", "BC", "BCD", "CD"]);
+        assert_eq!(Enzyme::from_name("Lys-C"), Some(Enzyme::lys_c()));
+        assert_eq!(Enzyme::from_name("pepsin"), None);
+    }
+
+    #[test]
+    /// Digests semi-specifically, i.e. with one unspecific terminus.
+    fn test_semi_specific_digestion() {
+        let digester = Digester::new(Enzyme::trypsin())
+            .with_specificity(Specificity::Semi)
+            .with_min_length(3)
+            .with_max_missed_cleavages(0);
+        assert_eq!(
+            get_sequences(&digester.digest("ACDKEFGR")),
+            vec!["ACD", "ACDK", "CDK", "EFG", "EFGR", "FGR"]
+        );
+    }
+
+    #[test]
+    /// Limits the peptide length and digests with and without the N-terminal methionine.
+    fn test_length_and_methionine_excision() {
+        let digester = Digester::new(Enzyme::trypsin())
+            .with_min_length(4)
+            .with_max_length(5)
+            .with_max_missed_cleavages(0)
+            .with_methionine_excision(true);
+        let peptides = digester.digest("MACDKEFGHIKLR");
+        assert_eq!(get_sequences(&peptides), vec!["MACDK", "ACDK"]);
+        assert_eq!(peptides[1].get_start(), 1);
+        assert_eq!(peptides[1].get_missed_cleavages(), 0);
+    }
+
+    #[test]
+    /// Checks that the excised methionine itself is no peptide.
+    fn test_methionine_excision_without_min_length() {
+        let digester = Digester::new(Enzyme::trypsin())
+            .with_min_length(1)
+            .with_max_missed_cleavages(0)
+            .with_methionine_excision(true);
+        let peptides = digester.digest("MPEPTIDEKAAAR");
+        assert!(!get_sequences(&peptides).contains(&"M"));
+        assert_eq!(get_sequences(&peptides), vec!["MPEPTIDEK", "PEPTIDEK", "AAAR"]);
+    }
+
+    #[test]
+    /// Digests a long sequence semi-specifically, which is bounded by the maximum length.
+    fn test_semi_specific_digestion_of_long_sequence() {
+        let sequence = "ACDEFGHIKLMNPQRSTVWY".repeat(40);
+        let digester = Digester::new(Enzyme::no_enzyme()).with_min_length(6).with_max_length(50);
+        let full = digester.digest(&sequence);
+        let semi = digester.clone().with_specificity(Specificity::Semi).digest(&sequence);
+        assert_eq!(full.len(), 34785);
+        assert_eq!(semi, full);
+
+        // Semi-specific tryptic peptides have a cleavage site or protein terminus at one end at least
+        let digester = Digester::new(Enzyme::trypsin()).with_specificity(Specificity::Semi);
+        let cleavage_sites = Enzyme::trypsin().get_cleavage_sites(sequence.as_bytes(), true);
+        let peptides = digester.digest(&sequence);
+        assert!(!peptides.is_empty());
+        for peptide in peptides.iter() {
+            assert!((6..=50).contains(&peptide.get_sequence().len()));
+            assert!(peptide.get_missed_cleavages() <= DEFAULT_MAX_MISSED_CLEAVAGES);
+            assert!(
+                peptide.get_start() == 0 || peptide.get_end() == sequence.len()
+                    || cleavage_sites.contains(&peptide.get_start()) || cleavage_sites.contains(&peptide.get_end())
+            );
+        }
+    }
+}
@@ -0,0 +1,160 @@
+/// Side of the cleavage residue the enzyme cleaves at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleavageSide {
+    /// After the cleavage residue, e.g. trypsin
+    CTerminal,
+    /// Before the cleavage residue, e.g. Asp-N
+    NTerminal
+}
+
+/// Cleavage rule of a protease
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enzyme {
+    name: String,
+    cleavage_residues: String,
+    restriction_residues: String,
+    side: CleavageSide
+}
+
+impl Enzyme {
+    /// Creates a new enzyme.
+    /// An empty set of cleavage residues cleaves between all residues (no enzyme / unspecific).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name
+    /// * `cleavage_residues` - Residues the enzyme cleaves at, e.g. `KR`
+    /// * `restriction_residues` - Residues after the cleavage site which prevent the cleavage, e.g. `P`
+    /// * `side` - Side of the cleavage residue
+    ///
+    pub fn new(name: &str, cleavage_residues: &str, restriction_residues: &str, side: CleavageSide) -> Self {
+        Self {
+            name: name.to_string(),
+            cleavage_residues: cleavage_residues.to_string(),
+            restriction_residues: restriction_residues.to_string(),
+            side
+        }
+    }
+
+    /// Trypsin, cleaves after K and R, not before P
+    ///
+    pub fn trypsin() -> Self {
+        Self::new("Trypsin", "KR", "P", CleavageSide::CTerminal)
+    }
+
+    /// Lys-C, cleaves after K
+    ///
+    pub fn lys_c() -> Self {
+        Self::new("Lys-C", "K", "", CleavageSide::CTerminal)
+    }
+
+    /// Arg-C, cleaves after R, not before P
+    ///
+    pub fn arg_c() -> Self {
+        Self::new("Arg-C", "R", "P", CleavageSide::CTerminal)
+    }
+
+    /// Glu-C, cleaves after E, not before P
+    ///
+    pub fn glu_c() -> Self {
+        Self::new("Glu-C", "E", "P", CleavageSide::CTerminal)
+    }
+
+    /// Asp-N, cleaves before D
+    ///
+    pub fn asp_n() -> Self {
+        Self::new("Asp-N", "D", "", CleavageSide::NTerminal)
+    }
+
+    /// Chymotrypsin (high specificity), cleaves after F, W and Y, not before P
+    ///
+    pub fn chymotrypsin() -> Self {
+        Self::new("Chymotrypsin", "FWY", "P", CleavageSide::CTerminal)
+    }
+
+    /// No enzyme, cleaves between all residues
+    ///
+    pub fn no_enzyme() -> Self {
+        Self::new("No enzyme", "", "", CleavageSide::CTerminal)
+    }
+
+    /// Returns the predefined enzyme with the given name (case insensitive, `-`, `_` and spaces are ignored),
+    /// e.g. `trypsin`, `Lys-C`, `no_enzyme`
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Enzyme name
+    ///
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized_name: String = name.chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .collect::<String>()
+            .to_lowercase();
+        match normalized_name.as_str() {
+            "trypsin" => Some(Self::trypsin()),
+            "lysc" => Some(Self::lys_c()),
+            "argc" => Some(Self::arg_c()),
+            "gluc" => Some(Self::glu_c()),
+            "aspn" => Some(Self::asp_n()),
+            "chymotrypsin" => Some(Self::chymotrypsin()),
+            "noenzyme" | "unspecific" => Some(Self::no_enzyme()),
+            _ => None
+        }
+    }
+
+    /// Returns the name
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the cleavage residues
+    ///
+    pub fn get_cleavage_residues(&self) -> &str {
+        &self.cleavage_residues
+    }
+
+    /// Returns the restriction residues
+    ///
+    pub fn get_restriction_residues(&self) -> &str {
+        &self.restriction_residues
+    }
+
+    /// Returns the cleavage side
+    ///
+    pub fn get_side(&self) -> CleavageSide {
+        self.side
+    }
+
+    /// Returns true if the enzyme cleaves between all residues
+    ///
+    pub fn is_unspecific(&self) -> bool {
+        self.cleavage_residues.is_empty()
+    }
+
+    /// Returns the cleavage sites of the given sequence, i.e. the positions `p` (0 < p < len)
+    /// where the enzyme cleaves between residue `p - 1` and `p`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - Amino acid sequence
+    /// * `apply_restriction` - If false, restriction residues are ignored (e.g. proline rule)
+    ///
+    pub fn get_cleavage_sites(&self, sequence: &[u8], apply_restriction: bool) -> Vec<usize> {
+        let is_cleavage_residue = |residue: u8| self.cleavage_residues.as_bytes().contains(&residue);
+        let is_restricted = |residue: u8| apply_restriction && self.restriction_residues.as_bytes().contains(&residue);
+        (1..sequence.len())
+            .filter(|&position| {
+                if self.is_unspecific() {
+                    return true;
+                }
+                match self.side {
+                    CleavageSide::CTerminal => is_cleavage_residue(sequence[position - 1])
+                        && !is_restricted(sequence[position]),
+                    CleavageSide::NTerminal => is_cleavage_residue(sequence[position])
+                        && !is_restricted(sequence[position - 1])
+                }
+            })
+            .collect()
+    }
+}
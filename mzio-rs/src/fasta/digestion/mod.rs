@@ -0,0 +1,44 @@
+//! In-silico digestion of protein sequences
+
+pub mod digester;
+pub mod enzyme;
+pub mod peptide;
+pub mod peptide_mapping;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::path::Path;
+
+    use fallible_iterator::FallibleIterator;
+
+    use crate::fasta::entry::Entry;
+    use crate::fasta::reader::Reader;
+
+    const FASTA_FILE_PATH_STR: &str = "./test_files/fasta/partial_mouse.fasta";
+
+    #[test]
+    /// Digests a FASTA file and maps the peptides back to their proteins.
+    fn test_peptide_mapping() {
+        let digester = digester::Digester::new(enzyme::Enzyme::trypsin());
+        let reader = Reader::new(Path::new(FASTA_FILE_PATH_STR), 1024).unwrap();
+        let mapping = peptide_mapping::PeptideMapping::from_entries(&digester, reader).unwrap();
+        assert!(!mapping.is_empty());
+
+        let entries: Vec<Entry> = Reader::new(Path::new(FASTA_FILE_PATH_STR), 1024).unwrap()
+            .collect()
+            .unwrap();
+        for entry in entries.iter() {
+            for peptide in digester.digest_entry(entry) {
+                let locations = mapping.get_locations(peptide.get_sequence()).unwrap();
+                assert!(locations.iter().any(|location| location.get_accession() == entry.get_accession()
+                    && location.get_start() == peptide.get_start()));
+                for location in locations {
+                    let protein = entries.iter().find(|e| e.get_accession() == location.get_accession()).unwrap();
+                    assert_eq!(&protein.get_sequence()[location.get_start()..location.get_end()], peptide.get_sequence());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+/// Peptide resulting from the digestion of a protein
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Peptide {
+    sequence: String,
+    start: usize,
+    end: usize,
+    missed_cleavages: usize
+}
+
+impl Peptide {
+    /// Creates a new peptide
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - Amino acid sequence
+    /// * `start` - Start position in the protein (0-based, inclusive)
+    /// * `end` - End position in the protein (0-based, exclusive)
+    /// * `missed_cleavages` - Number of missed cleavages
+    ///
+    pub fn new(sequence: String, start: usize, end: usize, missed_cleavages: usize) -> Self {
+        Self {
+            sequence,
+            start,
+            end,
+            missed_cleavages
+        }
+    }
+
+    /// Returns the amino acid sequence
+    ///
+    pub fn get_sequence(&self) -> &str {
+        &self.sequence
+    }
+
+    /// Returns the start position in the protein (0-based, inclusive)
+    ///
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end position in the protein (0-based, exclusive)
+    ///
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the number of missed cleavages
+    ///
+    pub fn get_missed_cleavages(&self) -> usize {
+        self.missed_cleavages
+    }
+}
@@ -0,0 +1,147 @@
+// std imports
+use std::collections::HashMap;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+
+// internal imports
+use crate::error::{Error, Result};
+use crate::fasta::digestion::digester::Digester;
+use crate::fasta::entry::Entry;
+
+/// Location of a peptide within a protein
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeptideLocation {
+    accession: String,
+    start: usize,
+    end: usize
+}
+
+impl PeptideLocation {
+    /// Returns the protein accession
+    ///
+    pub fn get_accession(&self) -> &str {
+        &self.accession
+    }
+
+    /// Returns the start position in the protein (0-based, inclusive)
+    ///
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end position in the protein (0-based, exclusive)
+    ///
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+}
+
+/// Maps the peptides of a digested protein database to their proteins
+#[derive(Debug, Clone, Default)]
+pub struct PeptideMapping {
+    locations: HashMap<String, Vec<PeptideLocation>>
+}
+
+impl PeptideMapping {
+    /// Creates a new empty mapping
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Digests all entries, e.g. directly from `fasta::reader::Reader`, and maps their peptides.
+    ///
+    /// # Arguments
+    ///
+    /// * `digester` - Digester
+    /// * `entries` - Fallible iterator of entries
+    ///
+    pub fn from_entries<I>(digester: &Digester, mut entries: I) -> Result<Self>
+    where
+        I: FallibleIterator<Item = Entry, Error = Error>,
+    {
+        let mut mapping = Self::new();
+        while let Some(entry) = entries.next()? {
+            mapping.add_entry(digester, &entry);
+        }
+        Ok(mapping)
+    }
+
+    /// Digests the given entry and adds its peptides
+    ///
+    /// # Arguments
+    ///
+    /// * `digester` - Digester
+    /// * `entry` - FASTA entry
+    ///
+    pub fn add_entry(&mut self, digester: &Digester, entry: &Entry) {
+        for peptide in digester.digest_entry(entry) {
+            let location = PeptideLocation {
+                accession: entry.get_accession().to_string(),
+                start: peptide.get_start(),
+                end: peptide.get_end()
+            };
+            match self.locations.get_mut(peptide.get_sequence()) {
+                Some(locations) => locations.push(location),
+                None => {
+                    self.locations.insert(peptide.get_sequence().to_string(), vec![location]);
+                }
+            }
+        }
+    }
+
+    /// Returns all locations of the given peptide
+    ///
+    /// # Arguments
+    ///
+    /// * `peptide` - Peptide sequence
+    ///
+    pub fn get_locations(&self, peptide: &str) -> Option<&Vec<PeptideLocation>> {
+        self.locations.get(peptide)
+    }
+
+    /// Returns the accessions of all proteins containing the given peptide, without duplicates
+    ///
+    /// # Arguments
+    ///
+    /// * `peptide` - Peptide sequence
+    ///
+    pub fn get_accessions(&self, peptide: &str) -> Vec<&str> {
+        let mut accessions: Vec<&str> = Vec::new();
+        for location in self.locations.get(peptide).into_iter().flatten() {
+            if !accessions.contains(&location.get_accession()) {
+                accessions.push(location.get_accession());
+            }
+        }
+        accessions
+    }
+
+    /// Returns true if the given peptide belongs to exactly one protein
+    ///
+    /// # Arguments
+    ///
+    /// * `peptide` - Peptide sequence
+    ///
+    pub fn is_unique(&self, peptide: &str) -> bool {
+        self.get_accessions(peptide).len() == 1
+    }
+
+    /// Returns the number of distinct peptides
+    ///
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Returns true if no peptides are mapped
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Returns an iterator over all peptides and their locations
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Vec<PeptideLocation>)> {
+        self.locations.iter()
+    }
+}
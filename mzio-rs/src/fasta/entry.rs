@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 
 /// Keeps all information of FASTA entry
+#[derive(Debug, Clone, PartialEq)]
 pub struct Entry {
     database: String,
     accession: String,
     entry_name: String,
     protein_name: String,
     keyword_attributes: HashMap<String, String>,
-    sequence: String
+    sequence: String,
+    header: String
 }
 
 impl Entry {
@@ -29,14 +31,26 @@ impl Entry {
                 entry_name,
                 protein_name,
                 keyword_attributes,
-                sequence
+                sequence,
+                header: String::new()
             }
         }
 
+        /// Sets the raw header as read from the FASTA file (without the leading '>'),
+        /// which is written back instead of a generated UniProt header.
+        /// # Arguments
+        ///
+        /// * `header` - Raw header
+        /// 
+        pub fn with_header(mut self, header: String) -> Self {
+            self.header = header;
+            self
+        }
+
         /// Returns the database type
         ///
         pub fn get_database(&self) -> &str {
-            &self.database.as_str()
+            self.database.as_str()
         }
 
         /// Returns the accession
@@ -70,4 +84,11 @@ impl Entry {
         pub fn get_sequence(&self) -> &str {
             &self.sequence
         }
-}
\ No newline at end of file
+
+        /// Returns the raw header (without the leading '>'),
+        /// empty if the entry was not read from a file.
+        /// 
+        pub fn get_header(&self) -> &str {
+            &self.header
+        }
+}
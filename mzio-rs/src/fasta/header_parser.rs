@@ -0,0 +1,344 @@
+// std imports
+use std::collections::HashMap;
+
+// internal imports
+use crate::fasta::entry::Entry;
+
+/// Parses FASTA headers of a specific format into entries.
+pub trait HeaderParser {
+    /// Creates a new Entry from the given header and sequence.
+    /// Returns None if the header does not match the format.
+    /// The created entry keeps the raw header for lossless writing.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - A FASTA header, including the leading '>'
+    /// * `sequence` - Amino acid sequence
+    ///
+    fn create_entry(&self, header: &str, sequence: &str) -> Option<Entry>;
+}
+
+/// Parser for UniProt headers, e.g.
+/// `>sp|P27748|ACOX_CUPNH Acetoin catabolism protein X OS=Cupriavidus necator OX=381666 GN=acoX PE=4 SV=2`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniProtHeaderParser;
+
+impl UniProtHeaderParser {
+    /// Processes and adds a keyword attribute to the HashMap
+    /// # Arguments
+    ///
+    /// * `raw_attr` - Raw attributes, e.g. `key=value with spaces`
+    /// * `keyword_attributes` - Additional keyword attributes
+    ///
+    fn prep_and_add_attribute_to_keyword_attributes(raw_attr: &str, keyword_attributes: &mut HashMap<String, String>) {
+        if let Some((key, value)) = raw_attr.split_once('=') {
+            keyword_attributes.insert(
+                key.to_string(),
+                value.to_string()
+            );
+        }
+    }
+}
+
+impl HeaderParser for UniProtHeaderParser {
+    fn create_entry(&self, header: &str, sequence: &str) -> Option<Entry> {
+        let raw_header = header.strip_prefix('>')?;
+        // Split by '|' and extract database and accession
+        let mut header_split = raw_header.splitn(3, '|');
+        let database: String = header_split.next()?.to_string();
+        let accession: String = header_split.next()?.to_string();
+
+        // Split by ' '
+        let mut description_split = header_split.next()?.split(' ').peekable();
+
+        // Extract entry name
+        let entry_name: String = description_split.next()?.to_string();
+        if database.is_empty() || accession.is_empty() || entry_name.is_empty() {
+            return None;
+        }
+        // Add chunks to protein name until first string with '=' occurs (begin of keyword attributes)
+        let mut protein_name_chunks: Vec<&str> = Vec::new();
+        while let Some(chunk) = description_split.next_if(|chunk| !chunk.contains('=')) {
+            protein_name_chunks.push(chunk);
+        }
+        let protein_name: String = protein_name_chunks.join(" ");
+        // Extract keyword attributes.
+        // Every time a chunk does not start a new attribute (chunk does not contains '=')
+        // add the chunk to the current attribute, otherwise process the current attribute
+        // and begin a new one
+        let mut keyword_attributes: HashMap<String, String> = HashMap::new();
+        let mut current_attr: String = String::new();
+        for chunk in description_split {
+            if !chunk.contains('=') {
+                current_attr.push(' ');
+                current_attr.push_str(chunk);
+            } else {
+                Self::prep_and_add_attribute_to_keyword_attributes(
+                    &current_attr,
+                    &mut keyword_attributes
+                );
+                current_attr = chunk.to_string();
+            }
+        }
+        // Process the remaining attribute
+        Self::prep_and_add_attribute_to_keyword_attributes(
+            &current_attr,
+            &mut keyword_attributes
+        );
+        Some(Entry::new(
+            database,
+            accession,
+            entry_name,
+            protein_name,
+            keyword_attributes,
+            sequence.replace('\n', "")
+        ).with_header(raw_header.to_string()))
+    }
+}
+
+/// Parser for NCBI headers, both RefSeq style `>NP_001234.1 protein name [Homo sapiens]`
+/// and the legacy GI style `>gi|4504347|ref|NP_000549.1| protein name [Homo sapiens]`.
+/// The database is the NCBI database tag (e.g. `ref`, `gb`), the organism is stored as keyword attribute `OS`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NcbiHeaderParser;
+
+impl NcbiHeaderParser {
+    /// Returns true if the accession looks like a RefSeq accession, e.g. `NP_001234.1` or `XP_12345`
+    ///
+    /// # Arguments
+    ///
+    /// * `accession` - Accession
+    ///
+    fn is_refseq_accession(accession: &str) -> bool {
+        let Some((prefix, number)) = accession.split_once('_') else {
+            return false;
+        };
+        let number = number.split_once('.').map_or(number, |(number, _)| number);
+        prefix.len() == 2
+            && prefix.chars().all(|c| c.is_ascii_uppercase())
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+    }
+}
+
+impl HeaderParser for NcbiHeaderParser {
+    fn create_entry(&self, header: &str, sequence: &str) -> Option<Entry> {
+        let raw_header = header.strip_prefix('>')?;
+        let (identifier, description) = raw_header.split_once(' ').unwrap_or((raw_header, ""));
+        let (database, accession) = if let Some(gi_identifier) = identifier.strip_prefix("gi|") {
+            // gi|<gi number>|<database>|<accession>|<locus>
+            let mut identifier_split = gi_identifier.split('|');
+            identifier_split.next()?;
+            let database = identifier_split.next()?;
+            let accession = identifier_split.next()?;
+            (database, accession)
+        } else if Self::is_refseq_accession(identifier) {
+            ("ref", identifier)
+        } else {
+            return None;
+        };
+        if accession.is_empty() {
+            return None;
+        }
+
+        let mut keyword_attributes: HashMap<String, String> = HashMap::new();
+        let mut protein_name = description.trim();
+        // Organism is appended in square brackets
+        if protein_name.ends_with(']') {
+            if let Some(organism_start) = protein_name.rfind('[') {
+                let organism = &protein_name[organism_start + 1..protein_name.len() - 1];
+                keyword_attributes.insert("OS".to_string(), organism.to_string());
+                protein_name = protein_name[..organism_start].trim_end();
+            }
+        }
+
+        Some(Entry::new(
+            database.to_string(),
+            accession.to_string(),
+            String::new(),
+            protein_name.to_string(),
+            keyword_attributes,
+            sequence.replace('\n', "")
+        ).with_header(raw_header.to_string()))
+    }
+}
+
+/// Parser for Ensembl headers, e.g.
+/// `>ENSP00000354587.3 pep chromosome:GRCh38:X:100:200:1 gene:ENSG00000198888.2 transcript:ENST00000361390.2 gene_symbol:MT-ND1 description:NADH dehydrogenase subunit 1`
+/// and GENCODE headers, e.g.
+/// `>ENSP00000493376.2|ENST00000641515.2|ENSG00000186092.7|OTTHUMG00000001094.4|OTTHUMT00000003223.4|OR4F5-201|OR4F5|326`.
+/// Key-value pairs are stored as keyword attributes, the gene symbol is used as entry name
+/// and the description as protein name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnsemblHeaderParser;
+
+impl EnsemblHeaderParser {
+    /// Keys of the GENCODE header fields following the accession
+    const GENCODE_KEYS: [&'static str; 7] = [
+        "transcript",
+        "gene",
+        "havana_gene",
+        "havana_transcript",
+        "transcript_symbol",
+        "gene_symbol",
+        "length"
+    ];
+}
+
+impl HeaderParser for EnsemblHeaderParser {
+    fn create_entry(&self, header: &str, sequence: &str) -> Option<Entry> {
+        let raw_header = header.strip_prefix('>')?;
+        if !raw_header.starts_with("ENS") {
+            return None;
+        }
+        let mut keyword_attributes: HashMap<String, String> = HashMap::new();
+        let mut protein_name = String::new();
+
+        let (identifier, description) = raw_header.split_once(' ').unwrap_or((raw_header, ""));
+        let mut identifier_split = identifier.split('|');
+        let accession = identifier_split.next()?.to_string();
+        // GENCODE
+        for (key, value) in Self::GENCODE_KEYS.iter().zip(identifier_split) {
+            if !value.is_empty() && value != "-" {
+                keyword_attributes.insert(key.to_string(), value.to_string());
+            }
+        }
+        // Ensembl, everything after `description:` belongs to the description
+        let (attributes, ensembl_description) = match description.split_once("description:") {
+            Some((attributes, ensembl_description)) => (attributes, Some(ensembl_description)),
+            None => (description, None)
+        };
+        for chunk in attributes.split_ascii_whitespace() {
+            match chunk.split_once(':') {
+                Some((key, value)) => keyword_attributes.insert(key.to_string(), value.to_string()),
+                None => keyword_attributes.insert("type".to_string(), chunk.to_string())
+            };
+        }
+        if let Some(ensembl_description) = ensembl_description {
+            protein_name = ensembl_description.trim().to_string();
+        }
+        let entry_name = keyword_attributes.get("gene_symbol").cloned().unwrap_or_default();
+
+        Some(Entry::new(
+            "ensembl".to_string(),
+            accession,
+            entry_name,
+            protein_name,
+            keyword_attributes,
+            sequence.replace('\n', "")
+        ).with_header(raw_header.to_string()))
+    }
+}
+
+/// Fallback parser for arbitrary headers, e.g. contaminant or in-house databases.
+/// The first word is used as accession and the remaining header as protein name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GenericHeaderParser;
+
+impl HeaderParser for GenericHeaderParser {
+    fn create_entry(&self, header: &str, sequence: &str) -> Option<Entry> {
+        let raw_header = header.strip_prefix('>')?;
+        let (accession, protein_name) = raw_header.split_once(|c: char| c.is_ascii_whitespace())
+            .unwrap_or((raw_header, ""));
+        if accession.is_empty() {
+            return None;
+        }
+        Some(Entry::new(
+            String::new(),
+            accession.to_string(),
+            String::new(),
+            protein_name.trim().to_string(),
+            HashMap::new(),
+            sequence.replace('\n', "")
+        ).with_header(raw_header.to_string()))
+    }
+}
+
+/// Detects the header format for each header by trying
+/// Ensembl/GENCODE, NCBI, UniProt and finally the generic format.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutoHeaderParser;
+
+impl HeaderParser for AutoHeaderParser {
+    fn create_entry(&self, header: &str, sequence: &str) -> Option<Entry> {
+        EnsemblHeaderParser.create_entry(header, sequence)
+            .or_else(|| NcbiHeaderParser.create_entry(header, sequence))
+            .or_else(|| UniProtHeaderParser.create_entry(header, sequence))
+            .or_else(|| GenericHeaderParser.create_entry(header, sequence))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UNIPROT_HEADER: &str = ">sp|P27748|ACOX_CUPNH Acetoin catabolism protein X OS=Cupriavidus necator OX=381666 GN=acoX PE=4 SV=2";
+    const REFSEQ_HEADER: &str = ">NP_000549.1 hemoglobin subunit alpha [Homo sapiens]";
+    const GI_HEADER: &str = ">gi|4504347|ref|NP_000549.1| hemoglobin subunit alpha [Homo sapiens]";
+    const ENSEMBL_HEADER: &str = ">ENSP00000354687.2 pep chromosome:GRCh38:MT:3307:4262:1 gene:ENSG00000198888.2 transcript:ENST00000361390.2 gene_biotype:protein_coding transcript_biotype:protein_coding gene_symbol:MT-ND1 description:mitochondrially encoded NADH:ubiquinone oxidoreductase core subunit 1 [Source:HGNC Symbol;Acc:HGNC:7455]";
+    const GENCODE_HEADER: &str = ">ENSP00000493376.2|ENST00000641515.2|ENSG00000186092.7|OTTHUMG00000001094.4|OTTHUMT00000003223.4|OR4F5-201|OR4F5|326";
+    const CONTAMINANT_HEADER: &str = ">CON__P00761 SWISS-PROT:P00761 TRYP_PIG Trypsin - Sus scrofa (Pig).";
+    const TEST_SEQUENCE: &str = "MGHA";
+
+    #[test]
+    /// Tests parsing of UniProt headers.
+    fn test_uniprot_header() {
+        let entry = UniProtHeaderParser.create_entry(UNIPROT_HEADER, TEST_SEQUENCE).unwrap();
+        assert_eq!(entry.get_database(), "sp");
+        assert_eq!(entry.get_accession(), "P27748");
+        assert_eq!(entry.get_entry_name(), "ACOX_CUPNH");
+        assert_eq!(entry.get_protein_name(), "Acetoin catabolism protein X");
+        assert_eq!(entry.get_keyword_attributes().get("OS").unwrap(), "Cupriavidus necator");
+        assert_eq!(entry.get_header(), &UNIPROT_HEADER[1..]);
+        assert!(UniProtHeaderParser.create_entry(REFSEQ_HEADER, TEST_SEQUENCE).is_none());
+    }
+
+    #[test]
+    /// Tests parsing of RefSeq and GI headers.
+    fn test_ncbi_header() {
+        for header in [REFSEQ_HEADER, GI_HEADER] {
+            let entry = NcbiHeaderParser.create_entry(header, TEST_SEQUENCE).unwrap();
+            assert_eq!(entry.get_database(), "ref");
+            assert_eq!(entry.get_accession(), "NP_000549.1");
+            assert_eq!(entry.get_protein_name(), "hemoglobin subunit alpha");
+            assert_eq!(entry.get_keyword_attributes().get("OS").unwrap(), "Homo sapiens");
+        }
+        assert!(NcbiHeaderParser.create_entry(CONTAMINANT_HEADER, TEST_SEQUENCE).is_none());
+    }
+
+    #[test]
+    /// Tests parsing of Ensembl and GENCODE headers.
+    fn test_ensembl_header() {
+        let entry = EnsemblHeaderParser.create_entry(ENSEMBL_HEADER, TEST_SEQUENCE).unwrap();
+        assert_eq!(entry.get_accession(), "ENSP00000354687.2");
+        assert_eq!(entry.get_entry_name(), "MT-ND1");
+        assert_eq!(entry.get_protein_name(), "mitochondrially encoded NADH:ubiquinone oxidoreductase core subunit 1 [Source:HGNC Symbol;Acc:HGNC:7455]");
+        assert_eq!(entry.get_keyword_attributes().get("gene").unwrap(), "ENSG00000198888.2");
+        assert_eq!(entry.get_keyword_attributes().get("type").unwrap(), "pep");
+
+        let entry = EnsemblHeaderParser.create_entry(GENCODE_HEADER, TEST_SEQUENCE).unwrap();
+        assert_eq!(entry.get_accession(), "ENSP00000493376.2");
+        assert_eq!(entry.get_entry_name(), "OR4F5");
+        assert_eq!(entry.get_keyword_attributes().get("transcript").unwrap(), "ENST00000641515.2");
+        assert_eq!(entry.get_keyword_attributes().get("length").unwrap(), "326");
+    }
+
+    #[test]
+    /// Tests the automatic detection of the header format.
+    fn test_auto_header() {
+        let expected_accessions = [
+            (UNIPROT_HEADER, "P27748"),
+            (REFSEQ_HEADER, "NP_000549.1"),
+            (GI_HEADER, "NP_000549.1"),
+            (ENSEMBL_HEADER, "ENSP00000354687.2"),
+            (GENCODE_HEADER, "ENSP00000493376.2"),
+            (CONTAMINANT_HEADER, "CON__P00761")
+        ];
+        for (header, expected_accession) in expected_accessions {
+            let entry = AutoHeaderParser.create_entry(header, TEST_SEQUENCE).unwrap();
+            assert_eq!(entry.get_accession(), expected_accession);
+            assert_eq!(entry.get_header(), &header[1..]);
+        }
+        assert!(AutoHeaderParser.create_entry(">", TEST_SEQUENCE).is_none());
+    }
+}
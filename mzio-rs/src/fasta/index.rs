@@ -0,0 +1,384 @@
+// std imports
+use std::collections::HashMap;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+// internal imports
+use crate::error::{Error, Position, Result};
+use crate::fasta::header_parser::{HeaderParser, UniProtHeaderParser};
+use crate::index::{append_extension, open_uncompressed, parse_int, read_tsv};
+use crate::line_reader::LineReader;
+
+/// Extension of the samtools compatible FASTA index
+pub const FAI_EXTENSION: &str = "fai";
+
+/// Extension of the accession sidecar, an optional cache of the header offsets
+pub const ACCESSION_INDEX_EXTENSION: &str = "acc";
+
+/// Record of a samtools compatible FASTA index (`.fai`) extended by the accession
+/// and the offset of the header line, which is unknown if the record was read from a plain `.fai` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaiRecord {
+    name: String,
+    length: u64,
+    offset: u64,
+    line_bases: u64,
+    line_width: u64,
+    accession: String,
+    header_offset: Option<u64>
+}
+
+impl FaiRecord {
+    /// Returns the name, i.e. the header up to the first whitespace (without '>')
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the sequence length
+    ///
+    pub fn get_length(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns the byte offset of the sequence
+    ///
+    pub fn get_offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the number of residues per line
+    ///
+    pub fn get_line_bases(&self) -> u64 {
+        self.line_bases
+    }
+
+    /// Returns the number of bytes per line, including the line ending
+    ///
+    pub fn get_line_width(&self) -> u64 {
+        self.line_width
+    }
+
+    /// Returns the accession, e.g. `P27748` for UniProt headers, otherwise the name
+    ///
+    pub fn get_accession(&self) -> &str {
+        &self.accession
+    }
+
+    /// Returns the byte offset of the header line, None if the record was read
+    /// from a `.fai` file without accession sidecar
+    ///
+    pub fn get_header_offset(&self) -> Option<u64> {
+        self.header_offset
+    }
+
+    /// Returns the byte offset of the residue at the given position
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Residue position (0-based)
+    ///
+    pub fn get_residue_offset(&self, position: u64) -> u64 {
+        if self.line_bases == 0 {
+            return self.offset;
+        }
+        self.offset + position / self.line_bases * self.line_width + position % self.line_bases
+    }
+}
+
+/// Index of a FASTA file, persisted as samtools compatible `.fai` file
+/// and an accession sidecar (`.acc`) containing the accession and header offset of each record.
+/// The sidecar is optional, a plain `.fai` file (e.g. created by `samtools faidx`) is sufficient.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FastaIndex {
+    records: Vec<FaiRecord>,
+    names: HashMap<String, usize>,
+    accessions: HashMap<String, usize>
+}
+
+impl FastaIndex {
+    /// Creates a new index from the given records
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - Records in file order
+    ///
+    pub fn new(records: Vec<FaiRecord>) -> Self {
+        let names = records.iter().enumerate()
+            .map(|(idx, record)| (record.name.clone(), idx))
+            .collect();
+        let accessions = records.iter().enumerate()
+            .map(|(idx, record)| (record.accession.clone(), idx))
+            .collect();
+        Self {
+            records,
+            names,
+            accessions
+        }
+    }
+
+    /// Returns the path of the `.fai` file for the given FASTA file
+    ///
+    /// # Arguments
+    ///
+    /// * `fasta_file_path` - Path to FASTA file
+    ///
+    pub fn get_fai_path(fasta_file_path: &Path) -> PathBuf {
+        append_extension(fasta_file_path, FAI_EXTENSION)
+    }
+
+    /// Returns the path of the accession sidecar for the given FASTA file
+    ///
+    /// # Arguments
+    ///
+    /// * `fasta_file_path` - Path to FASTA file
+    ///
+    pub fn get_accession_index_path(fasta_file_path: &Path) -> PathBuf {
+        append_extension(fasta_file_path, ACCESSION_INDEX_EXTENSION)
+    }
+
+    /// Returns the accession for the given name, e.g. `P27748` for `sp|P27748|ACOX_CUPNH`,
+    /// or the name itself if it is not a UniProt name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name, i.e. the header up to the first whitespace (without '>')
+    ///
+    fn get_accession_from_name(name: &str) -> String {
+        UniProtHeaderParser.create_entry(&format!(">{}", name), "")
+            .map(|entry| entry.get_accession().to_string())
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    /// Builds the index by scanning the given FASTA file.
+    /// Compressed files are not supported, as they cannot be accessed randomly.
+    ///
+    /// # Arguments
+    ///
+    /// * `fasta_file_path` - Path to FASTA file
+    ///
+    pub fn build(fasta_file_path: &Path) -> Result<Self> {
+        Self::build_from_line_reader(LineReader::new(
+            open_uncompressed(fasta_file_path)?,
+            Some(fasta_file_path.to_path_buf())
+        ))
+    }
+
+    /// Builds the index by scanning the given FASTA content.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Buffered reader providing uncompressed FASTA content
+    ///
+    pub fn build_from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        Self::build_from_line_reader(LineReader::new(reader, None))
+    }
+
+    /// Builds the index, checking that all sequence lines of a record have the same length
+    /// except the last one, as required for the offset calculation.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Line reader providing the FASTA content
+    ///
+    fn build_from_line_reader<R: BufRead>(mut internal_reader: LineReader<R>) -> Result<Self> {
+        let mut records: Vec<FaiRecord> = Vec::new();
+        let mut current: Option<FaiRecord> = None;
+        // Set if the current record had a line shorter than the line width, which must be the last one
+        let mut is_last_line_read = false;
+        let mut line = String::new();
+        loop {
+            let num_bytes = internal_reader.read_line(&mut line)? as u64;
+            if num_bytes == 0 {
+                break;
+            }
+            let position = internal_reader.get_position();
+            if line.starts_with('>') {
+                records.extend(current.take());
+                let header = line.trim_end();
+                let name = header[1..].split_ascii_whitespace().next().unwrap_or("").to_string();
+                let accession = Self::get_accession_from_name(&name);
+                let header_offset = position.get_byte_offset().unwrap_or_default();
+                current = Some(FaiRecord {
+                    name,
+                    length: 0,
+                    offset: header_offset + num_bytes,
+                    line_bases: 0,
+                    line_width: 0,
+                    accession,
+                    header_offset: Some(header_offset)
+                });
+                is_last_line_read = false;
+                continue;
+            }
+            let record = match current.as_mut() {
+                Some(record) => record,
+                None if line.trim().is_empty() => continue,
+                None => return Err(Error::MissingHeader {
+                    line: line.trim().to_string(),
+                    position
+                })
+            };
+            let line_bases = line.trim_end().len() as u64;
+            if line_bases == 0 {
+                is_last_line_read = true;
+                continue;
+            }
+            if record.line_bases == 0 {
+                record.line_bases = line_bases;
+                record.line_width = num_bytes;
+            } else if is_last_line_read || line_bases > record.line_bases
+                || (num_bytes > line_bases && num_bytes - line_bases != record.line_width - record.line_bases) {
+                return Err(Error::InvalidIndex {
+                    message: format!("inconsistent line length in sequence of `{}`", record.name),
+                    position
+                });
+            }
+            is_last_line_read = line_bases < record.line_bases;
+            record.length += line_bases;
+        }
+        records.extend(current);
+        Ok(Self::new(records))
+    }
+
+    /// Reads the index of the given FASTA file, i.e. the `.fai` file and the accession sidecar if it exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `fasta_file_path` - Path to FASTA file
+    ///
+    pub fn read(fasta_file_path: &Path) -> Result<Self> {
+        let accession_index_path = Self::get_accession_index_path(fasta_file_path);
+        Self::read_from_paths(
+            &Self::get_fai_path(fasta_file_path),
+            Some(accession_index_path.as_path()).filter(|path| path.is_file())
+        )
+    }
+
+    /// Reads the index from the given `.fai` file and accession sidecar.
+    /// Without sidecar the accessions are derived from the names and the header offsets are unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `fai_file_path` - Path to `.fai` file
+    /// * `accession_index_path` - Path to accession sidecar, optional
+    ///
+    pub fn read_from_paths(fai_file_path: &Path, accession_index_path: Option<&Path>) -> Result<Self> {
+        let mut records: Vec<FaiRecord> = Vec::new();
+        for (fields, position) in read_tsv(fai_file_path, 5, false)? {
+            records.push(FaiRecord {
+                name: fields[0].clone(),
+                length: parse_int(&fields[1], &position)?,
+                offset: parse_int(&fields[2], &position)?,
+                line_bases: parse_int(&fields[3], &position)?,
+                line_width: parse_int(&fields[4], &position)?,
+                accession: Self::get_accession_from_name(&fields[0]),
+                header_offset: None
+            });
+        }
+        let Some(accession_index_path) = accession_index_path else {
+            return Ok(Self::new(records));
+        };
+        let accession_lines = read_tsv(accession_index_path, 2, false)?;
+        if accession_lines.len() != records.len() {
+            return Err(Error::InvalidIndex {
+                message: format!("{} accessions for {} records", accession_lines.len(), records.len()),
+                position: Position::from_path(accession_index_path)
+            });
+        }
+        for (record, (fields, position)) in records.iter_mut().zip(accession_lines) {
+            record.accession = fields[0].clone();
+            record.header_offset = Some(parse_int(&fields[1], &position)?);
+        }
+        Ok(Self::new(records))
+    }
+
+    /// Writes the `.fai` file next to the given FASTA file,
+    /// as well as the accession sidecar if all header offsets are known.
+    ///
+    /// # Arguments
+    ///
+    /// * `fasta_file_path` - Path to FASTA file
+    ///
+    pub fn write(&self, fasta_file_path: &Path) -> Result<()> {
+        let accession_index_path = Self::get_accession_index_path(fasta_file_path);
+        let has_header_offsets = self.records.iter().all(|record| record.header_offset.is_some());
+        self.write_to_paths(
+            &Self::get_fai_path(fasta_file_path),
+            Some(accession_index_path.as_path()).filter(|_| has_header_offsets)
+        )
+    }
+
+    /// Writes the `.fai` file and accession sidecar to the given paths.
+    ///
+    /// # Arguments
+    ///
+    /// * `fai_file_path` - Path to `.fai` file
+    /// * `accession_index_path` - Path to accession sidecar, optional
+    ///
+    pub fn write_to_paths(&self, fai_file_path: &Path, accession_index_path: Option<&Path>) -> Result<()> {
+        let mut fai_content = String::new();
+        for record in self.records.iter() {
+            fai_content.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                record.name, record.length, record.offset, record.line_bases, record.line_width
+            ));
+        }
+        std::fs::write(fai_file_path, fai_content)
+            .map_err(|err| Error::io_with_path(err, fai_file_path))?;
+        let Some(accession_index_path) = accession_index_path else {
+            return Ok(());
+        };
+        let mut accession_content = String::new();
+        for record in self.records.iter() {
+            let Some(header_offset) = record.header_offset else {
+                return Err(Error::InvalidIndex {
+                    message: format!("header offset of `{}` is unknown", record.name),
+                    position: Position::from_path(accession_index_path)
+                });
+            };
+            accession_content.push_str(&format!("{}\t{}\n", record.accession, header_offset));
+        }
+        std::fs::write(accession_index_path, accession_content)
+            .map_err(|err| Error::io_with_path(err, accession_index_path))?;
+        Ok(())
+    }
+
+    /// Returns all records in file order
+    ///
+    pub fn get_records(&self) -> &Vec<FaiRecord> {
+        &self.records
+    }
+
+    /// Returns the record with the given name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name, i.e. the header up to the first whitespace
+    ///
+    pub fn get_record_by_name(&self, name: &str) -> Option<&FaiRecord> {
+        self.names.get(name).map(|idx| &self.records[*idx])
+    }
+
+    /// Returns the record with the given accession
+    ///
+    /// # Arguments
+    ///
+    /// * `accession` - Accession
+    ///
+    pub fn get_record_by_accession(&self, accession: &str) -> Option<&FaiRecord> {
+        self.accessions.get(accession).map(|idx| &self.records[*idx])
+    }
+
+    /// Returns the number of records
+    ///
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns true if the index has no records
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
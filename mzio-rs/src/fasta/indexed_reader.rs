@@ -0,0 +1,257 @@
+// std imports
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, SeekFrom};
+use std::path::{Path, PathBuf};
+
+// internal imports
+use crate::error::{Error, Position, Result};
+use crate::fasta::entry::Entry;
+use crate::fasta::header_parser::{HeaderParser, UniProtHeaderParser};
+use crate::fasta::index::{FaiRecord, FastaIndex};
+
+/// Number of bytes read at once when searching the header line of a record without header offset
+const HEADER_SEARCH_CHUNK_SIZE: u64 = 256;
+
+/// Reader for random access to the entries of an uncompressed FASTA file using a `FastaIndex`.
+pub struct IndexedReader<R: BufRead + Seek> {
+    internal_reader: R,
+    file_path: Option<PathBuf>,
+    index: FastaIndex,
+    header_parser: Box<dyn HeaderParser + Send>
+}
+
+impl IndexedReader<BufReader<File>> {
+    /// Creates a new IndexedReader for the given FASTA file.
+    /// The index is read from the `.fai` file (and the accession sidecar if it exists),
+    /// otherwise it is built (see `FastaIndex::write()` to persist it).
+    ///
+    /// # Arguments
+    ///
+    /// * `fasta_file_path` - Path to FASTA file
+    ///
+    pub fn new(fasta_file_path: &Path) -> Result<Self> {
+        let index = if FastaIndex::get_fai_path(fasta_file_path).is_file() {
+            FastaIndex::read(fasta_file_path)?
+        } else {
+            FastaIndex::build(fasta_file_path)?
+        };
+        Self::with_index(fasta_file_path, index)
+    }
+
+    /// Creates a new IndexedReader for the given FASTA file and index.
+    ///
+    /// # Arguments
+    ///
+    /// * `fasta_file_path` - Path to FASTA file
+    /// * `index` - Index of the FASTA file
+    ///
+    pub fn with_index(fasta_file_path: &Path, index: FastaIndex) -> Result<Self> {
+        let fasta_file = File::open(fasta_file_path)
+            .map_err(|err| Error::io_with_path(err, fasta_file_path))?;
+        let mut reader = Self::from_reader(BufReader::new(fasta_file), index);
+        reader.file_path = Some(fasta_file_path.to_path_buf());
+        Ok(reader)
+    }
+}
+
+impl<R: BufRead + Seek> IndexedReader<R> {
+    /// Creates a new IndexedReader from any buffered and seekable source
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the uncompressed FASTA content
+    /// * `index` - Index of the content
+    ///
+    pub fn from_reader(internal_reader: R, index: FastaIndex) -> Self {
+        Self {
+            internal_reader,
+            file_path: None,
+            index,
+            header_parser: Box::new(UniProtHeaderParser)
+        }
+    }
+
+    /// Sets the parser used to create entries from the headers
+    ///
+    /// # Arguments
+    ///
+    /// * `header_parser` - Header parser
+    ///
+    pub fn with_header_parser<P: HeaderParser + Send + 'static>(mut self, header_parser: P) -> Self {
+        self.header_parser = Box::new(header_parser);
+        self
+    }
+
+    /// Returns the index
+    ///
+    pub fn get_index(&self) -> &FastaIndex {
+        &self.index
+    }
+
+    /// Returns the entry with the given accession, None if the accession is not indexed.
+    ///
+    /// # Arguments
+    ///
+    /// * `accession` - Accession, e.g. `P27748`
+    ///
+    pub fn get_entry_by_accession(&mut self, accession: &str) -> Result<Option<Entry>> {
+        match self.index.get_record_by_accession(accession).cloned() {
+            Some(record) => self.read_entry(&record).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    /// Returns the entry with the given name, None if the name is not indexed.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name, i.e. the header up to the first whitespace, e.g. `sp|P27748|ACOX_CUPNH`
+    ///
+    pub fn get_entry_by_name(&mut self, name: &str) -> Result<Option<Entry>> {
+        match self.index.get_record_by_name(name).cloned() {
+            Some(record) => self.read_entry(&record).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    /// Returns the entry at the given position in the file, None if the position is out of range.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Position of the entry (0-based)
+    ///
+    pub fn get_entry_by_position(&mut self, position: usize) -> Result<Option<Entry>> {
+        match self.index.get_records().get(position).cloned() {
+            Some(record) => self.read_entry(&record).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    /// Returns a part of the sequence with the given name, e.g. for samtools like region queries.
+    /// The range is clipped to the sequence length.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name, i.e. the header up to the first whitespace
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    pub fn get_sequence_region(&mut self, name: &str, start: u64, end: u64) -> Result<Option<String>> {
+        match self.index.get_record_by_name(name).cloned() {
+            Some(record) => {
+                let end = end.min(record.get_length());
+                let start = start.min(end);
+                self.read_sequence(&record, start, end).map(Some)
+            },
+            None => Ok(None)
+        }
+    }
+
+    /// Returns the position of the given byte offset for error reporting
+    ///
+    /// # Arguments
+    ///
+    /// * `byte_offset` - Byte offset
+    ///
+    fn get_position(&self, byte_offset: u64) -> Position {
+        Position::new(self.file_path.clone(), None, Some(byte_offset))
+    }
+
+    /// Reads the residues in the given range of the record
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - Index record
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    fn read_sequence(&mut self, record: &FaiRecord, start: u64, end: u64) -> Result<String> {
+        if start >= end {
+            return Ok(String::new());
+        }
+        let start_offset = record.get_residue_offset(start);
+        // Offset after the last residue, so no trailing line ending is read
+        let end_offset = record.get_residue_offset(end - 1) + 1;
+        let mut raw_sequence = vec![0; (end_offset - start_offset) as usize];
+        self.internal_reader.seek(SeekFrom::Start(start_offset))
+            .and_then(|_| self.internal_reader.read_exact(&mut raw_sequence))
+            .map_err(|source| Error::Io {
+                source,
+                position: self.get_position(start_offset)
+            })?;
+        raw_sequence.retain(|byte| !byte.is_ascii_whitespace());
+        String::from_utf8(raw_sequence).map_err(|_| Error::InvalidIndex {
+            message: format!("sequence of `{}` is not valid UTF-8", record.get_name()),
+            position: self.get_position(start_offset)
+        })
+    }
+
+    /// Returns the byte offset of the record's header line. If it is not indexed,
+    /// it is the start of the line preceding the sequence, which is searched backwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - Index record
+    ///
+    fn find_header_offset(&mut self, record: &FaiRecord) -> Result<u64> {
+        if let Some(header_offset) = record.get_header_offset() {
+            return Ok(header_offset);
+        }
+        let mut start = record.get_offset();
+        let mut preceding_bytes: Vec<u8> = Vec::new();
+        loop {
+            let chunk_start = start.saturating_sub(HEADER_SEARCH_CHUNK_SIZE);
+            let mut chunk = vec![0; (start - chunk_start) as usize];
+            self.internal_reader.seek(SeekFrom::Start(chunk_start))
+                .and_then(|_| self.internal_reader.read_exact(&mut chunk))
+                .map_err(|source| Error::Io {
+                    source,
+                    position: self.get_position(chunk_start)
+                })?;
+            chunk.append(&mut preceding_bytes);
+            preceding_bytes = chunk;
+            start = chunk_start;
+            // The header line ends with the line ending directly before the sequence
+            let header_line = preceding_bytes.strip_suffix(b"\n").unwrap_or(&preceding_bytes);
+            if let Some(line_start) = header_line.iter().rposition(|byte| *byte == b'\n') {
+                return Ok(start + line_start as u64 + 1);
+            }
+            if start == 0 {
+                return Ok(0);
+            }
+        }
+    }
+
+    /// Reads header and sequence of the record and creates the entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - Index record
+    ///
+    fn read_entry(&mut self, record: &FaiRecord) -> Result<Entry> {
+        let header_offset = self.find_header_offset(record)?;
+        let mut header = String::new();
+        self.internal_reader.seek(SeekFrom::Start(header_offset))
+            .and_then(|_| self.internal_reader.read_line(&mut header))
+            .map_err(|source| Error::Io {
+                source,
+                position: self.get_position(header_offset)
+            })?;
+        let header = header.trim_end();
+        if !header.starts_with('>') {
+            return Err(Error::InvalidIndex {
+                message: format!("no header at offset {}, the index may be outdated", header_offset),
+                position: self.get_position(header_offset)
+            });
+        }
+        let sequence = self.read_sequence(record, 0, record.get_length())?;
+        match self.header_parser.create_entry(header, &sequence) {
+            Some(entry) => Ok(entry),
+            None => Err(Error::MalformedHeader {
+                header: header.to_string(),
+                position: self.get_position(header_offset)
+            })
+        }
+    }
+}
@@ -1,4 +1,10 @@
+pub mod decoy;
+pub mod digestion;
 pub mod entry;
+pub mod header_parser;
+pub mod index;
+pub mod indexed_reader;
+pub mod peff;
 pub mod reader;
 pub mod writer;
 
@@ -9,9 +15,15 @@ mod test {
     use std::fs;
     use std::path::Path;
 
+    use fallible_iterator::FallibleIterator;
+
     const FASTA_FILE_PATH_STR: &'static str = "./test_files/fasta/partial_mouse.fasta";
     const EXPECTED_NUM_PROTEINS: usize = 10;
     const TEMP_FASTA_PATH_STR: &'static str = "./test_files/fasta/partial_mouse.fasta.tmp";
+    const TEMP_INDEX_FASTA_PATH_STR: &str = "./test_files/fasta/partial_mouse.index.tmp.fasta";
+    const TEMP_FAI_FASTA_PATH_STR: &str = "./test_files/fasta/partial_mouse.fai.tmp.fasta";
+    #[cfg(feature = "gzip")]
+    const TEMP_GZIP_FASTA_PATH_STR: &str = "./test_files/fasta/partial_mouse.fasta.tmp.gz";
 
     #[test]
     /// Reads a FASTA file, parses the proteins,
@@ -25,7 +37,7 @@ mod test {
             1024
         ).unwrap();
 
-        let entries: Vec<entry::Entry> = reader.into_iter().collect();
+        let entries: Vec<entry::Entry> = reader.collect().unwrap();
         assert_eq!(entries.len(), EXPECTED_NUM_PROTEINS);
 
         let mut writer = writer::Writer::new(
@@ -43,4 +55,100 @@ mod test {
             tmp_fasta_content.as_str()
         );
     }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    /// Reads a FASTA file, writes it gzip compressed into a temporary file
+    /// and reads the compressed file back.
+    fn test_reading_and_writing_compressed() {
+        let fasta_file_path = Path::new(FASTA_FILE_PATH_STR);
+        let tmp_fasta_file_path = Path::new(TEMP_GZIP_FASTA_PATH_STR);
+
+        let entries: Vec<entry::Entry> = reader::Reader::new(fasta_file_path, 1024).unwrap()
+            .collect()
+            .unwrap();
+
+        let mut writer = writer::Writer::with_compression(
+            tmp_fasta_file_path,
+            crate::compression::Compression::from_path(tmp_fasta_file_path)
+        ).unwrap();
+        writer.write_all(entries.iter(), true).unwrap();
+        writer.finish().unwrap();
+
+        let compressed_entries: Vec<entry::Entry> = reader::Reader::new(tmp_fasta_file_path, 1024).unwrap()
+            .collect()
+            .unwrap();
+        fs::remove_file(tmp_fasta_file_path).unwrap();
+
+        assert_eq!(compressed_entries.len(), EXPECTED_NUM_PROTEINS);
+        for (entry, compressed_entry) in entries.iter().zip(compressed_entries.iter()) {
+            assert_eq!(entry.get_accession(), compressed_entry.get_accession());
+            assert_eq!(entry.get_sequence(), compressed_entry.get_sequence());
+        }
+    }
+
+    #[test]
+    /// Indexes a FASTA file, persists and reloads the index
+    /// and compares the randomly accessed entries with the sequentially read ones.
+    fn test_indexed_reading() {
+        let fasta_file_path = Path::new(TEMP_INDEX_FASTA_PATH_STR);
+        fs::copy(FASTA_FILE_PATH_STR, fasta_file_path).unwrap();
+
+        let index = index::FastaIndex::build(fasta_file_path).unwrap();
+        assert_eq!(index.len(), EXPECTED_NUM_PROTEINS);
+        let first_record = &index.get_records()[0];
+        assert_eq!(first_record.get_name(), "sp|A0A024B7W1|POLG_ZIKVF");
+        assert_eq!(first_record.get_accession(), "A0A024B7W1");
+        assert_eq!(first_record.get_header_offset(), Some(0));
+        assert_eq!(first_record.get_line_bases(), 60);
+        assert_eq!(first_record.get_line_width(), 61);
+        index.write(fasta_file_path).unwrap();
+        let fai_path = index::FastaIndex::get_fai_path(fasta_file_path);
+        assert!(fs::read_to_string(&fai_path).unwrap().starts_with("sp|A0A024B7W1|POLG_ZIKVF\t3423\t131\t60\t61\n"));
+
+        let entries: Vec<entry::Entry> = reader::Reader::new(fasta_file_path, 1024).unwrap()
+            .collect()
+            .unwrap();
+        let mut indexed_reader = indexed_reader::IndexedReader::new(fasta_file_path).unwrap();
+        assert_eq!(indexed_reader.get_index(), &index);
+        for (position, entry) in entries.iter().enumerate().rev() {
+            assert_eq!(indexed_reader.get_entry_by_accession(entry.get_accession()).unwrap().as_ref(), Some(entry));
+            assert_eq!(indexed_reader.get_entry_by_position(position).unwrap().as_ref(), Some(entry));
+        }
+        assert!(indexed_reader.get_entry_by_accession("P00000").unwrap().is_none());
+        assert_eq!(
+            indexed_reader.get_sequence_region("sp|A0A024B7W1|POLG_ZIKVF", 55, 65).unwrap().unwrap(),
+            &entries[0].get_sequence()[55..65]
+        );
+
+        fs::remove_file(fai_path).unwrap();
+        fs::remove_file(index::FastaIndex::get_accession_index_path(fasta_file_path)).unwrap();
+        fs::remove_file(fasta_file_path).unwrap();
+    }
+
+    #[test]
+    /// Reads entries using a plain `.fai` file without accession sidecar,
+    /// locating the headers before the indexed sequence offsets.
+    fn test_indexed_reading_with_plain_fai() {
+        let fasta_file_path = Path::new(TEMP_FAI_FASTA_PATH_STR);
+        fs::copy(FASTA_FILE_PATH_STR, fasta_file_path).unwrap();
+        let fai_path = index::FastaIndex::get_fai_path(fasta_file_path);
+        index::FastaIndex::build(fasta_file_path).unwrap().write_to_paths(&fai_path, None).unwrap();
+        assert!(!index::FastaIndex::get_accession_index_path(fasta_file_path).exists());
+
+        let entries: Vec<entry::Entry> = reader::Reader::new(fasta_file_path, 1024).unwrap()
+            .collect()
+            .unwrap();
+        let mut indexed_reader = indexed_reader::IndexedReader::new(fasta_file_path).unwrap();
+        let first_record = &indexed_reader.get_index().get_records()[0];
+        assert_eq!(first_record.get_accession(), "A0A024B7W1");
+        assert_eq!(first_record.get_header_offset(), None);
+        for (position, entry) in entries.iter().enumerate().rev() {
+            assert_eq!(indexed_reader.get_entry_by_accession(entry.get_accession()).unwrap().as_ref(), Some(entry));
+            assert_eq!(indexed_reader.get_entry_by_position(position).unwrap().as_ref(), Some(entry));
+        }
+
+        fs::remove_file(fai_path).unwrap();
+        fs::remove_file(fasta_file_path).unwrap();
+    }
 }
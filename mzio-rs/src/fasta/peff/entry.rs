@@ -0,0 +1,452 @@
+// std imports
+use std::collections::HashMap;
+
+// internal imports
+use crate::fasta::entry::Entry;
+
+/// Single amino acid variant, e.g. `(24|T)` or `(35|A|dbSNP)` of `\VariantSimple`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantSimple {
+    position: usize,
+    amino_acid: char,
+    tag: Option<String>
+}
+
+impl VariantSimple {
+    /// Returns the position (1-based)
+    ///
+    pub fn get_position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the new amino acid
+    ///
+    pub fn get_amino_acid(&self) -> char {
+        self.amino_acid
+    }
+
+    /// Returns the optional tag, e.g. the variant source
+    ///
+    pub fn get_tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+}
+
+/// Variant replacing a sequence range, e.g. `(60|61|ML)` of `\VariantComplex`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantComplex {
+    start: usize,
+    end: usize,
+    sequence: String,
+    tag: Option<String>
+}
+
+impl VariantComplex {
+    /// Returns the start position (1-based, inclusive)
+    ///
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end position (1-based, inclusive)
+    ///
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the replacing sequence, empty for deletions
+    ///
+    pub fn get_sequence(&self) -> &str {
+        &self.sequence
+    }
+
+    /// Returns the optional tag, e.g. the variant source
+    ///
+    pub fn get_tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+}
+
+/// Modified residue, e.g. `(29|MOD:00696|Phosphoserine)` of `\ModResPsi`, `\ModResUnimod` or `\ModRes`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModRes {
+    positions: Vec<usize>,
+    accession: Option<String>,
+    name: String
+}
+
+impl ModRes {
+    /// Returns the possible positions (1-based)
+    ///
+    pub fn get_positions(&self) -> &Vec<usize> {
+        &self.positions
+    }
+
+    /// Returns the accession, e.g. `MOD:00696`, None for custom modifications of `\ModRes`
+    ///
+    pub fn get_accession(&self) -> Option<&str> {
+        self.accession.as_deref()
+    }
+
+    /// Returns the modification name
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Processed region, e.g. `(1|18|signal peptide)` of `\Processed`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Processed {
+    start: usize,
+    end: usize,
+    name: String
+}
+
+impl Processed {
+    /// Returns the start position (1-based, inclusive)
+    ///
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end position (1-based, inclusive)
+    ///
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the type of the processed region, e.g. `signal peptide`
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// PEFF entry, keeping the FASTA entry and the structured PEFF annotations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeffEntry {
+    entry: Entry,
+    annotations: Vec<(String, String)>,
+    variants_simple: Vec<VariantSimple>,
+    variants_complex: Vec<VariantComplex>,
+    mod_res_psi: Vec<ModRes>,
+    mod_res_unimod: Vec<ModRes>,
+    mod_res: Vec<ModRes>,
+    processed: Vec<Processed>
+}
+
+impl PeffEntry {
+    /// Creates a new PEFF entry.
+    /// Returns None if one of the structured annotations is malformed.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Database prefix, e.g. `nxp`
+    /// * `accession` - Entry accession, e.g. `NX_P02768-1`
+    /// * `annotations` - Ordered annotations, e.g. `("PName", "Serum albumin")` for `\PName=Serum albumin`
+    /// * `sequence` - Amino acid sequence
+    ///
+    pub fn new(prefix: String, accession: String, annotations: Vec<(String, String)>, sequence: String) -> Option<Self> {
+        let mut variants_simple: Vec<VariantSimple> = Vec::new();
+        let mut variants_complex: Vec<VariantComplex> = Vec::new();
+        let mut mod_res_psi: Vec<ModRes> = Vec::new();
+        let mut mod_res_unimod: Vec<ModRes> = Vec::new();
+        let mut mod_res: Vec<ModRes> = Vec::new();
+        let mut processed: Vec<Processed> = Vec::new();
+        let mut keyword_attributes: HashMap<String, String> = HashMap::new();
+
+        for (key, value) in annotations.iter() {
+            match key.as_str() {
+                "VariantSimple" => for fields in Self::split_groups(value)? {
+                    variants_simple.push(Self::parse_variant_simple(&fields)?);
+                },
+                "VariantComplex" => for fields in Self::split_groups(value)? {
+                    variants_complex.push(Self::parse_variant_complex(&fields)?);
+                },
+                "ModResPsi" => for fields in Self::split_groups(value)? {
+                    mod_res_psi.push(Self::parse_mod_res(&fields)?);
+                },
+                "ModResUnimod" => for fields in Self::split_groups(value)? {
+                    mod_res_unimod.push(Self::parse_mod_res(&fields)?);
+                },
+                "ModRes" => for fields in Self::split_groups(value)? {
+                    mod_res.push(Self::parse_mod_res(&fields)?);
+                },
+                "Processed" => for fields in Self::split_groups(value)? {
+                    processed.push(Self::parse_processed(&fields)?);
+                },
+                _ => {
+                    keyword_attributes.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        let entry_name = keyword_attributes.get("GName").cloned().unwrap_or_default();
+        let protein_name = keyword_attributes.get("PName").cloned().unwrap_or_default();
+        Some(Self {
+            entry: Entry::new(
+                prefix,
+                accession,
+                entry_name,
+                protein_name,
+                keyword_attributes,
+                sequence
+            ),
+            annotations,
+            variants_simple,
+            variants_complex,
+            mod_res_psi,
+            mod_res_unimod,
+            mod_res,
+            processed
+        })
+    }
+
+    /// Splits a list of parenthesized groups, e.g. `(24|T)(35|A)`, into the `|`-separated fields of each group.
+    /// Parentheses within fields, e.g. in modification names, are kept as long as they are balanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Annotation value
+    ///
+    fn split_groups(value: &str) -> Option<Vec<Vec<String>>> {
+        let mut groups: Vec<Vec<String>> = Vec::new();
+        let mut depth: usize = 0;
+        let mut current_group = String::new();
+        for c in value.trim().chars() {
+            match c {
+                '(' => {
+                    if depth > 0 {
+                        current_group.push(c);
+                    }
+                    depth += 1;
+                },
+                ')' => {
+                    depth = depth.checked_sub(1)?;
+                    if depth > 0 {
+                        current_group.push(c);
+                    } else {
+                        groups.push(current_group.split('|').map(|field| field.to_string()).collect());
+                        current_group.clear();
+                    }
+                },
+                _ if depth > 0 => current_group.push(c),
+                // Characters outside of groups
+                _ => return None
+            }
+        }
+        if depth > 0 {
+            return None;
+        }
+        Some(groups)
+    }
+
+    /// Parses a comma separated list of positions, e.g. `4,5`
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - Positions
+    ///
+    fn parse_positions(positions: &str) -> Option<Vec<usize>> {
+        positions.split(',')
+            .map(|position| position.trim().parse().ok())
+            .collect()
+    }
+
+    /// Returns the optional field at the given index, None if missing or empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Fields of a group
+    /// * `index` - Field index
+    ///
+    fn get_optional_field(fields: &[String], index: usize) -> Option<String> {
+        fields.get(index).filter(|field| !field.is_empty()).cloned()
+    }
+
+    /// Parses a single amino acid variant, e.g. `(24|T)`
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Fields of a group
+    ///
+    fn parse_variant_simple(fields: &[String]) -> Option<VariantSimple> {
+        let mut amino_acids = fields.get(1)?.chars();
+        let amino_acid = amino_acids.next()?;
+        if amino_acids.next().is_some() {
+            return None;
+        }
+        Some(VariantSimple {
+            position: fields.first()?.trim().parse().ok()?,
+            amino_acid,
+            tag: Self::get_optional_field(fields, 2)
+        })
+    }
+
+    /// Parses a sequence variant, e.g. `(60|61|ML)`
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Fields of a group
+    ///
+    fn parse_variant_complex(fields: &[String]) -> Option<VariantComplex> {
+        Some(VariantComplex {
+            start: fields.first()?.trim().parse().ok()?,
+            end: fields.get(1)?.trim().parse().ok()?,
+            sequence: fields.get(2)?.clone(),
+            tag: Self::get_optional_field(fields, 3)
+        })
+    }
+
+    /// Parses a modified residue, e.g. `(29|MOD:00696|Phosphoserine)`
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Fields of a group
+    ///
+    fn parse_mod_res(fields: &[String]) -> Option<ModRes> {
+        Some(ModRes {
+            positions: Self::parse_positions(fields.first()?)?,
+            accession: Self::get_optional_field(fields, 1),
+            name: fields.get(2)?.clone()
+        })
+    }
+
+    /// Parses a processed region, e.g. `(1|18|signal peptide)`
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Fields of a group
+    ///
+    fn parse_processed(fields: &[String]) -> Option<Processed> {
+        Some(Processed {
+            start: fields.first()?.trim().parse().ok()?,
+            end: fields.get(1)?.trim().parse().ok()?,
+            // Newer PEFF versions put an accession in front of the name
+            name: fields.last()?.clone()
+        })
+    }
+
+    /// Returns the FASTA entry. The database is the PEFF prefix,
+    /// entry and protein name are taken from `\GName` and `\PName`
+    /// and all unstructured annotations are kept as keyword attributes.
+    ///
+    pub fn get_entry(&self) -> &Entry {
+        &self.entry
+    }
+
+    /// Converts the PEFF entry into a FASTA entry, see get_entry().
+    ///
+    pub fn into_entry(self) -> Entry {
+        self.entry
+    }
+
+    /// Returns the database prefix, e.g. `nxp`
+    ///
+    pub fn get_prefix(&self) -> &str {
+        self.entry.get_database()
+    }
+
+    /// Returns the accession
+    ///
+    pub fn get_accession(&self) -> &str {
+        self.entry.get_accession()
+    }
+
+    /// Returns the amino acid sequence
+    ///
+    pub fn get_sequence(&self) -> &str {
+        self.entry.get_sequence()
+    }
+
+    /// Returns all annotations in their original order
+    ///
+    pub fn get_annotations(&self) -> &Vec<(String, String)> {
+        &self.annotations
+    }
+
+    /// Returns the value of the first annotation with the given key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Annotation key without the leading '\', e.g. `PName`
+    ///
+    pub fn get_annotation(&self, key: &str) -> Option<&str> {
+        self.annotations.iter()
+            .find(|(annotation_key, _)| annotation_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the single amino acid variants of `\VariantSimple`
+    ///
+    pub fn get_variants_simple(&self) -> &Vec<VariantSimple> {
+        &self.variants_simple
+    }
+
+    /// Returns the sequence variants of `\VariantComplex`
+    ///
+    pub fn get_variants_complex(&self) -> &Vec<VariantComplex> {
+        &self.variants_complex
+    }
+
+    /// Returns the PSI-MOD modifications of `\ModResPsi`
+    ///
+    pub fn get_mod_res_psi(&self) -> &Vec<ModRes> {
+        &self.mod_res_psi
+    }
+
+    /// Returns the Unimod modifications of `\ModResUnimod`
+    ///
+    pub fn get_mod_res_unimod(&self) -> &Vec<ModRes> {
+        &self.mod_res_unimod
+    }
+
+    /// Returns the custom modifications of `\ModRes`
+    ///
+    pub fn get_mod_res(&self) -> &Vec<ModRes> {
+        &self.mod_res
+    }
+
+    /// Returns the processed regions of `\Processed`, e.g. signal peptides
+    ///
+    pub fn get_processed(&self) -> &Vec<Processed> {
+        &self.processed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Tests the parsing of structured annotations.
+    fn test_structured_annotations() {
+        let entry = PeffEntry::new(
+            "nxp".to_string(),
+            "NX_P02768-1".to_string(),
+            vec![
+                ("PName".to_string(), "Serum albumin".to_string()),
+                ("VariantSimple".to_string(), "(24|T)(35|A|dbSNP)".to_string()),
+                ("ModResPsi".to_string(), "(4,5|MOD:00696|Phosphoserine (ambiguous))".to_string()),
+                ("Processed".to_string(), "(1|18|signal peptide)".to_string())
+            ],
+            "MKWVTFISLL".to_string()
+        ).unwrap();
+        assert_eq!(entry.get_entry().get_protein_name(), "Serum albumin");
+        assert_eq!(entry.get_variants_simple().len(), 2);
+        assert_eq!(entry.get_variants_simple()[1].get_position(), 35);
+        assert_eq!(entry.get_variants_simple()[1].get_amino_acid(), 'A');
+        assert_eq!(entry.get_variants_simple()[1].get_tag(), Some("dbSNP"));
+        assert_eq!(entry.get_mod_res_psi()[0].get_positions(), &vec![4, 5]);
+        assert_eq!(entry.get_mod_res_psi()[0].get_name(), "Phosphoserine (ambiguous)");
+        assert_eq!(entry.get_processed()[0].get_end(), 18);
+
+        assert!(PeffEntry::new(
+            "nxp".to_string(),
+            "NX_P02768-1".to_string(),
+            vec![("VariantSimple".to_string(), "(x|T)".to_string())],
+            "MKWVTFISLL".to_string()
+        ).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+/// Database block of the PEFF file header, e.g.
+/// ```text
+/// # DbName=neXtProt
+/// # Prefix=nxp
+/// # //
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeffDatabase {
+    attributes: Vec<(String, String)>
+}
+
+impl PeffDatabase {
+    /// Creates a new database block
+    ///
+    /// # Arguments
+    ///
+    /// * `attributes` - Ordered attributes, e.g. `("DbName", "neXtProt")`
+    ///
+    pub fn new(attributes: Vec<(String, String)>) -> Self {
+        Self {
+            attributes
+        }
+    }
+
+    /// Returns the value of the first attribute with the given key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Attribute key, e.g. `DbDescription`
+    ///
+    pub fn get_attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|(attribute_key, _)| attribute_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all attributes in their original order
+    ///
+    pub fn get_attributes(&self) -> &Vec<(String, String)> {
+        &self.attributes
+    }
+
+    /// Returns the database name (`DbName`)
+    ///
+    pub fn get_db_name(&self) -> Option<&str> {
+        self.get_attribute("DbName")
+    }
+
+    /// Returns the prefix used in the entry identifiers (`Prefix`)
+    ///
+    pub fn get_prefix(&self) -> Option<&str> {
+        self.get_attribute("Prefix")
+    }
+}
+
+/// PEFF file header, e.g. `# PEFF 1.0` followed by one block per database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeffHeader {
+    version: String,
+    databases: Vec<PeffDatabase>
+}
+
+impl PeffHeader {
+    /// Creates a new PEFF header
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - PEFF version, e.g. `1.0`
+    /// * `databases` - Database blocks
+    ///
+    pub fn new(version: String, databases: Vec<PeffDatabase>) -> Self {
+        Self {
+            version,
+            databases
+        }
+    }
+
+    /// Returns the PEFF version
+    ///
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the database blocks
+    ///
+    pub fn get_databases(&self) -> &Vec<PeffDatabase> {
+        &self.databases
+    }
+
+    /// Returns the database block with the given prefix
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Prefix, e.g. `nxp`
+    ///
+    pub fn get_database_by_prefix(&self, prefix: &str) -> Option<&PeffDatabase> {
+        self.databases.iter().find(|database| database.get_prefix() == Some(prefix))
+    }
+}
+
+impl Default for PeffHeader {
+    fn default() -> Self {
+        Self::new("1.0".to_string(), Vec::new())
+    }
+}
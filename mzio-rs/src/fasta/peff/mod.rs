@@ -0,0 +1,73 @@
+//! Module for dealing with PEFF (PSI Extended FASTA Format) files,
+//! e.g. as distributed by neXtProt (https://www.nextprot.org)
+
+pub mod entry;
+pub mod header;
+pub mod reader;
+pub mod writer;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::fs;
+    use std::path::Path;
+
+    use fallible_iterator::FallibleIterator;
+
+    const PEFF_FILE_PATH_STR: &str = "../test_files/fasta/partial_nextprot.peff";
+    const EXPECTED_NUM_PROTEINS: usize = 2;
+    const TEMP_PEFF_PATH_STR: &str = "../test_files/fasta/partial_nextprot.peff.tmp";
+
+    #[test]
+    /// Reads a PEFF file, parses the header and proteins,
+    /// write them back into a temporary file and compares it with the original one.
+    fn test_reading_and_writing() {
+        let peff_file_path = Path::new(PEFF_FILE_PATH_STR);
+        let tmp_peff_file_path = Path::new(TEMP_PEFF_PATH_STR);
+
+        let mut reader = reader::Reader::new(peff_file_path, 1024).unwrap();
+        let peff_header = reader.get_peff_header().clone();
+        assert_eq!(peff_header.get_version(), "1.0");
+        assert_eq!(peff_header.get_databases().len(), 1);
+        assert_eq!(peff_header.get_databases()[0].get_db_name(), Some("neXtProt"));
+        assert_eq!(peff_header.get_databases()[0].get_prefix(), Some("nxp"));
+
+        let entries: Vec<entry::PeffEntry> = reader.by_ref().collect().unwrap();
+        assert_eq!(entries.len(), EXPECTED_NUM_PROTEINS);
+        assert_eq!(entries[0].get_prefix(), "nxp");
+        assert_eq!(entries[0].get_accession(), "NX_P02768-1");
+        assert_eq!(entries[0].get_entry().get_protein_name(), "Serum albumin isoform 1");
+        assert_eq!(entries[0].get_sequence().len(), 125);
+        assert_eq!(entries[0].get_variants_simple().len(), 2);
+        assert_eq!(entries[0].get_variants_complex()[0].get_sequence(), "ML");
+        assert_eq!(entries[0].get_mod_res_psi()[1].get_accession(), Some("MOD:00696"));
+        assert_eq!(entries[0].get_mod_res()[0].get_accession(), None);
+        assert_eq!(entries[0].get_processed()[2].get_name(), "mature protein");
+        assert_eq!(entries[1].get_mod_res_unimod()[0].get_positions(), &vec![4, 5]);
+
+        let mut writer = writer::Writer::new(tmp_peff_file_path).unwrap();
+        writer.write_peff_header(&peff_header).unwrap();
+        writer.write_all(entries.iter()).unwrap();
+        writer.flush().unwrap();
+
+        let tmp_peff_content = fs::read_to_string(tmp_peff_file_path).unwrap();
+        fs::remove_file(tmp_peff_file_path).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(peff_file_path).unwrap().trim(),
+            tmp_peff_content.trim()
+        );
+    }
+
+    #[test]
+    /// Tests that non-PEFF input is rejected.
+    fn test_reading_malformed_input() {
+        assert!(reader::Reader::from_reader(">sp|P27748|ACOX_CUPNH\nMGHA\n".as_bytes()).is_err());
+        let mut reader = reader::Reader::from_reader("# PEFF 1.0\n>nxp:NX_1 \\VariantSimple=(x|T)\nMGHA\n".as_bytes()).unwrap();
+        match reader.next().unwrap_err() {
+            crate::Error::MalformedHeader { position, .. } => assert_eq!(position.get_line_number(), Some(2)),
+            error => panic!("unexpected error: {}", error)
+        }
+    }
+}
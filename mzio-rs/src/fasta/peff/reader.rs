@@ -0,0 +1,219 @@
+// std imports
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+
+// internal imports
+use crate::compression::open_reader;
+use crate::error::{Error, Position, Result};
+use crate::fasta::peff::entry::PeffEntry;
+use crate::fasta::peff::header::{PeffDatabase, PeffHeader};
+use crate::line_reader::LineReader;
+
+/// Prefix of the PEFF header line containing the version
+const PEFF_VERSION_PREFIX: &str = "# PEFF ";
+
+/// Separator of database blocks in the PEFF header
+const PEFF_DATABASE_SEPARATOR: &str = "# //";
+
+/// Reader for PEFF (PSI Extended FASTA Format) files.
+/// The file header is read on creation, entries are read one by one.
+pub struct Reader<R: BufRead> {
+    internal_reader: LineReader<R>,
+    is_eof: bool,
+    peff_header: PeffHeader,
+    header: String,
+    header_position: Position,
+    sequence: String
+}
+
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given PEFF file and reads the file header.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
+    /// # Arguments
+    ///
+    /// * `peff_file_path` - Path to PEFF file
+    /// * `buffer_size` - Size of the internal read buffer
+    ///
+    pub fn new(peff_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Self::from_line_reader(LineReader::new(
+            open_reader(peff_file_path, buffer_size)?,
+            Some(peff_file_path.to_path_buf())
+        ))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source and reads the file header.
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the PEFF content
+    ///
+    pub fn from_reader(internal_reader: R) -> Result<Self> {
+        Self::from_line_reader(LineReader::new(internal_reader, None))
+    }
+
+    /// Creates a new Reader from a line reader and reads the file header.
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Line reader providing the PEFF content
+    ///
+    fn from_line_reader(internal_reader: LineReader<R>) -> Result<Self> {
+        let mut reader = Self {
+            internal_reader,
+            is_eof: false,
+            peff_header: PeffHeader::default(),
+            header: String::new(),
+            header_position: Position::default(),
+            sequence: String::new()
+        };
+        reader.read_peff_header()?;
+        Ok(reader)
+    }
+
+    /// Reads the file header until the first entry header.
+    ///
+    fn read_peff_header(&mut self) -> Result<()> {
+        let mut version: Option<String> = None;
+        let mut databases: Vec<PeffDatabase> = Vec::new();
+        let mut attributes: Vec<(String, String)> = Vec::new();
+        let mut line = String::new();
+        loop {
+            if self.internal_reader.read_line(&mut line)? == 0 {
+                self.is_eof = true;
+                break;
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if version.is_none() {
+                match line.strip_prefix(PEFF_VERSION_PREFIX) {
+                    Some(peff_version) => version = Some(peff_version.trim().to_string()),
+                    None => return Err(Error::MalformedHeader {
+                        header: line.to_string(),
+                        position: self.internal_reader.get_position()
+                    })
+                }
+            } else if line == PEFF_DATABASE_SEPARATOR {
+                if !attributes.is_empty() {
+                    databases.push(PeffDatabase::new(std::mem::take(&mut attributes)));
+                }
+            } else if let Some(attribute) = line.strip_prefix('#') {
+                match attribute.trim().split_once('=') {
+                    Some((key, value)) => attributes.push((key.to_string(), value.to_string())),
+                    None => return Err(Error::MalformedHeader {
+                        header: line.to_string(),
+                        position: self.internal_reader.get_position()
+                    })
+                }
+            } else if line.starts_with('>') {
+                self.header = line.to_string();
+                self.header_position = self.internal_reader.get_position();
+                break;
+            } else {
+                return Err(Error::MissingHeader {
+                    line: line.to_string(),
+                    position: self.internal_reader.get_position()
+                });
+            }
+        }
+        // Block without trailing separator
+        if !attributes.is_empty() {
+            databases.push(PeffDatabase::new(attributes));
+        }
+        match version {
+            Some(version) => {
+                self.peff_header = PeffHeader::new(version, databases);
+                Ok(())
+            },
+            None => Err(Error::UnsupportedFormat {
+                message: "input does not start with a PEFF header".to_string()
+            })
+        }
+    }
+
+    /// Returns the file header
+    ///
+    pub fn get_peff_header(&self) -> &PeffHeader {
+        &self.peff_header
+    }
+
+    /// Creates a new PEFF entry from the given header and sequence.
+    /// Returns None if the header is malformed.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - A PEFF entry header, e.g. `>nxp:NX_P02768-1 \PName=Serum albumin \Length=609`
+    /// * `sequence` - Amino acid sequence
+    ///
+    pub fn create_entry(header: &str, sequence: &str) -> Option<PeffEntry> {
+        let raw_header = header.strip_prefix('>')?;
+        let (identifier, raw_annotations) = raw_header.split_once(|c: char| c.is_ascii_whitespace())
+            .unwrap_or((raw_header, ""));
+        let (prefix, accession) = identifier.split_once(':').unwrap_or(("", identifier));
+        if accession.is_empty() {
+            return None;
+        }
+        let mut annotations: Vec<(String, String)> = Vec::new();
+        for raw_annotation in raw_annotations.split('\\') {
+            let raw_annotation = raw_annotation.trim();
+            if raw_annotation.is_empty() {
+                continue;
+            }
+            let (key, value) = raw_annotation.split_once('=')?;
+            annotations.push((key.to_string(), value.to_string()));
+        }
+        PeffEntry::new(
+            prefix.to_string(),
+            accession.to_string(),
+            annotations,
+            sequence.replace('\n', "")
+        )
+    }
+
+    /// Creates the entry for the current header and the sequence read so far.
+    ///
+    fn create_current_entry(&self) -> Result<PeffEntry> {
+        match Self::create_entry(&self.header, &self.sequence) {
+            Some(entry) => Ok(entry),
+            None => Err(Error::MalformedHeader {
+                header: self.header.clone(),
+                position: self.header_position.clone()
+            })
+        }
+    }
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
+    type Item = PeffEntry;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        if self.is_eof {
+            return Ok(None);
+        }
+        self.sequence.clear(); // Reset sequence, new header is still present from last iteration.
+        let mut line = String::new();
+        loop {
+            let num_bytes = self.internal_reader.read_line(&mut line)?;
+            if num_bytes == 0 {
+                self.is_eof = true;
+                return self.create_current_entry().map(Some);
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('>') {
+                let entry = self.create_current_entry()?;
+                self.header = line.to_string();
+                self.header_position = self.internal_reader.get_position();
+                return Ok(Some(entry));
+            }
+            self.sequence.push_str(line);
+        }
+    }
+}
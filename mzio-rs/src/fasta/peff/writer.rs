@@ -0,0 +1,166 @@
+// std imports
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use std::path::Path;
+
+// internal imports
+use crate::compression::{Compression, Encoder};
+use crate::error::{Error, Result};
+use crate::fasta::peff::entry::PeffEntry;
+use crate::fasta::peff::header::PeffHeader;
+use crate::fasta::writer::Writer as FastaWriter;
+
+/// Writer for PEFF (PSI Extended FASTA Format) files.
+/// Works on any target implementing `Write`, e.g. files, stdout or `Vec<u8>`.
+/// Write the file header first, then the entries.
+/// Use flush() to make sure the buffer is written completely.
+pub struct Writer<W: Write> {
+    internal_writer: W
+}
+
+impl Writer<BufWriter<File>> {
+    /// Creates a new buffered Writer for the given PEFF file
+    /// 
+    /// # Arguments
+    ///
+    /// * `peff_file_path` - Path to PEFF file
+    /// 
+    pub fn new(peff_file_path: &Path) -> Result<Self> {
+        let peff_file: File = File::create(peff_file_path)
+            .map_err(|err| Error::io_with_path(err, peff_file_path))?;
+        Ok(Self::from_writer(BufWriter::new(peff_file)))
+    }
+}
+
+impl Writer<Encoder<BufWriter<File>>> {
+    /// Creates a new buffered Writer for the given PEFF file, compressing the output.
+    /// Use finish() to complete the compressed file.
+    /// 
+    /// # Arguments
+    ///
+    /// * `peff_file_path` - Path to PEFF file
+    /// * `compression` - Compression of the written file, see `Compression::from_path()`
+    /// 
+    pub fn with_compression(peff_file_path: &Path, compression: Compression) -> Result<Self> {
+        Ok(Self::from_writer(Encoder::create(peff_file_path, compression)?))
+    }
+}
+
+impl<W: Write> Writer<Encoder<W>> {
+    /// Flushes the buffer, completes the compressed stream and returns the underlying write target.
+    /// 
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        self.internal_writer.finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new Writer for any write target.
+    /// The target is used as is, so wrap unbuffered targets in a `BufWriter`.
+    /// 
+    /// # Arguments
+    ///
+    /// * `internal_writer` - Write target
+    /// 
+    pub fn from_writer(internal_writer: W) -> Self {
+        Self {
+            internal_writer
+        }
+    }
+
+    /// Returns the underlying write target, e.g. to access an in-memory buffer.
+    /// Make sure to call flush() first.
+    /// 
+    pub fn into_inner(self) -> W {
+        self.internal_writer
+    }
+
+    /// Writes the complete byte slice and returns the number of written bytes.
+    /// 
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to write
+    /// 
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.internal_writer.write_all(bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Creates the header of the given entry, e.g. `>nxp:NX_P02768-1 \PName=Serum albumin`
+    /// 
+    /// # Arguments
+    ///
+    /// * `entry` - PEFF entry
+    /// 
+    fn create_header(entry: &PeffEntry) -> String {
+        let mut header = ">".to_string();
+        if !entry.get_prefix().is_empty() {
+            header.push_str(entry.get_prefix());
+            header.push(':');
+        }
+        header.push_str(entry.get_accession());
+        for (key, value) in entry.get_annotations() {
+            header.push_str(&format!(" \\{}={}", key, value));
+        }
+        header
+    }
+
+    /// Writes the PEFF file header.
+    /// 
+    /// # Arguments
+    ///
+    /// * `peff_header` - PEFF file header
+    /// 
+    pub fn write_peff_header(&mut self, peff_header: &PeffHeader) -> Result<usize> {
+        let mut written_bytes: usize = 0;
+        written_bytes += self.write_bytes(format!("# PEFF {}\n", peff_header.get_version()).as_bytes())?;
+        for database in peff_header.get_databases() {
+            for (key, value) in database.get_attributes() {
+                written_bytes += self.write_bytes(format!("# {}={}\n", key, value).as_bytes())?;
+            }
+            written_bytes += self.write_bytes(b"# //\n")?;
+        }
+        Ok(written_bytes)
+    }
+
+    /// Writes entry into the PEFF file.
+    /// 
+    /// # Arguments
+    ///
+    /// * `entry` - PEFF entry
+    /// 
+    pub fn write_entry(&mut self, entry: &PeffEntry) -> Result<usize> {
+        let mut written_bytes: usize = 0;
+        written_bytes += self.write_bytes(Self::create_header(entry).as_bytes())?;
+        written_bytes += self.write_bytes(b"\n")?;
+        written_bytes += self.write_bytes(FastaWriter::<W>::format_sequence(entry.get_sequence()).as_bytes())?;
+        written_bytes += self.write_bytes(b"\n")?;
+        Ok(written_bytes)
+    }
+
+    /// Writes multiple PEFF entries to file.
+    /// 
+    /// # Arguments
+    ///
+    /// * `entries` - Iterator of PEFF entries
+    /// 
+    pub fn write_all<'b, I>(&mut self, entries: I) -> Result<usize>
+    where
+        I: Iterator<Item = &'b PeffEntry>,
+    {
+        let mut written_bytes: usize = 0;
+        for entry in entries {
+            written_bytes += self.write_entry(entry)?;
+        }
+        Ok(written_bytes)
+    }
+
+    /// Flushes the buffer
+    /// 
+    pub fn flush(&mut self) -> Result<()> {
+        self.internal_writer.flush()?;
+        Ok(())
+    }
+}
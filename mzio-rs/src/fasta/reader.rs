@@ -1,55 +1,81 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
 use std::io::prelude::*;
 use std::path::Path;
 
+use crate::compression::open_reader;
+use crate::error::{Error, Position, Result};
 use crate::fasta::entry::Entry;
-use anyhow::Result;
+use crate::fasta::header_parser::{HeaderParser, UniProtHeaderParser};
+use crate::line_reader::LineReader;
+use fallible_iterator::FallibleIterator;
 
 /// Reader for common FASTA files as distributed by UniProt (https://uniprot.org)
-pub struct Reader {
-    internal_reader: BufReader<File>,
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+/// Headers of other formats can be read by setting a different `HeaderParser`.
+pub struct Reader<R: BufRead> {
+    internal_reader: LineReader<R>,
     is_eof: bool,
     header: String,
-    sequence: String
+    header_position: Position,
+    sequence: String,
+    header_parser: Box<dyn HeaderParser + Send>
 }
 
-impl Reader {
-    /// Creates a new Reader
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given FASTA file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
     /// # Arguments
     ///
     /// * `fasta_file_path` - Path to FASTA file
+    /// * `buffer_size` - Size of the internal read buffer
     /// 
     pub fn new(fasta_file_path: &Path, buffer_size: usize) -> Result<Self> {
-        let fasta_file: File = File::open(fasta_file_path)?;
-        Ok(Self {
-            internal_reader: BufReader::with_capacity(buffer_size, fasta_file),
+        Ok(Self::from_line_reader(LineReader::new(
+            open_reader(fasta_file_path, buffer_size)?,
+            Some(fasta_file_path.to_path_buf())
+        )))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the FASTA content
+    /// 
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self::from_line_reader(LineReader::new(internal_reader, None))
+    }
+
+    /// Creates a new Reader from a line reader
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Line reader providing the FASTA content
+    /// 
+    fn from_line_reader(internal_reader: LineReader<R>) -> Self {
+        Self {
+            internal_reader,
             is_eof: false,
             header: String::new(),
-            sequence: String::new()
-        })
+            header_position: Position::default(),
+            sequence: String::new(),
+            header_parser: Box::new(UniProtHeaderParser)
+        }
     }
 
-    /// Processes and adds a keyword attribute to the HashMap
+    /// Sets the parser used to create entries from the headers, e.g. `AutoHeaderParser`
+    /// for mixed or non-UniProt databases.
     /// # Arguments
     ///
-    /// * `raw_attr` - Raw attributes, e.g. `key=value with spaces`
-    /// * `keyword_attributes` - Additional keyword attributes
+    /// * `header_parser` - Header parser
     /// 
-    fn prep_and_add_attribute_to_keyword_attributes(raw_attr: &str, keyword_attributes: &mut HashMap<String, String>) {
-        let attr_split = raw_attr.split("=").collect::<Vec<&str>>();
-        if let Some(key) = attr_split.get(0) {
-            if let Some(value) = attr_split.get(1) {
-                keyword_attributes.insert(
-                    key.to_string(),
-                    value.to_string()
-                );
-            }
-        }
+    pub fn with_header_parser<P: HeaderParser + Send + 'static>(mut self, header_parser: P) -> Self {
+        self.header_parser = Box::new(header_parser);
+        self
     }
 
-    /// Creates a new Entry from the given header and sequence.
+    /// Creates a new Entry from the given UniProt header and sequence.
+    /// Returns None if the header does not follow the UniProt format
+    /// `>db|accession|entry_name protein name KEY=value ...`.
     /// 
     /// # Arguments
     ///
@@ -57,100 +83,66 @@ impl Reader {
     /// * `sequence` - Amino acid sequence
     /// 
     pub fn create_entry(header: &str, sequence: &str) -> Option<Entry> {
-        // Split by '|' and extract database and accession 
-        let mut header_split = header.split("|").collect::<Vec<&str>>();
-        let mut database: String = header_split.remove(0).to_string();
-        database = database.as_str()[1..].to_string(); // remove '>'
-        let accession: String = header_split.remove(0).to_string();
-
-        // Split by ' '
-        header_split = header_split.remove(0).split(" ").collect::<Vec<&str>>();
-
-        // Extract entry name 
-        let entry_name: String = header_split.remove(0).to_string();
-        // Add chunks to protein name until first string with '=' occurs (begin of keyword attributes) 
-        let mut protein_name: String = header_split.remove(0).to_string();
-        loop {
-            if let Some(chunk) = header_split.get(0) {
-                if !chunk.contains("=") {
-                    protein_name.push_str(" ");
-                    protein_name.push_str(header_split.remove(0));
-                } else {
-                    break
-                }
-            }
-        }
-        // Extract keyword attributes
-        let mut keyword_attributes: HashMap<String, String> = HashMap::new();
-        if header_split.len() > 0 {
-            let mut current_attr: String = String::new();
-            while header_split.len() > 0 {
-                if let Some(chunk) = header_split.get(0) {
-                    // Every time a chunk does not start a new attribute (chunk does not contains '=')
-                    // add the chunk to the current attribute, otherwise process the current attribute
-                    // and begin a new one
-                    if !chunk.contains("=") {
-                        current_attr.push_str(" ");
-                        current_attr.push_str(header_split.remove(0));
-                    } else {
-                        Reader::prep_and_add_attribute_to_keyword_attributes(
-                            &current_attr, 
-                            &mut keyword_attributes
-                        );
-                        if header_split.len() > 0 {
-                            current_attr = header_split.remove(0).to_string();
-                        }
-                    }
-                }
-            }
-            // Process the remaining attribute
-            Reader::prep_and_add_attribute_to_keyword_attributes(
-                &current_attr, 
-                &mut keyword_attributes
-            );
+        UniProtHeaderParser.create_entry(header, sequence)
+    }
+
+    /// Creates the entry for the current header and the sequence read so far.
+    /// 
+    fn create_current_entry(&self) -> Result<Entry> {
+        match self.header_parser.create_entry(&self.header, &self.sequence) {
+            Some(entry) => Ok(entry),
+            None => Err(Error::MalformedHeader {
+                header: self.header.clone(),
+                position: self.header_position.clone()
+            })
         }
-        return Some(Entry::new(
-            database,   // database
-            accession,   // accession
-            entry_name,
-            protein_name,
-            keyword_attributes,
-            sequence.replace("\n", "")
-        ));
     }
 }
 
 
-impl Iterator for Reader {
+impl<R: BufRead> FallibleIterator for Reader<R> {
     type Item = Entry;
+    type Error = Error;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next(&mut self) -> Result<Option<Self::Item>> {
         if self.is_eof {
-            return None;
+            return Ok(None);
         }
-        self.sequence = String::new(); // Reset sequence, new header is still present from last iteration.
+        self.sequence.clear(); // Reset sequence, new header is still present from last iteration.
+        let mut line = String::new();
         loop {
-            let mut line = String::new();
-            if let Ok(num_bytes) = self.internal_reader.read_line(&mut line) {
-                if num_bytes == 0 {
-                    self.is_eof = true;
-                    return Reader::create_entry(&self.header, &self.sequence);
+            let num_bytes = self.internal_reader.read_line(&mut line)?;
+            if num_bytes == 0 {
+                self.is_eof = true;
+                // Empty input
+                if self.header.is_empty() {
+                    return Ok(None);
+                }
+                return self.create_current_entry().map(Some);
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('>') {
+                if !self.header.is_empty() {
+                    let entry = self.create_current_entry()?;
+                    self.header = line.to_string(); // safe newly read header
+                    self.header_position = self.internal_reader.get_position();
+                    return Ok(Some(entry));
                 }
-                line = line.as_mut_str().trim().to_string();
-                if !line.starts_with(">") && num_bytes > 0 {
-                    self.sequence.push_str(&line)
-                } else {
-                    if self.header.len() > 0 {
-                        let entry = Reader::create_entry(&self.header, &self.sequence);
-                        self.header = line; // safe newly read header
-                        return entry;
-                    } else  {
-                        self.header = line; 
-                    }
+                self.header = line.to_string();
+                self.header_position = self.internal_reader.get_position();
+            } else {
+                if self.header.is_empty() {
+                    return Err(Error::MissingHeader {
+                        line: line.to_string(),
+                        position: self.internal_reader.get_position()
+                    });
                 }
+                self.sequence.push_str(line);
             }
         }
-        
     }
 }
 
@@ -188,7 +180,7 @@ QRSGIVALDGERELAFGPDDEVTVTLHDHAFRSIDVAACMRHAGRHHLMRSLPQPAAVG";
     /// Tests the creation of a FASTA entry from a header and a sequence.
     ///
     fn test_entry_creation() {
-        let entry = Reader::create_entry(TEST_HEADER, TEST_SEQUENCE).unwrap();
+        let entry = Reader::<&[u8]>::create_entry(TEST_HEADER, TEST_SEQUENCE).unwrap();
         assert_eq!(entry.get_database(), EXPECTED_DATABASE);
         assert_eq!(entry.get_accession(), EXPECTED_ACCESSION);
         assert_eq!(entry.get_entry_name(), EXPECTED_ENTRY_NAME);
@@ -203,4 +195,78 @@ QRSGIVALDGERELAFGPDDEVTVTLHDHAFRSIDVAACMRHAGRHHLMRSLPQPAAVG";
             )
         }
     }
+
+    #[test]
+    /// Tests reading entries from an in-memory buffer.
+    ///
+    fn test_reading_from_buffer() {
+        let fasta_content = format!("{}\n{}\n{}\n{}\n", TEST_HEADER, TEST_SEQUENCE, TEST_HEADER, TEST_SEQUENCE);
+        let reader = Reader::from_reader(fasta_content.as_bytes());
+
+        let entries: Vec<Entry> = reader.collect().unwrap();
+        assert_eq!(entries.len(), 2);
+        for entry in entries {
+            assert_eq!(entry.get_accession(), EXPECTED_ACCESSION);
+            assert_eq!(entry.get_sequence(), EXPECTED_SEQUENCE);
+        }
+    }
+
+    #[test]
+    /// Tests that headers without keyword attributes are parsed.
+    ///
+    fn test_entry_creation_without_attributes() {
+        let entry = Reader::<&[u8]>::create_entry(">sp|P27748|ACOX_CUPNH Acetoin catabolism protein X", "MGHA").unwrap();
+        assert_eq!(entry.get_entry_name(), EXPECTED_ENTRY_NAME);
+        assert_eq!(entry.get_protein_name(), EXPECTED_PROTEIN_NAME);
+        assert!(entry.get_keyword_attributes().is_empty());
+    }
+
+    #[test]
+    /// Tests that empty input yields no entries.
+    ///
+    fn test_reading_empty_input() {
+        let mut reader = Reader::from_reader("\n\n".as_bytes());
+        assert!(reader.next().unwrap().is_none());
+    }
+
+    #[test]
+    /// Tests that malformed content is reported with line number and offending line.
+    ///
+    fn test_reading_malformed_input() {
+        let fasta_content = format!("{}\n{}\n>no_uniprot_header\nMGHA\n", TEST_HEADER, TEST_SEQUENCE);
+        let mut reader = Reader::from_reader(fasta_content.as_bytes());
+        assert!(reader.next().unwrap().is_some());
+        match reader.next().unwrap_err() {
+            Error::MalformedHeader { header, position } => {
+                assert_eq!(header, ">no_uniprot_header");
+                assert_eq!(position.get_line_number(), Some(8));
+                assert_eq!(position.get_byte_offset(), Some((TEST_HEADER.len() + TEST_SEQUENCE.len() + 2) as u64));
+            },
+            error => panic!("unexpected error: {}", error)
+        }
+
+        let mut reader = Reader::from_reader("MGHA\n".as_bytes());
+        match reader.next().unwrap_err() {
+            Error::MissingHeader { line, position } => {
+                assert_eq!(line, "MGHA");
+                assert_eq!(position.get_line_number(), Some(1));
+            },
+            error => panic!("unexpected error: {}", error)
+        }
+    }
+
+    #[test]
+    /// Tests reading non-UniProt headers with automatic format detection.
+    ///
+    fn test_reading_with_header_parser() {
+        let fasta_content = format!(">CON__P00761 Trypsin\n{}\n>NP_000549.1 hemoglobin subunit alpha [Homo sapiens]\nMVLS\n", TEST_SEQUENCE);
+        let entries: Vec<Entry> = Reader::from_reader(fasta_content.as_bytes())
+            .with_header_parser(crate::fasta::header_parser::AutoHeaderParser)
+            .collect()
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].get_accession(), "CON__P00761");
+        assert_eq!(entries[0].get_header(), "CON__P00761 Trypsin");
+        assert_eq!(entries[1].get_accession(), "NP_000549.1");
+    }
 }
@@ -3,33 +3,93 @@ use std::io::BufWriter;
 use std::io::prelude::*;
 use std::path::Path;
 
+use crate::compression::{Compression, Encoder};
+use crate::error::{Error, Result};
 use crate::fasta::entry::Entry;
-use anyhow::Result;
 
 /// Max amino acids per sequence line.
 const MAX_AMINO_ACIDS_PER_LINE: usize = 60;
 
 /// Writer for common FASTA files as distributed by UniProt (https://uniprot.org)
-/// Use flush() to mak ensure the buffer is written completely.
-pub struct Writer {
-    internal_writer: BufWriter<File>
+/// Works on any target implementing `Write`, e.g. files, stdout or `Vec<u8>`.
+/// Use flush() to make sure the buffer is written completely.
+pub struct Writer<W: Write> {
+    internal_writer: W
 }
 
-impl Writer {
-    /// Creates a new Writer
+impl Writer<BufWriter<File>> {
+    /// Creates a new buffered Writer for the given FASTA file
     /// 
     /// # Arguments
     ///
     /// * `fasta_file_path` - Path to FASTA file
     /// 
     pub fn new(fasta_file_path: &Path) -> Result<Self> {
-        let fasta_file: File = File::create(fasta_file_path)?;
-        Ok(Self {
-            internal_writer: BufWriter::new(fasta_file)
-        })
+        let fasta_file: File = File::create(fasta_file_path)
+            .map_err(|err| Error::io_with_path(err, fasta_file_path))?;
+        Ok(Self::from_writer(BufWriter::new(fasta_file)))
+    }
+}
+
+impl Writer<Encoder<BufWriter<File>>> {
+    /// Creates a new buffered Writer for the given FASTA file, compressing the output.
+    /// Use finish() to complete the compressed file.
+    /// 
+    /// # Arguments
+    ///
+    /// * `fasta_file_path` - Path to FASTA file
+    /// * `compression` - Compression of the written file, see `Compression::from_path()`
+    /// 
+    pub fn with_compression(fasta_file_path: &Path, compression: Compression) -> Result<Self> {
+        Ok(Self::from_writer(Encoder::create(fasta_file_path, compression)?))
+    }
+}
+
+impl<W: Write> Writer<Encoder<W>> {
+    /// Flushes the buffer, completes the compressed stream and returns the underlying write target.
+    /// 
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        self.internal_writer.finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new Writer for any write target.
+    /// The target is used as is, so wrap unbuffered targets in a `BufWriter`.
+    /// 
+    /// # Arguments
+    ///
+    /// * `internal_writer` - Write target
+    /// 
+    pub fn from_writer(internal_writer: W) -> Self {
+        Self {
+            internal_writer
+        }
+    }
+
+    /// Returns the underlying write target, e.g. to access an in-memory buffer.
+    /// Make sure to call flush() first.
+    /// 
+    pub fn into_inner(self) -> W {
+        self.internal_writer
+    }
+
+    /// Writes the complete byte slice, as `Write::write()` might only write parts of it
+    /// on unbuffered targets.
+    /// Returns the number of written bytes.
+    /// 
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to write
+    /// 
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.internal_writer.write_all(bytes)?;
+        Ok(bytes.len())
     }
 
     /// Creates a fasta header from the given entry.
+    /// The raw header is used if available, otherwise a UniProt header is created.
     /// 
     /// # Arguments
     ///
@@ -37,17 +97,21 @@ impl Writer {
     /// * `sort_keyword_attributes` - If true the keyword attributes will be sorted (for testing and readability reasons)
     /// 
     fn create_header(entry: &Entry, sort_keyword_attributes: bool) -> String {
+        // Keep raw headers of read entries
+        if !entry.get_header().is_empty() {
+            return format!(">{}", entry.get_header());
+        }
         let mut header = ">".to_string();
         header.push_str(entry.get_database());
-        header.push_str("|");
+        header.push('|');
         header.push_str(entry.get_accession());
-        header.push_str("|");
+        header.push('|');
         header.push_str(entry.get_entry_name());
-        header.push_str(" ");
+        header.push(' ');
         header.push_str(entry.get_protein_name());
-        if entry.get_keyword_attributes().len() > 0 {
-            header.push_str(" ");
-            let mut keyword_arguments: Vec<String> = entry.get_keyword_attributes().into_iter()
+        if !entry.get_keyword_attributes().is_empty() {
+            header.push(' ');
+            let mut keyword_arguments: Vec<String> = entry.get_keyword_attributes().iter()
                 .map(|(key, value)| format!("{}={}", key, value)).collect();
             if sort_keyword_attributes {
                 keyword_arguments.sort();
@@ -55,8 +119,8 @@ impl Writer {
             }
             header.push_str(&keyword_arguments.join(" "));
         }
-        return header;
-    } 
+        header
+    }
 
     /// Splits sequence into chunk of MAX_AMINO_ACIDS_PER_LINE.
     /// 
@@ -64,13 +128,13 @@ impl Writer {
     ///
     /// * `sequence` - Amino acid sequence
     /// 
-    fn format_sequence(sequence: &str) -> String {
-        return sequence.chars()
+    pub(crate) fn format_sequence(sequence: &str) -> String {
+        sequence.chars()
             .collect::<Vec<char>>()
             .chunks(MAX_AMINO_ACIDS_PER_LINE)
             .map(|c| c.iter().collect::<String>())
             .collect::<Vec<String>>()
-            .join("\n");
+            .join("\n")
     }
 
     /// Writes entry into the FASTA file.
@@ -82,11 +146,11 @@ impl Writer {
     /// 
     pub fn write_entry(&mut self, entry: &Entry, sort_keyword_attributes: bool) -> Result<usize> {
         let mut written_bytes: usize = 0;
-        written_bytes += self.internal_writer.write(Self::create_header(entry, sort_keyword_attributes).as_bytes())?;
-        written_bytes += self.internal_writer.write(b"\n")?;
-        written_bytes += self.internal_writer.write(Self::format_sequence(entry.get_sequence()).as_bytes())?;
-        written_bytes += self.internal_writer.write(b"\n")?;
-        return Ok(written_bytes);
+        written_bytes += self.write_bytes(Self::create_header(entry, sort_keyword_attributes).as_bytes())?;
+        written_bytes += self.write_bytes(b"\n")?;
+        written_bytes += self.write_bytes(Self::format_sequence(entry.get_sequence()).as_bytes())?;
+        written_bytes += self.write_bytes(b"\n")?;
+        Ok(written_bytes)
     }
 
     /// Writes multiple FASTA entry to file.
@@ -104,7 +168,7 @@ impl Writer {
         for entry in entries {
             written_bytes += self.write_entry(entry, sort_keyword_attributes)?;
         }
-        return Ok(written_bytes);
+        Ok(written_bytes)
     }
 
     /// Flushes the buffer
@@ -150,7 +214,7 @@ QRSGIVALDGERELAFGPDDEVTVTLHDHAFRSIDVAACMRHAGRHHLMRSLPQPAAVG";
     /// Tests the creation of a FASTA entry from a header and a sequence.
     ///
     fn test_seqeunce_formatting() {
-        let formatted_sequence  = Writer::format_sequence(TEST_SEQUENCE);
+        let formatted_sequence  = Writer::<Vec<u8>>::format_sequence(TEST_SEQUENCE);
         assert_eq!(formatted_sequence, EXPECTED_SEQUENCE)
     }
 
@@ -165,7 +229,30 @@ QRSGIVALDGERELAFGPDDEVTVTLHDHAFRSIDVAACMRHAGRHHLMRSLPQPAAVG";
             TEST_KEYWORD_ATTRIBUTES.into_iter().map(|elem| (elem.0.to_string(), elem.1.to_string())).collect::<HashMap<String, String>>(),
             TEST_SEQUENCE.to_string()
         );
-        let header = Writer::create_header(&entry, true);
+        let header = Writer::<Vec<u8>>::create_header(&entry, true);
         assert_eq!(header, EXPECTED_HEADER);
     }
+
+    #[test]
+    /// Writes an entry into an in-memory buffer.
+    fn test_writing_to_buffer() {
+        let entry = Entry::new(
+            TEST_DATABASE.to_string(),
+            TEST_ACCESSION.to_string(),
+            TEST_ENTRY_NAME.to_string(),
+            TEST_PROTEIN_NAME.to_string(),
+            TEST_KEYWORD_ATTRIBUTES.into_iter().map(|elem| (elem.0.to_string(), elem.1.to_string())).collect::<HashMap<String, String>>(),
+            TEST_SEQUENCE.to_string()
+        );
+        let mut writer = Writer::from_writer(Vec::new());
+        let written_bytes = writer.write_entry(&entry, true).unwrap();
+        writer.flush().unwrap();
+
+        let buffer = writer.into_inner();
+        assert_eq!(written_bytes, buffer.len());
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            format!("{}\n{}\n", EXPECTED_HEADER, EXPECTED_SEQUENCE)
+        );
+    }
 }
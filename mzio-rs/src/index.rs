@@ -0,0 +1,96 @@
+//! Helpers shared by the file indices.
+
+// std imports
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+// internal imports
+use crate::compression::Compression;
+use crate::error::{Error, Position, Result};
+use crate::line_reader::LineReader;
+
+/// Appends an extension to the path, e.g. `db.fasta` => `db.fasta.fai`
+///
+/// # Arguments
+///
+/// * `file_path` - Path
+/// * `extension` - Additional extension
+///
+pub(crate) fn append_extension(file_path: &Path, extension: &str) -> PathBuf {
+    let mut path = file_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    PathBuf::from(path)
+}
+
+/// Opens the given file for indexing.
+/// Compressed files are rejected, as they cannot be accessed randomly.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to file
+///
+pub(crate) fn open_uncompressed(file_path: &Path) -> Result<BufReader<File>> {
+    let file = File::open(file_path).map_err(|err| Error::io_with_path(err, file_path))?;
+    let mut reader = BufReader::new(file);
+    let compression = Compression::detect(
+        reader.fill_buf().map_err(|err| Error::io_with_path(err, file_path))?
+    );
+    if compression != Compression::None {
+        return Err(Error::UnsupportedFormat {
+            message: format!("cannot index {:?} compressed file {}", compression, file_path.display())
+        });
+    }
+    Ok(reader)
+}
+
+/// Reads a tab separated file, returning the fields and position of each line.
+/// Empty lines are skipped, lines starting with '#' only if `skip_comments` is set.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to file
+/// * `num_fields` - Minimum number of fields per line
+/// * `skip_comments` - Skip lines starting with '#', e.g. a commented header
+///
+pub(crate) fn read_tsv(file_path: &Path, num_fields: usize, skip_comments: bool) -> Result<Vec<(Vec<String>, Position)>> {
+    let file = File::open(file_path).map_err(|err| Error::io_with_path(err, file_path))?;
+    let mut internal_reader = LineReader::new(BufReader::new(file), Some(file_path.to_path_buf()));
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    while internal_reader.read_line(&mut line)? > 0 {
+        let trimmed_line = line.trim_end_matches(['\r', '\n']);
+        if trimmed_line.is_empty() || (skip_comments && trimmed_line.starts_with('#')) {
+            continue;
+        }
+        let fields: Vec<String> = trimmed_line.split('\t').map(|field| field.to_string()).collect();
+        if fields.len() < num_fields {
+            return Err(Error::InvalidIndex {
+                message: format!("expected {} fields, found {}", num_fields, fields.len()),
+                position: internal_reader.get_position()
+            });
+        }
+        lines.push((fields, internal_reader.get_position()));
+    }
+    Ok(lines)
+}
+
+/// Parses an integer of an index file.
+///
+/// # Arguments
+///
+/// * `value` - String to parse
+/// * `position` - Position of the line
+///
+pub(crate) fn parse_int<T>(value: &str, position: &Position) -> Result<T>
+where
+    T: std::str::FromStr<Err = std::num::ParseIntError>
+{
+    value.parse().map_err(|source| Error::IntParse {
+        value: value.to_string(),
+        source,
+        position: position.clone()
+    })
+}
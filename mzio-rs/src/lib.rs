@@ -1,2 +1,20 @@
+pub mod compression;
+pub mod error;
 pub mod fasta;
+mod index;
+mod line_reader;
 pub mod mgf;
+pub mod ms2;
+pub mod msp;
+#[cfg(feature = "mzidentml")]
+pub mod mzidentml;
+pub mod mztab;
+#[cfg(feature = "mzml")]
+pub mod mzml;
+#[cfg(feature = "mzxml")]
+pub mod mzxml;
+pub mod sptxt;
+#[cfg(any(feature = "mzidentml", feature = "mzml", feature = "mzxml"))]
+mod xml;
+
+pub use error::{Error, Result};
@@ -0,0 +1,86 @@
+//! Line based reading with position tracking for error reporting.
+
+// std imports
+use std::io::prelude::*;
+use std::path::PathBuf;
+
+// internal imports
+use crate::error::{Error, Position, Result};
+
+/// Wraps a buffered reader, counting lines and bytes,
+/// so parse errors can report where they occurred.
+pub(crate) struct LineReader<R: BufRead> {
+    internal_reader: R,
+    file_path: Option<PathBuf>,
+    line_number: usize,
+    line_offset: u64,
+    byte_offset: u64
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// Creates a new LineReader
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader
+    /// * `file_path` - Path to the read file, if any
+    ///
+    pub fn new(internal_reader: R, file_path: Option<PathBuf>) -> Self {
+        Self {
+            internal_reader,
+            file_path,
+            line_number: 0,
+            line_offset: 0,
+            byte_offset: 0
+        }
+    }
+
+    /// Clears the given buffer and reads the next line into it, including the line ending.
+    /// Returns the number of read bytes, 0 on EOF.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - Buffer for the line
+    ///
+    pub fn read_line(&mut self, line: &mut String) -> Result<usize> {
+        line.clear();
+        let num_bytes = match self.internal_reader.read_line(line) {
+            Ok(num_bytes) => num_bytes,
+            Err(source) => return Err(Error::Io {
+                source,
+                position: Position::new(
+                    self.file_path.clone(),
+                    Some(self.line_number + 1),
+                    Some(self.byte_offset)
+                )
+            })
+        };
+        if num_bytes > 0 {
+            self.line_number += 1;
+            self.line_offset = self.byte_offset;
+            self.byte_offset += num_bytes as u64;
+        }
+        Ok(num_bytes)
+    }
+
+    /// Returns the position of the last read line.
+    ///
+    pub fn get_position(&self) -> Position {
+        Position::new(self.file_path.clone(), Some(self.line_number), Some(self.line_offset))
+    }
+}
+
+/// Splits the first whitespace separated token from the given string.
+/// Returns the token and the remainder without leading whitespace.
+///
+/// # Arguments
+///
+/// * `value` - String to split
+///
+pub(crate) fn split_first_token(value: &str) -> (&str, &str) {
+    let value = value.trim_start();
+    match value.find(|c: char| c.is_ascii_whitespace()) {
+        Some(idx) => (&value[..idx], value[idx..].trim_start()),
+        None => (value, "")
+    }
+}
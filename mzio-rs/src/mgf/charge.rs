@@ -0,0 +1,93 @@
+/// Notation of charges in written MGF files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChargeFormat {
+    /// Mascot notation with trailing sign, e.g. `2+` or `2+ and 3+`
+    #[default]
+    Mascot,
+    /// Plain signed integers, e.g. `2`, `-3` or `2,3`
+    Plain
+}
+
+/// Parses the charges of a `CHARGE=` value, e.g. `2`, `2+`, `3-`, `+2` or `2+ and 3+`.
+/// Returns None if any of the charges is invalid.
+///
+/// # Arguments
+///
+/// * `value` - Value of the `CHARGE=` line
+///
+pub fn parse_charges(value: &str) -> Option<Vec<i8>> {
+    let mut charges: Vec<i8> = Vec::new();
+    for raw_charge in value.split([',', ' ']).filter(|raw_charge| !raw_charge.is_empty() && *raw_charge != "and") {
+        charges.push(parse_charge(raw_charge)?);
+    }
+    if charges.is_empty() {
+        return None;
+    }
+    Some(charges)
+}
+
+/// Parses a single charge with optional leading or trailing sign.
+/// Returns None if the charge is invalid.
+///
+/// # Arguments
+///
+/// * `raw_charge` - Single charge, e.g. `2+`
+///
+pub fn parse_charge(raw_charge: &str) -> Option<i8> {
+    let (is_negative, magnitude) = if let Some(magnitude) = raw_charge.strip_suffix('+') {
+        (false, magnitude)
+    } else if let Some(magnitude) = raw_charge.strip_suffix('-') {
+        (true, magnitude)
+    } else if let Some(magnitude) = raw_charge.strip_prefix('+') {
+        (false, magnitude)
+    } else if let Some(magnitude) = raw_charge.strip_prefix('-') {
+        (true, magnitude)
+    } else {
+        (false, raw_charge)
+    };
+    if !magnitude.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let magnitude: i8 = magnitude.parse().ok()?;
+    Some(if is_negative { -magnitude } else { magnitude })
+}
+
+/// Formats charges in the given notation, e.g. `2+ and 3+`
+///
+/// # Arguments
+///
+/// * `charges` - Charges
+/// * `charge_format` - Notation
+///
+pub fn format_charges(charges: &[i8], charge_format: ChargeFormat) -> String {
+    match charge_format {
+        ChargeFormat::Mascot => charges.iter()
+            .map(|charge| format!("{}{}", charge.unsigned_abs(), if *charge < 0 { '-' } else { '+' }))
+            .collect::<Vec<String>>()
+            .join(" and "),
+        ChargeFormat::Plain => charges.iter()
+            .map(|charge| charge.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Parses single and multiple charges in the supported notations.
+    fn test_charge_parsing() {
+        assert_eq!(parse_charges("2"), Some(vec![2]));
+        assert_eq!(parse_charges("2+"), Some(vec![2]));
+        assert_eq!(parse_charges("3-"), Some(vec![-3]));
+        assert_eq!(parse_charges("-3"), Some(vec![-3]));
+        assert_eq!(parse_charges("2+ and 3+"), Some(vec![2, 3]));
+        assert_eq!(parse_charges("2+,3+"), Some(vec![2, 3]));
+        assert_eq!(parse_charges("2x"), None);
+        assert_eq!(parse_charges(""), None);
+        assert_eq!(format_charges(&[2, -3], ChargeFormat::Mascot), "2+ and 3-");
+        assert_eq!(format_charges(&[2, -3], ChargeFormat::Plain), "2,-3");
+    }
+}
@@ -0,0 +1,342 @@
+// internal imports
+use crate::mgf::charge::{ChargeFormat, format_charges, parse_charges};
+
+/// Mass type of the search (`MASS=`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MassType {
+    Monoisotopic,
+    Average
+}
+
+impl MassType {
+    /// Parses the mass type (case insensitive), None if unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value of the `MASS=` line
+    ///
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "monoisotopic" => Some(Self::Monoisotopic),
+            "average" => Some(Self::Average),
+            _ => None
+        }
+    }
+
+    /// Returns the name as used in MGF files
+    ///
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Monoisotopic => "Monoisotopic",
+            Self::Average => "Average"
+        }
+    }
+}
+
+/// Mass tolerance, e.g. `TOL=10` with `TOLU=ppm`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tolerance {
+    value: f64,
+    unit: Option<String>
+}
+
+impl Tolerance {
+    /// Creates a new tolerance
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Tolerance value
+    /// * `unit` - Unit, e.g. `ppm` or `Da`
+    ///
+    pub fn new(value: f64, unit: Option<String>) -> Self {
+        Self {
+            value,
+            unit
+        }
+    }
+
+    /// Returns the value
+    ///
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the unit
+    ///
+    pub fn get_unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+}
+
+/// Global parameters of a MGF file, preceding the first spectrum.
+/// All parameters, including repeated ones, are kept in their original order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MgfHeader {
+    params: Vec<(String, String)>
+}
+
+impl MgfHeader {
+    /// Keys of the parameters with dedicated getter and setter
+    ///
+    const DEDICATED_KEYS: [&'static str; 10] = [
+        "COM", "SEARCH", "MASS", "DB", "CLE", "CHARGE", "TOL", "TOLU", "ITOL", "ITOLU"
+    ];
+
+    /// Creates a new empty header
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the comment (`COM=`)
+    ///
+    pub fn get_comment(&self) -> Option<&str> {
+        self.get_param("COM")
+    }
+
+    /// Sets the comment (`COM=`)
+    ///
+    /// # Arguments
+    ///
+    /// * `comment` - Comment
+    ///
+    pub fn set_comment(&mut self, comment: Option<String>) {
+        self.set_param("COM", comment);
+    }
+
+    /// Returns the search type (`SEARCH=`), e.g. `MIS`
+    ///
+    pub fn get_search(&self) -> Option<&str> {
+        self.get_param("SEARCH")
+    }
+
+    /// Sets the search type (`SEARCH=`)
+    ///
+    /// # Arguments
+    ///
+    /// * `search` - Search type
+    ///
+    pub fn set_search(&mut self, search: Option<String>) {
+        self.set_param("SEARCH", search);
+    }
+
+    /// Returns the mass type (`MASS=`), None if missing or unknown
+    ///
+    pub fn get_mass(&self) -> Option<MassType> {
+        self.get_param("MASS").and_then(MassType::from_name)
+    }
+
+    /// Sets the mass type (`MASS=`)
+    ///
+    /// # Arguments
+    ///
+    /// * `mass` - Mass type
+    ///
+    pub fn set_mass(&mut self, mass: Option<MassType>) {
+        self.set_param("MASS", mass.map(|mass| mass.as_str().to_string()));
+    }
+
+    /// Returns the database (`DB=`)
+    ///
+    pub fn get_database(&self) -> Option<&str> {
+        self.get_param("DB")
+    }
+
+    /// Sets the database (`DB=`)
+    ///
+    /// # Arguments
+    ///
+    /// * `database` - Database
+    ///
+    pub fn set_database(&mut self, database: Option<String>) {
+        self.set_param("DB", database);
+    }
+
+    /// Returns the enzyme (`CLE=`)
+    ///
+    pub fn get_cleavage(&self) -> Option<&str> {
+        self.get_param("CLE")
+    }
+
+    /// Sets the enzyme (`CLE=`)
+    ///
+    /// # Arguments
+    ///
+    /// * `cleavage` - Enzyme, e.g. `Trypsin`
+    ///
+    pub fn set_cleavage(&mut self, cleavage: Option<String>) {
+        self.set_param("CLE", cleavage);
+    }
+
+    /// Returns the default precursor charges (`CHARGE=`), empty if missing or invalid
+    ///
+    pub fn get_charges(&self) -> Vec<i8> {
+        self.get_param("CHARGE").and_then(parse_charges).unwrap_or_default()
+    }
+
+    /// Sets the default precursor charges (`CHARGE=`), removes them if empty
+    ///
+    /// # Arguments
+    ///
+    /// * `charges` - Charges
+    ///
+    pub fn set_charges(&mut self, charges: Vec<i8>) {
+        let charges = Some(format_charges(&charges, ChargeFormat::default())).filter(|charges| !charges.is_empty());
+        self.set_param("CHARGE", charges);
+    }
+
+    /// Returns the precursor tolerance (`TOL=`, `TOLU=`), None if missing or invalid
+    ///
+    pub fn get_tolerance(&self) -> Option<Tolerance> {
+        self.get_tolerance_param("TOL", "TOLU")
+    }
+
+    /// Sets the precursor tolerance (`TOL=`, `TOLU=`)
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - Tolerance
+    ///
+    pub fn set_tolerance(&mut self, tolerance: Option<Tolerance>) {
+        self.set_tolerance_param("TOL", "TOLU", tolerance);
+    }
+
+    /// Returns the fragment tolerance (`ITOL=`, `ITOLU=`), None if missing or invalid
+    ///
+    pub fn get_fragment_tolerance(&self) -> Option<Tolerance> {
+        self.get_tolerance_param("ITOL", "ITOLU")
+    }
+
+    /// Sets the fragment tolerance (`ITOL=`, `ITOLU=`)
+    ///
+    /// # Arguments
+    ///
+    /// * `fragment_tolerance` - Tolerance
+    ///
+    pub fn set_fragment_tolerance(&mut self, fragment_tolerance: Option<Tolerance>) {
+        self.set_tolerance_param("ITOL", "ITOLU", fragment_tolerance);
+    }
+
+    /// Returns all parameters in their original order
+    ///
+    pub fn get_params(&self) -> &Vec<(String, String)> {
+        &self.params
+    }
+
+    /// Returns the value of the first parameter with the given key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key (without `=`)
+    ///
+    pub fn get_param(&self, key: &str) -> Option<&str> {
+        self.params.iter()
+            .find(|(param_key, _)| param_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Appends a parameter, keeping existing parameters with the same key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key (without `=`)
+    /// * `value` - Value
+    ///
+    pub fn add_param(&mut self, key: String, value: String) {
+        self.params.push((key, value));
+    }
+
+    /// Sets the value of a parameter. Replaces the first parameter with the given key
+    /// and removes the repeated ones, or appends it if the key is new.
+    /// Removes all parameters with the key if the value is None.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key (without `=`)
+    /// * `value` - Value
+    ///
+    pub fn set_param(&mut self, key: &str, mut value: Option<String>) {
+        self.params.retain_mut(|(param_key, param_value)| {
+            if param_key != key {
+                return true;
+            }
+            match value.take() {
+                Some(new_value) => {
+                    *param_value = new_value;
+                    true
+                },
+                None => false
+            }
+        });
+        if let Some(value) = value {
+            self.params.push((key.to_string(), value));
+        }
+    }
+
+    /// Returns the parameters without dedicated getter, e.g. `FORMAT` or `USER01`,
+    /// in their original order
+    ///
+    pub fn get_additional_params(&self) -> impl Iterator<Item = &(String, String)> {
+        self.params.iter().filter(|(key, _)| !Self::has_dedicated_field(key))
+    }
+
+    /// Returns true if the parameter with the given key has a dedicated getter and setter
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key (without `=`)
+    ///
+    pub fn has_dedicated_field(key: &str) -> bool {
+        Self::DEDICATED_KEYS.contains(&key)
+    }
+
+    /// Returns true if no parameter is set
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Returns the header as ordered key value pairs, as written to the file.
+    /// The charges are written in the given notation, all other values as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `charge_format` - Notation of the charges
+    ///
+    pub fn to_params(&self, charge_format: ChargeFormat) -> Vec<(String, String)> {
+        self.params.iter()
+            .map(|(key, value)| {
+                let value = match parse_charges(value) {
+                    Some(charges) if key == "CHARGE" => format_charges(&charges, charge_format),
+                    _ => value.clone()
+                };
+                (key.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Returns the tolerance from the given value and unit parameters
+    ///
+    /// # Arguments
+    ///
+    /// * `value_key` - Key of the value
+    /// * `unit_key` - Key of the unit
+    ///
+    fn get_tolerance_param(&self, value_key: &str, unit_key: &str) -> Option<Tolerance> {
+        let value = fast_float::parse(self.get_param(value_key)?).ok()?;
+        Some(Tolerance::new(value, self.get_param(unit_key).map(|unit| unit.to_string())))
+    }
+
+    /// Sets the given value and unit parameters to the tolerance
+    ///
+    /// # Arguments
+    ///
+    /// * `value_key` - Key of the value
+    /// * `unit_key` - Key of the unit
+    /// * `tolerance` - Tolerance
+    ///
+    fn set_tolerance_param(&mut self, value_key: &str, unit_key: &str, tolerance: Option<Tolerance>) {
+        self.set_param(value_key, tolerance.as_ref().map(|tolerance| tolerance.value.to_string()));
+        self.set_param(unit_key, tolerance.and_then(|tolerance| tolerance.unit));
+    }
+}
@@ -0,0 +1,363 @@
+// std imports
+use std::collections::HashMap;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+// internal imports
+use crate::error::{Error, Result, parse_float};
+use crate::index::{append_extension, open_uncompressed, parse_int, read_tsv};
+use crate::line_reader::LineReader;
+use crate::mgf::charge::{ChargeFormat, format_charges, parse_charges};
+
+/// Extension of the persisted MGF index
+pub const MGF_INDEX_EXTENSION: &str = "idx";
+
+/// Header of the persisted MGF index
+const MGF_INDEX_HEADER: &str = "#offset\ttitle\tprecursor_mz\tretention_time\tcharge\tscan";
+
+/// Index entry of a single spectrum
+#[derive(Debug, Clone, PartialEq)]
+pub struct MgfIndexEntry {
+    offset: u64,
+    title: String,
+    precursor_mz: f64,
+    retention_time: Option<f64>,
+    precursor_charges: Vec<i8>,
+    scan: Option<u32>
+}
+
+impl MgfIndexEntry {
+    /// Returns the byte offset of the `BEGIN IONS` line
+    ///
+    pub fn get_offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the title
+    ///
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the precursor m/z
+    ///
+    pub fn get_precursor_mz(&self) -> f64 {
+        self.precursor_mz
+    }
+
+    /// Returns the retention time
+    ///
+    pub fn get_retention_time(&self) -> Option<f64> {
+        self.retention_time
+    }
+
+    /// Returns the precursor charges, empty if unknown
+    ///
+    pub fn get_precursor_charges(&self) -> &Vec<i8> {
+        &self.precursor_charges
+    }
+
+    /// Returns the scan number, taken from `SCANS=` or the title
+    ///
+    pub fn get_scan(&self) -> Option<u32> {
+        self.scan
+    }
+}
+
+/// Index of a MGF file, persisted as tab separated sidecar file (`.idx`).
+/// Titles must not contain tabs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MgfIndex {
+    entries: Vec<MgfIndexEntry>,
+    titles: HashMap<String, usize>,
+    scans: HashMap<u32, usize>
+}
+
+impl MgfIndex {
+    /// Creates a new index from the given entries
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - Entries in file order
+    ///
+    pub fn new(entries: Vec<MgfIndexEntry>) -> Self {
+        let titles = entries.iter().enumerate()
+            .map(|(idx, entry)| (entry.title.clone(), idx))
+            .collect();
+        let scans = entries.iter().enumerate()
+            .filter_map(|(idx, entry)| Some((entry.scan?, idx)))
+            .collect();
+        Self {
+            entries,
+            titles,
+            scans
+        }
+    }
+
+    /// Returns the path of the index file for the given MGF file
+    ///
+    /// # Arguments
+    ///
+    /// * `mgf_file_path` - Path to MGF file
+    ///
+    pub fn get_index_path(mgf_file_path: &Path) -> PathBuf {
+        append_extension(mgf_file_path, MGF_INDEX_EXTENSION)
+    }
+
+    /// Builds the index by scanning the given MGF file.
+    /// Compressed files are not supported, as they cannot be accessed randomly.
+    ///
+    /// # Arguments
+    ///
+    /// * `mgf_file_path` - Path to MGF file
+    ///
+    pub fn build(mgf_file_path: &Path) -> Result<Self> {
+        Self::build_from_line_reader(LineReader::new(
+            open_uncompressed(mgf_file_path)?,
+            Some(mgf_file_path.to_path_buf())
+        ))
+    }
+
+    /// Builds the index by scanning the given MGF content.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Buffered reader providing uncompressed MGF content
+    ///
+    pub fn build_from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        Self::build_from_line_reader(LineReader::new(reader, None))
+    }
+
+    /// Builds the index, only parsing the spectrum headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Line reader providing the MGF content
+    ///
+    fn build_from_line_reader<R: BufRead>(mut internal_reader: LineReader<R>) -> Result<Self> {
+        let mut entries: Vec<MgfIndexEntry> = Vec::new();
+        let mut current: Option<MgfIndexEntry> = None;
+        let mut is_precursor_read = false;
+        let mut line = String::new();
+        loop {
+            if internal_reader.read_line(&mut line)? == 0 {
+                if current.is_some() {
+                    return Err(Error::UnterminatedSpectrum {
+                        position: internal_reader.get_position()
+                    });
+                }
+                break;
+            }
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(|c: char| c.is_ascii_digit()) {
+                continue;
+            }
+            if line == "BEGIN IONS" {
+                current = Some(MgfIndexEntry {
+                    offset: internal_reader.get_position().get_byte_offset().unwrap_or_default(),
+                    title: String::new(),
+                    precursor_mz: 0.0,
+                    retention_time: None,
+                    precursor_charges: Vec::new(),
+                    scan: None
+                });
+                is_precursor_read = false;
+                continue;
+            }
+            let entry = match current.as_mut() {
+                Some(entry) => entry,
+                None => continue
+            };
+            if line == "END IONS" {
+                let mut entry = current.take().unwrap();
+                if entry.scan.is_none() {
+                    entry.scan = parse_scan_from_title(&entry.title);
+                }
+                entries.push(entry);
+            } else if let Some(value) = line.strip_prefix("TITLE=") {
+                entry.title = value.to_string();
+            } else if let Some(value) = line.strip_prefix("PEPMASS=") {
+                // Further precursors of chimeric spectra are not indexed
+                if !is_precursor_read {
+                    let value = value.split_ascii_whitespace().next().unwrap_or_default();
+                    entry.precursor_mz = parse_float(value, || internal_reader.get_position())?;
+                    is_precursor_read = true;
+                }
+            } else if let Some(value) = line.strip_prefix("RTINSECONDS=") {
+                entry.retention_time = Some(parse_float(value, || internal_reader.get_position())?);
+            } else if let Some(value) = line.strip_prefix("CHARGE=") {
+                entry.precursor_charges = parse_charges(value).ok_or_else(|| Error::ChargeParse {
+                    value: value.to_string(),
+                    position: internal_reader.get_position()
+                })?;
+            } else if let Some(value) = line.strip_prefix("SCANS=") {
+                // Ranges like `1234-1236` are referenced by the first scan
+                let value = value.split('-').next().unwrap_or_default();
+                entry.scan = Some(parse_int(value, &internal_reader.get_position())?);
+            }
+        }
+        Ok(Self::new(entries))
+    }
+
+    /// Reads the index of the given MGF file.
+    ///
+    /// # Arguments
+    ///
+    /// * `mgf_file_path` - Path to MGF file
+    ///
+    pub fn read(mgf_file_path: &Path) -> Result<Self> {
+        Self::read_from_path(&Self::get_index_path(mgf_file_path))
+    }
+
+    /// Reads the index from the given index file.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_file_path` - Path to the index file
+    ///
+    pub fn read_from_path(index_file_path: &Path) -> Result<Self> {
+        let mut entries: Vec<MgfIndexEntry> = Vec::new();
+        for (fields, position) in read_tsv(index_file_path, 6, true)? {
+            entries.push(MgfIndexEntry {
+                offset: parse_int(&fields[0], &position)?,
+                title: fields[1].clone(),
+                precursor_mz: parse_float(&fields[2], || position.clone())?,
+                retention_time: parse_optional(&fields[3], || parse_float(&fields[3], || position.clone()))?,
+                precursor_charges: parse_optional(&fields[4], || parse_charges(&fields[4]).ok_or_else(|| Error::ChargeParse {
+                    value: fields[4].clone(),
+                    position: position.clone()
+                }))?.unwrap_or_default(),
+                scan: parse_optional(&fields[5], || parse_int(&fields[5], &position))?
+            });
+        }
+        Ok(Self::new(entries))
+    }
+
+    /// Writes the index file next to the given MGF file.
+    ///
+    /// # Arguments
+    ///
+    /// * `mgf_file_path` - Path to MGF file
+    ///
+    pub fn write(&self, mgf_file_path: &Path) -> Result<()> {
+        self.write_to_path(&Self::get_index_path(mgf_file_path))
+    }
+
+    /// Writes the index to the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_file_path` - Path to the index file
+    ///
+    pub fn write_to_path(&self, index_file_path: &Path) -> Result<()> {
+        let format_optional = |value: Option<String>| value.unwrap_or_default();
+        let mut content = format!("{}\n", MGF_INDEX_HEADER);
+        for entry in self.entries.iter() {
+            content.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                entry.offset,
+                entry.title,
+                entry.precursor_mz,
+                format_optional(entry.retention_time.map(|value| value.to_string())),
+                format_charges(&entry.precursor_charges, ChargeFormat::Plain),
+                format_optional(entry.scan.map(|value| value.to_string()))
+            ));
+        }
+        std::fs::write(index_file_path, content)
+            .map_err(|err| Error::io_with_path(err, index_file_path))
+    }
+
+    /// Returns all entries in file order
+    ///
+    pub fn get_entries(&self) -> &Vec<MgfIndexEntry> {
+        &self.entries
+    }
+
+    /// Returns the entry with the given title
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - Spectrum title
+    ///
+    pub fn get_entry_by_title(&self, title: &str) -> Option<&MgfIndexEntry> {
+        self.titles.get(title).map(|idx| &self.entries[*idx])
+    }
+
+    /// Returns the entry with the given scan number
+    ///
+    /// # Arguments
+    ///
+    /// * `scan` - Scan number
+    ///
+    pub fn get_entry_by_scan(&self, scan: u32) -> Option<&MgfIndexEntry> {
+        self.scans.get(&scan).map(|idx| &self.entries[*idx])
+    }
+
+    /// Returns the number of indexed spectra
+    ///
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no spectra are indexed
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Parses an optional value, empty strings are None.
+///
+/// # Arguments
+///
+/// * `value` - String to parse
+/// * `parse` - Parser for non-empty values
+///
+fn parse_optional<T, F>(value: &str, parse: F) -> Result<Option<T>>
+where
+    F: FnOnce() -> Result<T>
+{
+    if value.is_empty() {
+        Ok(None)
+    } else {
+        parse().map(Some)
+    }
+}
+
+/// Extracts the scan number from common title formats, e.g.
+/// * `... scan=1234 ...` (Thermo native IDs)
+/// * `run.1234.1234.2` (Trans-Proteomic Pipeline)
+///
+/// # Arguments
+///
+/// * `title` - Spectrum title
+///
+fn parse_scan_from_title(title: &str) -> Option<u32> {
+    if let Some((_, native_id)) = title.split_once("scan=") {
+        let scan: String = native_id.chars().take_while(|c| c.is_ascii_digit()).collect();
+        return scan.parse().ok();
+    }
+    let mut split = title.trim().rsplitn(4, '.');
+    let _charge: u8 = split.next()?.parse().ok()?;
+    let last_scan: u32 = split.next()?.parse().ok()?;
+    let first_scan: u32 = split.next()?.parse().ok()?;
+    split.next()?;
+    if first_scan <= last_scan {
+        Some(first_scan)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Extracts scan numbers from Thermo native IDs and TPP titles.
+    fn test_parse_scan_from_title() {
+        assert_eq!(parse_scan_from_title("controllerType=0 controllerNumber=1 scan=4711"), Some(4711));
+        assert_eq!(parse_scan_from_title("Velos005137.1234.1234.2"), Some(1234));
+        assert_eq!(parse_scan_from_title("824.836730957031_212.9232"), None);
+    }
+}
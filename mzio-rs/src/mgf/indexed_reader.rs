@@ -0,0 +1,191 @@
+// std imports
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, SeekFrom};
+use std::path::{Path, PathBuf};
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+
+// internal imports
+use crate::error::{Error, Position, Result};
+use crate::line_reader::LineReader;
+use crate::mgf::header::MgfHeader;
+use crate::mgf::index::MgfIndex;
+use crate::mgf::reader::Reader;
+use crate::mgf::spectrum::Spectrum;
+
+/// Reader for random access to the spectra of an uncompressed MGF file using a `MgfIndex`.
+pub struct IndexedReader<R: BufRead + Seek> {
+    internal_reader: R,
+    file_path: Option<PathBuf>,
+    index: MgfIndex,
+    header: MgfHeader,
+    apply_header_defaults: bool
+}
+
+impl IndexedReader<BufReader<File>> {
+    /// Creates a new IndexedReader for the given MGF file.
+    /// The index is read from the index file if it exists,
+    /// otherwise it is built (see `MgfIndex::write()` to persist it).
+    ///
+    /// # Arguments
+    ///
+    /// * `mgf_file_path` - Path to MGF file
+    ///
+    pub fn new(mgf_file_path: &Path) -> Result<Self> {
+        let index = if MgfIndex::get_index_path(mgf_file_path).is_file() {
+            MgfIndex::read(mgf_file_path)?
+        } else {
+            MgfIndex::build(mgf_file_path)?
+        };
+        Self::with_index(mgf_file_path, index)
+    }
+
+    /// Creates a new IndexedReader for the given MGF file and index.
+    ///
+    /// # Arguments
+    ///
+    /// * `mgf_file_path` - Path to MGF file
+    /// * `index` - Index of the MGF file
+    ///
+    pub fn with_index(mgf_file_path: &Path, index: MgfIndex) -> Result<Self> {
+        let mgf_file = File::open(mgf_file_path)
+            .map_err(|err| Error::io_with_path(err, mgf_file_path))?;
+        Self::open(BufReader::new(mgf_file), index, Some(mgf_file_path.to_path_buf()))
+    }
+}
+
+impl<R: BufRead + Seek> IndexedReader<R> {
+    /// Creates a new IndexedReader from any buffered and seekable source.
+    /// Reads the global parameters preceding the first spectrum once.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the uncompressed MGF content
+    /// * `index` - Index of the content
+    ///
+    pub fn from_reader(internal_reader: R, index: MgfIndex) -> Result<Self> {
+        Self::open(internal_reader, index, None)
+    }
+
+    /// Creates a new IndexedReader and reads the global parameters preceding the first spectrum.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the uncompressed MGF content
+    /// * `index` - Index of the content
+    /// * `file_path` - Path of the MGF file, used for error positions
+    ///
+    fn open(mut internal_reader: R, index: MgfIndex, file_path: Option<PathBuf>) -> Result<Self> {
+        internal_reader.seek(SeekFrom::Start(0))?;
+        let header = Reader::from_line_reader(LineReader::new(&mut internal_reader, file_path.clone()))
+            .get_header()?
+            .clone();
+        Ok(Self {
+            internal_reader,
+            file_path,
+            index,
+            header,
+            apply_header_defaults: true
+        })
+    }
+
+    /// Enables or disables applying the header defaults, e.g. the global `CHARGE=`,
+    /// to spectra without their own value. Enabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `apply_header_defaults` - Apply header defaults
+    ///
+    pub fn with_header_defaults(mut self, apply_header_defaults: bool) -> Self {
+        self.apply_header_defaults = apply_header_defaults;
+        self
+    }
+
+    /// Returns the global parameters preceding the first spectrum
+    ///
+    pub fn get_header(&self) -> &MgfHeader {
+        &self.header
+    }
+
+    /// Returns the index
+    ///
+    pub fn get_index(&self) -> &MgfIndex {
+        &self.index
+    }
+
+    /// Returns the spectrum with the given title, None if the title is not indexed.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - Spectrum title
+    ///
+    pub fn get_spectrum_by_title(&mut self, title: &str) -> Result<Option<Spectrum>> {
+        match self.index.get_entry_by_title(title).map(|entry| entry.get_offset()) {
+            Some(offset) => self.get_spectrum_by_offset(offset).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    /// Returns the spectrum with the given scan number, None if the scan is not indexed.
+    ///
+    /// # Arguments
+    ///
+    /// * `scan` - Scan number
+    ///
+    pub fn get_spectrum_by_scan(&mut self, scan: u32) -> Result<Option<Spectrum>> {
+        match self.index.get_entry_by_scan(scan).map(|entry| entry.get_offset()) {
+            Some(offset) => self.get_spectrum_by_offset(offset).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    /// Returns the spectrum at the given position in the file, None if the position is out of range.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Position of the spectrum (0-based)
+    ///
+    pub fn get_spectrum_by_position(&mut self, position: usize) -> Result<Option<Spectrum>> {
+        match self.index.get_entries().get(position).map(|entry| entry.get_offset()) {
+            Some(offset) => self.get_spectrum_by_offset(offset).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    /// Reads the spectrum starting at the given byte offset.
+    /// Returns an error if the offset does not point to a `BEGIN IONS` line.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Byte offset of the `BEGIN IONS` line
+    ///
+    pub fn get_spectrum_by_offset(&mut self, offset: u64) -> Result<Spectrum> {
+        let position = Position::new(self.file_path.clone(), None, Some(offset));
+        let mut line = String::new();
+        self.internal_reader.seek(SeekFrom::Start(offset))
+            .and_then(|_| self.internal_reader.read_line(&mut line))
+            .and_then(|_| self.internal_reader.seek(SeekFrom::Start(offset)))
+            .map_err(|source| Error::Io {
+                source,
+                position: position.clone()
+            })?;
+        if line.trim() != "BEGIN IONS" {
+            return Err(Error::InvalidIndex {
+                message: format!("no `BEGIN IONS` at offset {}, the index may be outdated", offset),
+                position
+            });
+        }
+        let mut reader = Reader::from_reader(&mut self.internal_reader)
+            .with_header(self.header.clone())
+            .with_header_defaults(self.apply_header_defaults);
+        match reader.next()? {
+            Some(spectrum) => Ok(spectrum),
+            None => Err(Error::InvalidIndex {
+                message: format!("no spectrum at offset {}, the index may be outdated", offset),
+                position
+            })
+        }
+    }
+}
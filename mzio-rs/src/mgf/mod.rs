@@ -1,8 +1,13 @@
-/// Module for dealing with MGF files
+//! Module for dealing with MGF files
 
+pub mod charge;
+pub mod header;
+pub mod index;
+pub mod indexed_reader;
 pub mod reader;
 pub mod spectrum;
 pub mod writer;
+pub mod writer_config;
 
 
 #[cfg(test)]
@@ -19,6 +24,9 @@ mod test {
     const MGF_FILE_PATH_STR: &'static str = "../test_files/mgf/Velos005137.mgf";
     const EXPECTED_NUM_SPECTRA: usize = 100;
     const TEMP_MGF_PATH_STR: &'static str = "../test_files/mgf/Velos005137.mgf.tmp";
+    const TEMP_INDEX_MGF_PATH_STR: &str = "../test_files/mgf/Velos005137.index.tmp.mgf";
+    #[cfg(feature = "gzip")]
+    const TEMP_GZIP_MGF_PATH_STR: &str = "../test_files/mgf/Velos005137.mgf.tmp.gz";
 
     #[test]
     /// Reads a MGF file, parses the spectra,
@@ -35,9 +43,12 @@ mod test {
         let entries: Vec<spectrum::Spectrum> = reader.into_fallible_iter().collect().unwrap();
         assert_eq!(entries.len(), EXPECTED_NUM_SPECTRA);
 
+        // The test file uses plain charges (`CHARGE=2`)
         let mut writer = writer::Writer::new(
             tmp_mgf_file_path
-        ).unwrap();
+        ).unwrap().with_config(
+            writer_config::WriterConfig::default().with_charge_format(charge::ChargeFormat::Plain)
+        );
 
         writer.write_all(entries.iter()).unwrap();
         writer.flush().unwrap();
@@ -56,4 +67,433 @@ mod test {
             )
         }
     }
+
+    #[test]
+    /// Reads spectra from an in-memory buffer.
+    fn test_reading_from_buffer() {
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\nCHARGE=2+ and 3+\n100.1 20.5\n200.2 30\nEND IONS\n";
+        let reader = reader::Reader::from_reader(mgf_content.as_bytes());
+
+        let spectra: Vec<spectrum::Spectrum> = reader.into_fallible_iter().collect().unwrap();
+        assert_eq!(spectra.len(), 1);
+        assert_eq!(spectra[0].get_title(), "test");
+        assert_eq!(spectra[0].get_precursor_mz(), 500.5);
+        assert_eq!(spectra[0].get_precursor_intensity(), None);
+        assert_eq!(spectra[0].get_precursor_charges(), &vec![2, 3]);
+        assert_eq!(spectra[0].get_mz_list(), &vec![100.1, 200.2]);
+        assert_eq!(spectra[0].get_intensity_list(), &vec![20.5, 30.0]);
+    }
+
+    #[test]
+    /// Writes spectra into an in-memory buffer and reads them back.
+    fn test_writing_to_buffer() {
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\nRTINSECONDS=12.5\nCHARGE=2+\n100.1 20.5\n200.2 30\nEND IONS\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mgf_content.as_bytes())
+            .into_fallible_iter()
+            .collect()
+            .unwrap();
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        let written_bytes = writer.write_all(spectra.iter()).unwrap();
+        writer.flush().unwrap();
+
+        let buffer = writer.into_inner();
+        assert_eq!(written_bytes, buffer.len());
+        assert_eq!(String::from_utf8(buffer).unwrap(), mgf_content);
+    }
+
+    #[test]
+    /// Reads and writes precursor intensities and multiple precursors.
+    fn test_precursors_roundtrip() {
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=824.83 15433.2\nPEPMASS=500.5\nCHARGE=2+\n100.1 20.5\nEND IONS\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mgf_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert_eq!(spectra[0].get_precursor_mz(), 824.83);
+        assert_eq!(spectra[0].get_precursor_intensity(), Some(15433.2));
+        assert_eq!(spectra[0].get_precursors()[1], spectrum::Precursor::new(500.5, None));
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), mgf_content);
+    }
+
+    #[test]
+    /// Writes `PEPMASS=0` for spectra without precursor.
+    fn test_writing_without_precursor() {
+        let spectrum = spectrum::Spectrum::new("test".to_string(), Vec::new(), Vec::new(), None, vec![100.1], vec![20.5]);
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_spectrum(&spectrum).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "BEGIN IONS\nTITLE=test\nPEPMASS=0\n100.1 20.5\nEND IONS\n"
+        );
+    }
+
+    #[test]
+    /// Reads the precursor charge of `PEPMASS=`, which is used if there is no `CHARGE=`.
+    fn test_precursor_charge_in_pepmass() {
+        let read = |mgf_content: &str| reader::Reader::from_reader(mgf_content.as_bytes())
+            .collect::<Vec<spectrum::Spectrum>>();
+        let spectra = read("BEGIN IONS\nTITLE=test\nPEPMASS=500.5 1200 3+\n100.1 20.5\nEND IONS\n").unwrap();
+        assert_eq!(spectra[0].get_precursors(), &vec![spectrum::Precursor::new(500.5, Some(1200.0))]);
+        assert_eq!(spectra[0].get_precursor_charges(), &vec![3]);
+
+        let spectra = read("BEGIN IONS\nTITLE=test\nPEPMASS=500.5 1200 3+\nCHARGE=2+\n100.1 20.5\nEND IONS\n").unwrap();
+        assert_eq!(spectra[0].get_precursor_charges(), &vec![2]);
+
+        let error = read("BEGIN IONS\nTITLE=test\nPEPMASS=500.5 1200 x\nEND IONS\n").unwrap_err();
+        assert!(matches!(error, crate::Error::ChargeParse { .. }));
+        let error = read("BEGIN IONS\nTITLE=test\nPEPMASS=500.5 1200 3+ 4\nEND IONS\n").unwrap_err();
+        assert!(matches!(error, crate::Error::InvalidContent { .. }));
+    }
+
+    #[test]
+    /// Reads and writes peak charges and annotations.
+    fn test_peak_annotations_roundtrip() {
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\n100.1 20.5\n250.1 1200 2+\n\
+            300.2 40 1+ \"y3-H2O\"\n400.3 50 b4\n500.4 60\nEND IONS\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mgf_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert_eq!(spectra[0].get_peak_charge_list(), &vec![None, Some(2), Some(1), None, None]);
+        assert_eq!(
+            spectra[0].get_peak_annotation_list(),
+            &vec![None, None, Some("\"y3-H2O\"".to_string()), Some("b4".to_string()), None]
+        );
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), mgf_content);
+
+        // Spectra without peak charges and annotations do not allocate them
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader("BEGIN IONS\n100.1 20.5\nEND IONS\n".as_bytes())
+            .collect()
+            .unwrap();
+        assert!(spectra[0].get_peak_charge_list().is_empty());
+        assert!(spectra[0].get_peak_annotation_list().is_empty());
+    }
+
+    #[test]
+    /// Writes spectra with a custom number formatting and a reduced set of fields.
+    fn test_writing_with_config() {
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5 1000\nRTINSECONDS=12.5\nCHARGE=2\nSCANS=42\n\
+            100.123456 20.5\n200.2 0\n300.3 1200.75 1+ b3\nEND IONS\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mgf_content.as_bytes())
+            .collect()
+            .unwrap();
+
+        let config = writer_config::WriterConfig::new()
+            .with_mz_format(writer_config::FloatFormat::Fixed(3))
+            .with_intensity_format(writer_config::FloatFormat::Scientific(2))
+            .with_charge_format(charge::ChargeFormat::Plain)
+            .with_line_ending(writer_config::LineEnding::CrLf)
+            .with_omit_zero_intensities(true)
+            .with_retention_time(false)
+            .with_precursor_intensity(false)
+            .with_additional_params(false)
+            .with_peak_annotations(false);
+        let mut writer = writer::Writer::from_writer(Vec::new()).with_config(config);
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "BEGIN IONS\r\nTITLE=test\r\nPEPMASS=500.500\r\nCHARGE=2\r\n\
+            100.123 2.05e1\r\n300.300 1.20e3 1\r\nEND IONS\r\n"
+        );
+
+        let config = writer_config::WriterConfig::new()
+            .with_mz_format(writer_config::FloatFormat::Fixed(2))
+            .with_intensity_format(writer_config::FloatFormat::Fixed(0))
+            .with_retention_time_format(writer_config::FloatFormat::Fixed(3))
+            .with_charges(false)
+            .with_additional_params(false);
+        let mut header = header::MgfHeader::new();
+        header.set_mass(Some(header::MassType::Monoisotopic));
+        header.set_charges(vec![2]);
+        header.add_param("FORMAT".to_string(), "Mascot generic".to_string());
+        let mut writer = writer::Writer::from_writer(Vec::new()).with_config(config);
+        writer.write_header(&header).unwrap();
+        writer.write_all(spectra.iter().take(1)).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "MASS=Monoisotopic\nBEGIN IONS\nTITLE=test\nPEPMASS=500.50 1000\nRTINSECONDS=12.500\n\
+            100.12 20\n200.20 0\n300.30 1201 1+ b3\nEND IONS\n"
+        );
+    }
+
+    #[test]
+    /// Reads and writes spectrum parameters without dedicated field.
+    fn test_additional_params_roundtrip() {
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\nCHARGE=2+\n\
+            SCANS=42\nSEQ=PEPTIDEK\nUSER01=x=y\n100.1 20.5\nEND IONS\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mgf_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert_eq!(
+            spectra[0].get_additional_params(),
+            &vec![
+                ("SCANS".to_string(), "42".to_string()),
+                ("SEQ".to_string(), "PEPTIDEK".to_string()),
+                ("USER01".to_string(), "x=y".to_string())
+            ]
+        );
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), mgf_content);
+    }
+
+    #[test]
+    /// Keeps repeated spectrum parameters in their original order.
+    fn test_repeated_additional_params_roundtrip() {
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\nCOM=first\nUSER01=a\n\
+            COM=second\nUSER01=b\n100.1 20.5\nEND IONS\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mgf_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert_eq!(
+            spectra[0].get_additional_params(),
+            &vec![
+                ("COM".to_string(), "first".to_string()),
+                ("USER01".to_string(), "a".to_string()),
+                ("COM".to_string(), "second".to_string()),
+                ("USER01".to_string(), "b".to_string())
+            ]
+        );
+        assert_eq!(spectra[0].get_additional_param("COM"), Some("first"));
+        assert_eq!(spectra[0].get_additional_param("SEQ"), None);
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), mgf_content);
+    }
+
+    #[test]
+    /// Rejects parameters between two spectra instead of dropping them.
+    fn test_params_between_spectra() {
+        let mgf_content = "BEGIN IONS\nTITLE=first\nPEPMASS=500.5\n100.1 20.5\nEND IONS\n\
+            SCANS=42\nBEGIN IONS\nTITLE=second\nPEPMASS=600.5\n100.1 20.5\nEND IONS\n";
+        let mut reader = reader::Reader::from_reader(mgf_content.as_bytes());
+        assert_eq!(reader.next().unwrap().unwrap().get_title(), "first");
+        let error = reader.next().unwrap_err();
+        assert!(matches!(error, crate::Error::InvalidContent { .. }));
+        assert_eq!(error.get_position().unwrap().get_line_number(), Some(6));
+    }
+
+    #[test]
+    /// Reads and writes the global parameters and applies the default charge.
+    fn test_header_roundtrip() {
+        let mgf_content = "COM=test search\nSEARCH=MIS\nMASS=Monoisotopic\nCLE=Trypsin\nCHARGE=2+\n\
+            TOL=10\nTOLU=ppm\nITOL=0.5\nITOLU=Da\nFORMAT=Mascot generic\n\
+            BEGIN IONS\nTITLE=test\nPEPMASS=500.5\n100.1 20.5\nEND IONS\n";
+        let mut reader = reader::Reader::from_reader(mgf_content.as_bytes());
+        let header = reader.get_header().unwrap().clone();
+        assert_eq!(header.get_mass(), Some(header::MassType::Monoisotopic));
+        assert_eq!(header.get_charges(), vec![2]);
+        assert_eq!(header.get_tolerance().unwrap().get_value(), 10.0);
+        assert_eq!(header.get_fragment_tolerance().unwrap().get_unit(), Some("Da"));
+        assert_eq!(
+            header.get_additional_params().collect::<Vec<_>>(),
+            vec![&("FORMAT".to_string(), "Mascot generic".to_string())]
+        );
+        let spectra: Vec<spectrum::Spectrum> = reader.collect().unwrap();
+        assert_eq!(spectra[0].get_precursor_charges(), &vec![2]);
+
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mgf_content.as_bytes())
+            .with_header_defaults(false)
+            .collect()
+            .unwrap();
+        assert!(spectra[0].get_precursor_charges().is_empty());
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_header(&header).unwrap();
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), mgf_content);
+
+        let mut reader = reader::Reader::from_reader("MASS=Heavy\n".as_bytes());
+        assert!(matches!(reader.get_header().unwrap_err(), crate::Error::MalformedHeader { .. }));
+    }
+
+    #[test]
+    /// Keeps the global parameters, including repeated ones, in their original order.
+    fn test_header_order_roundtrip() {
+        let mgf_content = "CHARGE=2+\nCOM=first\nUSER01=a\nCOM=second\nUSER01=b\n\
+            BEGIN IONS\nTITLE=test\nPEPMASS=500.5\n100.1 20.5\nEND IONS\n";
+        let mut reader = reader::Reader::from_reader(mgf_content.as_bytes());
+        let mut header = reader.get_header().unwrap().clone();
+        assert_eq!(header.get_comment(), Some("first"));
+        assert_eq!(
+            header.get_additional_params().collect::<Vec<_>>(),
+            vec![&("USER01".to_string(), "a".to_string()), &("USER01".to_string(), "b".to_string())]
+        );
+        let spectra: Vec<spectrum::Spectrum> = reader.with_header_defaults(false).collect().unwrap();
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_header(&header).unwrap();
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), mgf_content);
+
+        header.set_comment(Some("only".to_string()));
+        header.set_charges(Vec::new());
+        header.set_mass(Some(header::MassType::Average));
+        assert_eq!(
+            header.to_params(charge::ChargeFormat::default()),
+            vec![
+                ("COM".to_string(), "only".to_string()),
+                ("USER01".to_string(), "a".to_string()),
+                ("USER01".to_string(), "b".to_string()),
+                ("MASS".to_string(), "Average".to_string())
+            ]
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    /// Reads a MGF file, writes it gzip compressed into a temporary file
+    /// and reads the compressed file back.
+    fn test_reading_and_writing_compressed() {
+        let mgf_file_path = Path::new(MGF_FILE_PATH_STR);
+        let tmp_mgf_file_path = Path::new(TEMP_GZIP_MGF_PATH_STR);
+
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::new(mgf_file_path, 1024).unwrap()
+            .into_fallible_iter()
+            .collect()
+            .unwrap();
+
+        let mut writer = writer::Writer::with_compression(
+            tmp_mgf_file_path,
+            crate::compression::Compression::Gzip
+        ).unwrap();
+        writer.write_all(spectra.iter()).unwrap();
+        writer.finish().unwrap();
+
+        let compressed_spectra: Vec<spectrum::Spectrum> = reader::Reader::new(tmp_mgf_file_path, 1024).unwrap()
+            .into_fallible_iter()
+            .collect()
+            .unwrap();
+        fs::remove_file(tmp_mgf_file_path).unwrap();
+
+        assert_eq!(compressed_spectra.len(), EXPECTED_NUM_SPECTRA);
+        for (spectrum, compressed_spectrum) in zip(spectra.iter(), compressed_spectra.iter()) {
+            assert_eq!(spectrum.get_title(), compressed_spectrum.get_title());
+            assert_eq!(spectrum.get_mz_list(), compressed_spectrum.get_mz_list());
+        }
+    }
+
+    #[test]
+    /// Tests that malformed spectra are reported with their position.
+    fn test_reading_malformed_input() {
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\n100.1\nEND IONS\n";
+        let mut reader = reader::Reader::from_reader(mgf_content.as_bytes());
+        match reader.next().unwrap_err() {
+            crate::Error::MissingPeakValue { value_name, position } => {
+                assert_eq!(value_name, "intensity");
+                assert_eq!(position.get_line_number(), Some(4));
+                assert_eq!(position.get_byte_offset(), Some(36));
+            },
+            error => panic!("unexpected error: {}", error)
+        }
+
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\n100.1 2x0\nEND IONS\n";
+        let mut reader = reader::Reader::from_reader(mgf_content.as_bytes());
+        match reader.next().unwrap_err() {
+            crate::Error::FloatParse { value, .. } => assert_eq!(value, "2x0"),
+            error => panic!("unexpected error: {}", error)
+        }
+
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\n100.1 200.0\n";
+        let mut reader = reader::Reader::from_reader(mgf_content.as_bytes());
+        match reader.next().unwrap_err() {
+            crate::Error::UnterminatedSpectrum { position } => assert_eq!(position.get_line_number(), Some(1)),
+            error => panic!("unexpected error: {}", error)
+        }
+
+        let mgf_content = "BEGIN IONS\nTITLE=test\nCHARGE=2x\nEND IONS\n";
+        let mut reader = reader::Reader::from_reader(mgf_content.as_bytes());
+        match reader.next().unwrap_err() {
+            crate::Error::ChargeParse { value, position } => {
+                assert_eq!(value, "2x");
+                assert_eq!(position.get_line_number(), Some(3));
+            },
+            error => panic!("unexpected error: {}", error)
+        }
+    }
+
+    #[test]
+    /// Indexes a MGF file, persists and reloads the index
+    /// and compares the randomly accessed spectra with the sequentially read ones.
+    fn test_indexed_reading() {
+        let mgf_file_path = Path::new(TEMP_INDEX_MGF_PATH_STR);
+        fs::copy(MGF_FILE_PATH_STR, mgf_file_path).unwrap();
+
+        let index = index::MgfIndex::build(mgf_file_path).unwrap();
+        assert_eq!(index.len(), EXPECTED_NUM_SPECTRA);
+        assert_eq!(index.get_entries()[0].get_offset(), 0);
+        assert_eq!(index.get_entries()[0].get_title(), "824.836730957031_212.9232");
+        assert_eq!(index.get_entries()[0].get_precursor_charges(), &vec![2]);
+        index.write(mgf_file_path).unwrap();
+
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::new(mgf_file_path, 1024).unwrap()
+            .into_fallible_iter()
+            .collect()
+            .unwrap();
+        let mut indexed_reader = indexed_reader::IndexedReader::new(mgf_file_path).unwrap();
+        assert_eq!(indexed_reader.get_index(), &index);
+        for (position, spectrum) in spectra.iter().enumerate().rev() {
+            assert_eq!(indexed_reader.get_spectrum_by_title(spectrum.get_title()).unwrap().as_ref(), Some(spectrum));
+            assert_eq!(indexed_reader.get_spectrum_by_position(position).unwrap().as_ref(), Some(spectrum));
+        }
+        assert!(indexed_reader.get_spectrum_by_title("unknown").unwrap().is_none());
+
+        fs::remove_file(index::MgfIndex::get_index_path(mgf_file_path)).unwrap();
+        fs::remove_file(mgf_file_path).unwrap();
+    }
+
+    #[test]
+    /// Accesses spectra of an in-memory buffer by scan number.
+    fn test_indexed_reading_by_scan() {
+        let mgf_content = "BEGIN IONS\nTITLE=first\nPEPMASS=500.5\nSCANS=10\n100.1 20.5\nEND IONS\n\
+            BEGIN IONS\nTITLE=run.11.11.2\nPEPMASS=600.5\n100.1 20.5\nEND IONS\n";
+        let index = index::MgfIndex::build_from_reader(mgf_content.as_bytes()).unwrap();
+        assert_eq!(index.get_entries()[1].get_offset(), 66);
+        let mut indexed_reader = indexed_reader::IndexedReader::from_reader(
+            std::io::Cursor::new(mgf_content.as_bytes()),
+            index
+        ).unwrap();
+        assert_eq!(indexed_reader.get_spectrum_by_scan(11).unwrap().unwrap().get_title(), "run.11.11.2");
+        assert_eq!(indexed_reader.get_spectrum_by_scan(10).unwrap().unwrap().get_title(), "first");
+        assert!(indexed_reader.get_spectrum_by_scan(12).unwrap().is_none());
+
+        // Offset not pointing to `BEGIN IONS`, e.g. of an outdated index
+        let error = indexed_reader.get_spectrum_by_offset(11).unwrap_err();
+        assert!(matches!(error, crate::error::Error::InvalidIndex { .. }));
+    }
+
+    #[test]
+    /// Compares the randomly accessed spectra of a file with global parameters
+    /// with the sequentially read ones, which get the default charge.
+    fn test_indexed_reading_with_header() {
+        let mgf_content = "COM=test\nCHARGE=2+ and 3+\n\
+            BEGIN IONS\nTITLE=first\nPEPMASS=500.5\n100.1 20.5\nEND IONS\n\
+            BEGIN IONS\nTITLE=second\nPEPMASS=600.5\nCHARGE=4+\n100.1 20.5\nEND IONS\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mgf_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert_eq!(spectra[0].get_precursor_charges(), &vec![2, 3]);
+        let index = index::MgfIndex::build_from_reader(mgf_content.as_bytes()).unwrap();
+        let mut indexed_reader = indexed_reader::IndexedReader::from_reader(
+            std::io::Cursor::new(mgf_content.as_bytes()),
+            index.clone()
+        ).unwrap();
+        assert_eq!(indexed_reader.get_header().get_comment(), Some("test"));
+        for (position, spectrum) in spectra.iter().enumerate() {
+            assert_eq!(indexed_reader.get_spectrum_by_position(position).unwrap().as_ref(), Some(spectrum));
+        }
+
+        let mut indexed_reader = indexed_reader::IndexedReader::from_reader(
+            std::io::Cursor::new(mgf_content.as_bytes()),
+            index
+        ).unwrap().with_header_defaults(false);
+        assert!(indexed_reader.get_spectrum_by_title("first").unwrap().unwrap().get_precursor_charges().is_empty());
+    }
 }
@@ -1,98 +1,276 @@
 
 // std imports
-use std::fs::File;
-use std::io::BufReader;
 use std::io::prelude::*;
 use std::path::Path;
 
 // 3rd party imports
-use anyhow::{Result, bail};
 use fallible_iterator::FallibleIterator;
 
 // internal imports
-use crate::mgf::spectrum::Spectrum;
+use crate::compression::open_reader;
+use crate::error::{Error, Position, Result, parse_float};
+use crate::line_reader::{LineReader, split_first_token};
+use crate::mgf::charge::{parse_charge, parse_charges};
+use crate::mgf::header::{MassType, MgfHeader};
+use crate::mgf::spectrum::{Precursor, Spectrum};
 
 /// Reader for MGF
-pub struct Reader {
-    internal_reader: BufReader<File>
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+pub struct Reader<R: BufRead> {
+    internal_reader: LineReader<R>,
+    is_header_read: bool,
+    header: MgfHeader,
+    apply_header_defaults: bool,
+    // Position of a `BEGIN IONS` line consumed while reading the global parameters
+    pending_spectrum_position: Option<Position>
 }
 
-impl Reader {
-    /// Creates a new Reader
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given MGF file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
     /// 
     /// # Arguments
     ///
     /// * `mgf_file_path` - Path to MGF file
+    /// * `buffer_size` - Size of the internal read buffer
     /// 
-    pub fn new(mgf_file_path: & Path, buffer_size: usize) -> Result<Self> {
-        let mgf_file: File = File::open(mgf_file_path)?;
-        Ok(Self {
-            internal_reader: BufReader::with_capacity(buffer_size, mgf_file),
-        })
+    pub fn new(mgf_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Ok(Self::from_line_reader(LineReader::new(
+            open_reader(mgf_file_path, buffer_size)?,
+            Some(mgf_file_path.to_path_buf())
+        )))
     }
 }
 
-impl FallibleIterator for Reader {
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    /// 
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the MGF content
+    /// 
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self::from_line_reader(LineReader::new(internal_reader, None))
+    }
+
+    /// Creates a new Reader from a line reader
+    /// 
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Line reader providing the MGF content
+    /// 
+    pub(crate) fn from_line_reader(internal_reader: LineReader<R>) -> Self {
+        Self {
+            internal_reader,
+            is_header_read: false,
+            header: MgfHeader::default(),
+            apply_header_defaults: true,
+            pending_spectrum_position: None
+        }
+    }
+
+    /// Enables or disables applying the header defaults, e.g. the global `CHARGE=`,
+    /// to spectra without their own value. Enabled by default.
+    /// 
+    /// # Arguments
+    ///
+    /// * `apply_header_defaults` - Apply header defaults
+    /// 
+    pub fn with_header_defaults(mut self, apply_header_defaults: bool) -> Self {
+        self.apply_header_defaults = apply_header_defaults;
+        self
+    }
+
+    /// Uses the given global parameters instead of reading them, e.g. when reading
+    /// a single spectrum at an indexed offset.
+    /// 
+    /// # Arguments
+    ///
+    /// * `header` - Global parameters
+    /// 
+    pub(crate) fn with_header(mut self, header: MgfHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Returns the global parameters preceding the first spectrum, e.g. `MASS=` or `CHARGE=`.
+    /// Reads them if no spectrum was read yet.
+    /// 
+    pub fn get_header(&mut self) -> Result<&MgfHeader> {
+        self.read_header()?;
+        Ok(&self.header)
+    }
+
+    /// Reads the global parameters until the first `BEGIN IONS`, if not done yet.
+    /// 
+    fn read_header(&mut self) -> Result<()> {
+        if self.is_header_read {
+            return Ok(());
+        }
+        self.is_header_read = true;
+        let mut line = String::new();
+        while self.internal_reader.read_line(&mut line)? > 0 {
+            let line = line.trim();
+            if line == "BEGIN IONS" {
+                self.pending_spectrum_position = Some(self.internal_reader.get_position());
+                break;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some(param) => param,
+                None => continue
+            };
+            match key {
+                "MASS" if MassType::from_name(value).is_none() => {
+                    return Err(Error::MalformedHeader {
+                        header: line.to_string(),
+                        position: self.internal_reader.get_position()
+                    });
+                },
+                "CHARGE" if parse_charges(value).is_none() => {
+                    return Err(Error::ChargeParse {
+                        value: value.to_string(),
+                        position: self.internal_reader.get_position()
+                    });
+                },
+                "TOL" | "ITOL" => {
+                    parse_float::<f64, _>(value, || self.internal_reader.get_position())?;
+                },
+                _ => ()
+            }
+            self.header.add_param(key.to_string(), value.to_string());
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
     type Item = Spectrum;
-    type Error = anyhow::Error;
+    type Error = Error;
 
     fn next(&mut self) -> Result<Option<Self::Item>> {
+        self.read_header()?;
 
-        let mut in_spectrum: bool = false;
+        let mut spectrum_position: Option<Position> = self.pending_spectrum_position.take();
         let mut title = String::new();
-        let mut precursor_mz: f64 = 0.0;
-        let mut precursor_charge: Option<i8> = None;
+        let mut precursors: Vec<Precursor> = Vec::new();
+        let mut precursor_charges: Vec<i8> = Vec::new();
+        // Charges of the `PEPMASS=` lines, used if there is no `CHARGE=`
+        let mut pepmass_charges: Vec<i8> = Vec::new();
         let mut retention_time: Option<f64> = None;
         let mut mz_list: Vec<f64> = Vec::new();
         let mut intensity_list: Vec<f32> = Vec::new();
+        let mut peak_charge_list: Vec<Option<i8>> = Vec::new();
+        let mut peak_annotation_list: Vec<Option<String>> = Vec::new();
+        let mut additional_params: Vec<(String, String)> = Vec::new();
 
+        let mut line = String::new();
         loop {
-            let mut line = String::new();
-            if let Ok(num_bytes) = self.internal_reader.read_line(&mut line) {
-                if num_bytes == 0 {
-                    if in_spectrum {
-                        bail!("reach EOF before END IONS (incomplete spectrum)".to_string());
-                    }
-                    return Ok(None);
+            let num_bytes = self.internal_reader.read_line(&mut line)?;
+            if num_bytes == 0 {
+                if let Some(position) = spectrum_position {
+                    return Err(Error::UnterminatedSpectrum { position });
                 }
-                line = line.as_mut_str().trim().to_string();
-                if line.is_empty() {
-                    continue
+                return Ok(None);
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                continue
+            }
+            // Global parameters are only allowed before the first spectrum
+            if spectrum_position.is_none() && line.contains('=') {
+                return Err(Error::InvalidContent {
+                    message: format!("`{}` is outside of a spectrum", line),
+                    position: self.internal_reader.get_position()
+                });
+            }
+
+            if line.starts_with(|c: char| c.is_ascii_digit()) {
+                let (mz, remainder) = split_first_token(line);
+                mz_list.push(parse_float(mz, || self.internal_reader.get_position())?);
+
+                let (intens, remainder) = split_first_token(remainder);
+                if intens.is_empty() {
+                    return Err(Error::MissingPeakValue {
+                        value_name: "intensity",
+                        position: self.internal_reader.get_position()
+                    });
+                }
+                intensity_list.push(parse_float(intens, || self.internal_reader.get_position())?);
+
+                // Optional fragment charge and annotation, e.g. `250.1 1200 2+ b2`
+                let (charge, annotation) = match split_first_token(remainder) {
+                    (raw_charge, annotation) if parse_charge(raw_charge).is_some() => (parse_charge(raw_charge), annotation),
+                    _ => (None, remainder)
+                };
+                if charge.is_some() {
+                    peak_charge_list.resize(mz_list.len() - 1, None);
+                    peak_charge_list.push(charge);
+                }
+                if !annotation.is_empty() {
+                    peak_annotation_list.resize(mz_list.len() - 1, None);
+                    peak_annotation_list.push(Some(annotation.to_string()));
                 }
 
-                if line.chars().nth(0).unwrap().is_numeric() {
-                    let mut split = line.split_ascii_whitespace();
-
-                    match split.next() {
-                        Some(mz) => mz_list.push(fast_float::parse(mz)?),
-                        None => bail!("mz value is missing")
-                    };
-
-                    match split.next() {
-                        Some(intens) => intensity_list.push(fast_float::parse(intens)?),
-                        None => bail!("intensity value is missing")
-                    };
-
-                } else if line.starts_with("TITLE=") {
-                    title = line[6..].to_owned();
-                } else if line.starts_with("PEPMASS=") {
-                    precursor_mz = fast_float::parse(&line[8..])?;
-                } else if line.starts_with("RTINSECONDS=") {
-                    retention_time = Some(fast_float::parse(&line[12..])?);
-                } else if line.starts_with("CHARGE=") {
-                    precursor_charge = Some(line[7..].parse()?);
-                } else if line == "BEGIN IONS" {
-                    in_spectrum = true;
-                } else if line == "END IONS" {
-                    return Ok(Some(Spectrum::new(
-                        title,
-                        precursor_mz,
-                        precursor_charge,
-                        retention_time,
-                        mz_list,
-                        intensity_list
-                    )));
+            } else if let Some(value) = line.strip_prefix("TITLE=") {
+                title = value.to_owned();
+            } else if let Some(value) = line.strip_prefix("PEPMASS=") {
+                // `<m/z> [<intensity> [<charge>]]`, e.g. `PEPMASS=500.5 1200 2+`
+                let mut split = value.split_ascii_whitespace();
+                let mz = parse_float(split.next().unwrap_or_default(), || self.internal_reader.get_position())?;
+                let intensity = match split.next() {
+                    Some(intensity) => Some(parse_float(intensity, || self.internal_reader.get_position())?),
+                    None => None
+                };
+                if let Some(raw_charge) = split.next() {
+                    let charge = parse_charge(raw_charge).ok_or_else(|| Error::ChargeParse {
+                        value: raw_charge.to_string(),
+                        position: self.internal_reader.get_position()
+                    })?;
+                    if !pepmass_charges.contains(&charge) {
+                        pepmass_charges.push(charge);
+                    }
+                }
+                if split.next().is_some() {
+                    return Err(Error::InvalidContent {
+                        message: format!("`PEPMASS={}` has more than m/z, intensity and charge", value),
+                        position: self.internal_reader.get_position()
+                    });
+                }
+                precursors.push(Precursor::new(mz, intensity));
+            } else if let Some(value) = line.strip_prefix("RTINSECONDS=") {
+                retention_time = Some(parse_float(value, || self.internal_reader.get_position())?);
+            } else if let Some(value) = line.strip_prefix("CHARGE=") {
+                precursor_charges = parse_charges(value).ok_or_else(|| Error::ChargeParse {
+                    value: value.to_string(),
+                    position: self.internal_reader.get_position()
+                })?;
+            } else if line == "BEGIN IONS" {
+                spectrum_position = Some(self.internal_reader.get_position());
+            } else if line == "END IONS" {
+                if !peak_charge_list.is_empty() {
+                    peak_charge_list.resize(mz_list.len(), None);
+                }
+                if !peak_annotation_list.is_empty() {
+                    peak_annotation_list.resize(mz_list.len(), None);
+                }
+                if precursor_charges.is_empty() {
+                    precursor_charges = pepmass_charges;
+                }
+                if precursor_charges.is_empty() && self.apply_header_defaults {
+                    precursor_charges = self.header.get_charges();
                 }
+                return Ok(Some(Spectrum::new(
+                    title,
+                    precursors,
+                    precursor_charges,
+                    retention_time,
+                    mz_list,
+                    intensity_list
+                ).with_peak_charges(peak_charge_list)
+                    .with_peak_annotations(peak_annotation_list)
+                    .with_additional_params(additional_params)));
+            } else if let Some((key, value)) = line.split_once('=') {
+                additional_params.push((key.to_string(), value.to_string()));
             }
         }
     }
@@ -1,11 +1,50 @@
+/// Precursor of a spectrum (`PEPMASS=`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Precursor {
+    mz: f64,
+    intensity: Option<f64>
+}
+
+impl Precursor {
+    /// Creates a new precursor
+    /// 
+    /// # Arguments
+    ///
+    /// * `mz` - Precursor m/z
+    /// * `intensity` - Precursor intensity
+    /// 
+    pub fn new(mz: f64, intensity: Option<f64>) -> Self {
+        Self {
+            mz,
+            intensity
+        }
+    }
+
+    /// Returns the m/z
+    /// 
+    pub fn get_mz(&self) -> f64 {
+        self.mz
+    }
+
+    /// Returns the intensity
+    /// 
+    pub fn get_intensity(&self) -> Option<f64> {
+        self.intensity
+    }
+}
+
 /// Spectrum representation for MGF files.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Spectrum {
     title: String,
-    precursor_mz: f64,
-    precursor_charge: Option<i8>,
+    precursors: Vec<Precursor>,
+    precursor_charges: Vec<i8>,
     retention_time: Option<f64>,
     mz_list: Vec<f64>,
-    intensity_list: Vec<f32>
+    intensity_list: Vec<f32>,
+    peak_charge_list: Vec<Option<i8>>,
+    peak_annotation_list: Vec<Option<String>>,
+    additional_params: Vec<(String, String)>
 }
 
 impl Spectrum {
@@ -14,40 +53,103 @@ impl Spectrum {
     /// # Arguments
     ///
     /// * `title` - Specturm title
-    /// * `precursor_mz` -  Precursor mass
-    /// * `precursor_charge` -  Precursor charge
+    /// * `precursors` -  Precursors, each written as separate `PEPMASS=` line
+    /// * `precursor_charges` -  Precursor charges, empty if unknown
     /// * `retention_time` -  Retention time
     /// * `mz_list` - M/Z list
     /// * `intensity_list` -  Intensity list
     /// 
-    pub fn new(title: String, precursor_mz: f64, precursor_charge: Option<i8>,
+    pub fn new(title: String, precursors: Vec<Precursor>, precursor_charges: Vec<i8>,
         retention_time: Option<f64>, mz_list: Vec<f64>, intensity_list: Vec<f32>) -> Self {
         Self {
             title,
-            precursor_mz,
-            precursor_charge,
+            precursors,
+            precursor_charges,
             retention_time,
             mz_list,
             intensity_list,
+            peak_charge_list: Vec::new(),
+            peak_annotation_list: Vec::new(),
+            additional_params: Vec::new()
         }
     }
 
+    /// Sets the precursors, e.g. multiple ones for chimeric or DIA pseudo-spectra.
+    /// Each precursor is written as separate `PEPMASS=` line.
+    /// 
+    /// # Arguments
+    ///
+    /// * `precursors` - Precursors
+    /// 
+    pub fn with_precursors(mut self, precursors: Vec<Precursor>) -> Self {
+        self.precursors = precursors;
+        self
+    }
+
+    /// Sets the fragment charges of the peaks.
+    /// Empty if no peak has a charge, otherwise one optional charge per peak.
+    /// 
+    /// # Arguments
+    ///
+    /// * `peak_charge_list` - Peak charges
+    /// 
+    pub fn with_peak_charges(mut self, peak_charge_list: Vec<Option<i8>>) -> Self {
+        self.peak_charge_list = peak_charge_list;
+        self
+    }
+
+    /// Sets the annotations of the peaks, e.g. `b2` or `"y3-H2O"`.
+    /// Empty if no peak is annotated, otherwise one optional annotation per peak.
+    /// 
+    /// # Arguments
+    ///
+    /// * `peak_annotation_list` - Peak annotations
+    /// 
+    pub fn with_peak_annotations(mut self, peak_annotation_list: Vec<Option<String>>) -> Self {
+        self.peak_annotation_list = peak_annotation_list;
+        self
+    }
+
+    /// Sets parameters without dedicated field, e.g. `SCANS`, `SEQ` or `USER01`,
+    /// which are written back in the given order. Keys may repeat.
+    /// 
+    /// # Arguments
+    ///
+    /// * `additional_params` - Ordered parameters (key without `=`)
+    /// 
+    pub fn with_additional_params(mut self, additional_params: Vec<(String, String)>) -> Self {
+        self.additional_params = additional_params;
+        self
+    }
+
     /// Returns the spectrum title
     /// 
     pub fn get_title(&self) -> &str {
         &self.title
     }
 
-    /// Returns the precursor mass
+    /// Returns the m/z of the first precursor, 0.0 if there is none
     /// 
     pub fn get_precursor_mz(&self) -> f64 {
-        self.precursor_mz
+        self.precursors.first().map(|precursor| precursor.get_mz()).unwrap_or_default()
     }
 
-    /// Returns precursor charge
+    /// Returns the intensity of the first precursor
     /// 
-    pub fn get_precursor_charge(&self) -> &Option<i8> {
-        &self.precursor_charge
+    pub fn get_precursor_intensity(&self) -> Option<f64> {
+        self.precursors.first().and_then(|precursor| precursor.get_intensity())
+    }
+
+    /// Returns all precursors
+    /// 
+    pub fn get_precursors(&self) -> &Vec<Precursor> {
+        &self.precursors
+    }
+
+    /// Returns precursor charges, empty if unknown
+    /// 
+    pub fn get_precursor_charges(&self) -> &Vec<i8> {
+        &self.precursor_charges
     }
 
     /// Returns retention time
@@ -68,4 +170,39 @@ impl Spectrum {
         &self.intensity_list
     }
 
-}
\ No newline at end of file
+    /// Returns the fragment charges of the peaks, empty if no peak has a charge
+    /// 
+    pub fn get_peak_charge_list(&self) -> &Vec<Option<i8>> {
+        &self.peak_charge_list
+    }
+
+    /// Returns the annotations of the peaks, empty if no peak is annotated
+    /// 
+    pub fn get_peak_annotation_list(&self) -> &Vec<Option<String>> {
+        &self.peak_annotation_list
+    }
+
+    /// Returns the parameters without dedicated field in their original order
+    /// 
+    pub fn get_additional_params(&self) -> &Vec<(String, String)> {
+        &self.additional_params
+    }
+
+    /// Returns the value of the first parameter without dedicated field with the given key
+    /// 
+    /// # Arguments
+    ///
+    /// * `key` - Key (without `=`)
+    /// 
+    pub fn get_additional_param(&self, key: &str) -> Option<&str> {
+        self.additional_params.iter()
+            .find(|(param_key, _)| param_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the parameters without dedicated field for modification
+    /// 
+    pub fn get_additional_params_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.additional_params
+    }
+}
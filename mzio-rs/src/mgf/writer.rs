@@ -4,30 +4,133 @@ use std::io::BufWriter;
 use std::io::prelude::*;
 use std::path::Path;
 
-// 3rd party imports
-use anyhow::Result;
 
 // internal imports 
+use crate::compression::{Compression, Encoder};
+use crate::error::{Error, Result};
+use crate::mgf::charge::format_charges;
+use crate::mgf::header::MgfHeader;
 use crate::mgf::spectrum::Spectrum;
+use crate::mgf::writer_config::WriterConfig;
 
 /// Writer for MGF files
-/// Use flush() to make ensure the buffer is written completely.
-pub struct Writer {
-    internal_writer: BufWriter<File>
+/// Works on any target implementing `Write`, e.g. files, stdout or `Vec<u8>`.
+/// Use flush() to make sure the buffer is written completely.
+pub struct Writer<W: Write> {
+    internal_writer: W,
+    config: WriterConfig
 }
 
-impl Writer {
-    /// Creates a new Writer
+impl Writer<BufWriter<File>> {
+    /// Creates a new buffered Writer for the given MGF file
     /// 
     /// # Arguments
     ///
     /// * `mgf_file_path` - Path to MGF file
     /// 
     pub fn new(mgf_file_path: &Path) -> Result<Self> {
-        let mgf_file: File = File::create(mgf_file_path)?;
-        Ok(Self {
-            internal_writer: BufWriter::new(mgf_file)
-        })
+        let mgf_file: File = File::create(mgf_file_path)
+            .map_err(|err| Error::io_with_path(err, mgf_file_path))?;
+        Ok(Self::from_writer(BufWriter::new(mgf_file)))
+    }
+}
+
+impl Writer<Encoder<BufWriter<File>>> {
+    /// Creates a new buffered Writer for the given MGF file, compressing the output.
+    /// Use finish() to complete the compressed file.
+    /// 
+    /// # Arguments
+    ///
+    /// * `mgf_file_path` - Path to MGF file
+    /// * `compression` - Compression of the written file, see `Compression::from_path()`
+    /// 
+    pub fn with_compression(mgf_file_path: &Path, compression: Compression) -> Result<Self> {
+        Ok(Self::from_writer(Encoder::create(mgf_file_path, compression)?))
+    }
+}
+
+impl<W: Write> Writer<Encoder<W>> {
+    /// Flushes the buffer, completes the compressed stream and returns the underlying write target.
+    /// 
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        self.internal_writer.finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new Writer for any write target.
+    /// The target is used as is, so wrap unbuffered targets in a `BufWriter`.
+    /// 
+    /// # Arguments
+    ///
+    /// * `internal_writer` - Write target
+    /// 
+    pub fn from_writer(internal_writer: W) -> Self {
+        Self {
+            internal_writer,
+            config: WriterConfig::default()
+        }
+    }
+
+    /// Sets the configuration, e.g. number formatting and written fields.
+    /// 
+    /// # Arguments
+    ///
+    /// * `config` - Writer configuration
+    /// 
+    pub fn with_config(mut self, config: WriterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the configuration
+    /// 
+    pub fn get_config(&self) -> &WriterConfig {
+        &self.config
+    }
+
+    /// Returns the underlying write target, e.g. to access an in-memory buffer.
+    /// Make sure to call flush() first.
+    /// 
+    pub fn into_inner(self) -> W {
+        self.internal_writer
+    }
+
+    /// Writes the complete byte slice, as `Write::write()` might only write parts of it
+    /// on unbuffered targets.
+    /// Returns the number of written bytes.
+    /// 
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to write
+    /// 
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.internal_writer.write_all(bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Writes the global parameters, which must precede the first spectrum.
+    /// 
+    /// # Arguments
+    ///
+    /// * `header` - Global parameters
+    /// 
+    pub fn write_header(&mut self, header: &MgfHeader) -> Result<usize> {
+        let line_ending = self.config.get_line_ending().as_str();
+        let mut content = String::new();
+        for (key, value) in header.to_params(self.config.get_charge_format()) {
+            let is_written = match key.as_str() {
+                "CHARGE" => self.config.is_writing_charges(),
+                _ if MgfHeader::has_dedicated_field(&key) => true,
+                _ => self.config.is_writing_additional_params()
+            };
+            if !is_written {
+                continue;
+            }
+            content.push_str(&format!("{}={}{}", key, value, line_ending));
+        }
+        self.write_bytes(content.as_bytes())
     }
 
     /// Writes a spectrum into the file.
@@ -36,22 +139,61 @@ impl Writer {
     ///
     /// * `spectrum` - Spectrum
     /// 
-    pub fn write_spectrum(&mut self, spectrum: &Spectrum,) -> Result<usize> {
-        let mut written_bytes: usize = 0;
-        written_bytes += self.internal_writer.write("BEGIN IONS\n".as_bytes())?;
-        written_bytes += self.internal_writer.write(format!("TITLE={}\n", spectrum.get_title()).as_bytes())?;
-        written_bytes += self.internal_writer.write(format!("PEPMASS={}", spectrum.get_precursor_mz()).as_bytes())?;
-        if let Some(retention_time) = spectrum.get_retention_time() {
-            written_bytes += self.internal_writer.write(format!("\nRTINSECONDS={}", retention_time).as_bytes())?;
+    pub fn write_spectrum(&mut self, spectrum: &Spectrum) -> Result<usize> {
+        let config = &self.config;
+        let mut lines: Vec<String> = vec!["BEGIN IONS".to_string()];
+        if config.is_writing_title() {
+            lines.push(format!("TITLE={}", spectrum.get_title()));
+        }
+        // `PEPMASS=` is mandatory, so spectra without precursor get an m/z of 0
+        if spectrum.get_precursors().is_empty() {
+            lines.push(format!("PEPMASS={}", config.get_mz_format().format(spectrum.get_precursor_mz())));
+        }
+        for precursor in spectrum.get_precursors() {
+            let mz = config.get_mz_format().format(precursor.get_mz());
+            match precursor.get_intensity() {
+                Some(intensity) if config.is_writing_precursor_intensity() => {
+                    lines.push(format!("PEPMASS={} {}", mz, config.get_intensity_format().format(intensity)));
+                },
+                _ => lines.push(format!("PEPMASS={}", mz))
+            }
         }
-        if let Some(charge) = spectrum.get_precursor_charge() {
-            written_bytes += self.internal_writer.write(format!("\nCHARGE={}", charge).as_bytes())?;
+        if let (Some(retention_time), true) = (spectrum.get_retention_time(), config.is_writing_retention_time()) {
+            lines.push(format!("RTINSECONDS={}", config.get_retention_time_format().format(*retention_time)));
         }
-        for (mz, intensity) in zip(spectrum.get_mz_list(), spectrum.get_intensity_list()) {
-            written_bytes += self.internal_writer.write(format!("\n{mz} {intensity}").as_bytes())?;
+        if !spectrum.get_precursor_charges().is_empty() && config.is_writing_charges() {
+            lines.push(format!("CHARGE={}", format_charges(spectrum.get_precursor_charges(), config.get_charge_format())));
         }
-        written_bytes += self.internal_writer.write("\nEND IONS\n".as_bytes())?;
-        return Ok(written_bytes);
+        if config.is_writing_additional_params() {
+            for (key, value) in spectrum.get_additional_params() {
+                lines.push(format!("{}={}", key, value));
+            }
+        }
+        for (idx, (mz, intensity)) in zip(spectrum.get_mz_list(), spectrum.get_intensity_list()).enumerate() {
+            if *intensity == 0.0 && config.is_omitting_zero_intensities() {
+                continue;
+            }
+            let mut peak = format!(
+                "{} {}",
+                config.get_mz_format().format(*mz),
+                config.get_intensity_format().format(*intensity)
+            );
+            if let (Some(Some(charge)), true) = (spectrum.get_peak_charge_list().get(idx), config.is_writing_peak_charges()) {
+                peak.push(' ');
+                peak.push_str(&format_charges(&[*charge], config.get_charge_format()));
+            }
+            if let (Some(Some(annotation)), true) = (spectrum.get_peak_annotation_list().get(idx), config.is_writing_peak_annotations()) {
+                peak.push(' ');
+                peak.push_str(annotation);
+            }
+            lines.push(peak);
+        }
+        lines.push("END IONS".to_string());
+
+        let line_ending = config.get_line_ending().as_str();
+        let mut content = lines.join(line_ending);
+        content.push_str(line_ending);
+        self.write_bytes(content.as_bytes())
     }
 
     /// Writes multiple spectra to file.
@@ -68,7 +210,7 @@ impl Writer {
         for spectrum in spectra {
             written_bytes += self.write_spectrum(spectrum)?;
         }
-        return Ok(written_bytes);
+        Ok(written_bytes)
     }
 
     /// Flushes the buffer
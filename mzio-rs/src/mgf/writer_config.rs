@@ -0,0 +1,337 @@
+// std imports
+use std::fmt::{Display, LowerExp};
+
+// internal imports
+use crate::mgf::charge::ChargeFormat;
+
+/// Formatting of floating point numbers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// Shortest representation which is read back as the same value, e.g. `824.836730957031`
+    #[default]
+    Shortest,
+    /// Fixed number of decimal places, e.g. `824.8367` for 4
+    Fixed(usize),
+    /// Scientific notation with the given number of decimal places, e.g. `8.248e2` for 3
+    Scientific(usize)
+}
+
+impl FloatFormat {
+    /// Formats the given value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value
+    ///
+    pub fn format<T: Display + LowerExp>(&self, value: T) -> String {
+        match self {
+            Self::Shortest => value.to_string(),
+            Self::Fixed(decimals) => format!("{:.*}", decimals, value),
+            Self::Scientific(decimals) => format!("{:.*e}", decimals, value)
+        }
+    }
+}
+
+/// Line ending of written files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    CrLf
+}
+
+impl LineEnding {
+    /// Returns the line ending characters
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n"
+        }
+    }
+}
+
+/// Configuration of the MGF writer.
+/// The default writes all information with the shortest lossless number formatting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriterConfig {
+    mz_format: FloatFormat,
+    intensity_format: FloatFormat,
+    retention_time_format: FloatFormat,
+    charge_format: ChargeFormat,
+    line_ending: LineEnding,
+    omit_zero_intensities: bool,
+    write_title: bool,
+    write_retention_time: bool,
+    write_charges: bool,
+    write_precursor_intensity: bool,
+    write_additional_params: bool,
+    write_peak_charges: bool,
+    write_peak_annotations: bool
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        Self {
+            mz_format: FloatFormat::default(),
+            intensity_format: FloatFormat::default(),
+            retention_time_format: FloatFormat::default(),
+            charge_format: ChargeFormat::default(),
+            line_ending: LineEnding::default(),
+            omit_zero_intensities: false,
+            write_title: true,
+            write_retention_time: true,
+            write_charges: true,
+            write_precursor_intensity: true,
+            write_additional_params: true,
+            write_peak_charges: true,
+            write_peak_annotations: true
+        }
+    }
+}
+
+impl WriterConfig {
+    /// Creates the default configuration
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the format of the m/z values of the peaks and `PEPMASS=`
+    ///
+    /// # Arguments
+    ///
+    /// * `mz_format` - Float format
+    ///
+    pub fn with_mz_format(mut self, mz_format: FloatFormat) -> Self {
+        self.mz_format = mz_format;
+        self
+    }
+
+    /// Sets the format of the intensities of the peaks and `PEPMASS=`
+    ///
+    /// # Arguments
+    ///
+    /// * `intensity_format` - Float format
+    ///
+    pub fn with_intensity_format(mut self, intensity_format: FloatFormat) -> Self {
+        self.intensity_format = intensity_format;
+        self
+    }
+
+    /// Sets the format of the retention time (`RTINSECONDS=`)
+    ///
+    /// # Arguments
+    ///
+    /// * `retention_time_format` - Float format
+    ///
+    pub fn with_retention_time_format(mut self, retention_time_format: FloatFormat) -> Self {
+        self.retention_time_format = retention_time_format;
+        self
+    }
+
+    /// Sets the notation of charges, `ChargeFormat::Mascot` (`2+`) by default
+    ///
+    /// # Arguments
+    ///
+    /// * `charge_format` - Notation of the charges
+    ///
+    pub fn with_charge_format(mut self, charge_format: ChargeFormat) -> Self {
+        self.charge_format = charge_format;
+        self
+    }
+
+    /// Sets the line ending
+    ///
+    /// # Arguments
+    ///
+    /// * `line_ending` - Line ending
+    ///
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Omits peaks with an intensity of zero
+    ///
+    /// # Arguments
+    ///
+    /// * `omit_zero_intensities` - Omit zero intensity peaks
+    ///
+    pub fn with_omit_zero_intensities(mut self, omit_zero_intensities: bool) -> Self {
+        self.omit_zero_intensities = omit_zero_intensities;
+        self
+    }
+
+    /// Sets if `TITLE=` is written
+    ///
+    /// # Arguments
+    ///
+    /// * `write_title` - Write the title
+    ///
+    pub fn with_title(mut self, write_title: bool) -> Self {
+        self.write_title = write_title;
+        self
+    }
+
+    /// Sets if `RTINSECONDS=` is written
+    ///
+    /// # Arguments
+    ///
+    /// * `write_retention_time` - Write the retention time
+    ///
+    pub fn with_retention_time(mut self, write_retention_time: bool) -> Self {
+        self.write_retention_time = write_retention_time;
+        self
+    }
+
+    /// Sets if `CHARGE=` is written, for the spectra and the global parameters
+    ///
+    /// # Arguments
+    ///
+    /// * `write_charges` - Write the precursor charges
+    ///
+    pub fn with_charges(mut self, write_charges: bool) -> Self {
+        self.write_charges = write_charges;
+        self
+    }
+
+    /// Sets if the precursor intensity is written to `PEPMASS=`
+    ///
+    /// # Arguments
+    ///
+    /// * `write_precursor_intensity` - Write the precursor intensity
+    ///
+    pub fn with_precursor_intensity(mut self, write_precursor_intensity: bool) -> Self {
+        self.write_precursor_intensity = write_precursor_intensity;
+        self
+    }
+
+    /// Sets if parameters without dedicated field are written, for the spectra and the global parameters
+    ///
+    /// # Arguments
+    ///
+    /// * `write_additional_params` - Write the additional parameters
+    ///
+    pub fn with_additional_params(mut self, write_additional_params: bool) -> Self {
+        self.write_additional_params = write_additional_params;
+        self
+    }
+
+    /// Sets if the peak charges are written
+    ///
+    /// # Arguments
+    ///
+    /// * `write_peak_charges` - Write the peak charges
+    ///
+    pub fn with_peak_charges(mut self, write_peak_charges: bool) -> Self {
+        self.write_peak_charges = write_peak_charges;
+        self
+    }
+
+    /// Sets if the peak annotations are written
+    ///
+    /// # Arguments
+    ///
+    /// * `write_peak_annotations` - Write the peak annotations
+    ///
+    pub fn with_peak_annotations(mut self, write_peak_annotations: bool) -> Self {
+        self.write_peak_annotations = write_peak_annotations;
+        self
+    }
+
+    /// Returns the format of the m/z values of the peaks and `PEPMASS=`
+    ///
+    pub fn get_mz_format(&self) -> FloatFormat {
+        self.mz_format
+    }
+
+    /// Returns the format of the intensities of the peaks and `PEPMASS=`
+    ///
+    pub fn get_intensity_format(&self) -> FloatFormat {
+        self.intensity_format
+    }
+
+    /// Returns the format of the retention time (`RTINSECONDS=`)
+    ///
+    pub fn get_retention_time_format(&self) -> FloatFormat {
+        self.retention_time_format
+    }
+
+    /// Returns the notation of charges
+    ///
+    pub fn get_charge_format(&self) -> ChargeFormat {
+        self.charge_format
+    }
+
+    /// Returns the line ending
+    ///
+    pub fn get_line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Returns true if peaks with an intensity of zero are omitted
+    ///
+    pub fn is_omitting_zero_intensities(&self) -> bool {
+        self.omit_zero_intensities
+    }
+
+    /// Returns true if `TITLE=` is written
+    ///
+    pub fn is_writing_title(&self) -> bool {
+        self.write_title
+    }
+
+    /// Returns true if `RTINSECONDS=` is written
+    ///
+    pub fn is_writing_retention_time(&self) -> bool {
+        self.write_retention_time
+    }
+
+    /// Returns true if `CHARGE=` is written
+    ///
+    pub fn is_writing_charges(&self) -> bool {
+        self.write_charges
+    }
+
+    /// Returns true if the precursor intensity is written
+    ///
+    pub fn is_writing_precursor_intensity(&self) -> bool {
+        self.write_precursor_intensity
+    }
+
+    /// Returns true if parameters without dedicated field are written
+    ///
+    pub fn is_writing_additional_params(&self) -> bool {
+        self.write_additional_params
+    }
+
+    /// Returns true if the peak charges are written
+    ///
+    pub fn is_writing_peak_charges(&self) -> bool {
+        self.write_peak_charges
+    }
+
+    /// Returns true if the peak annotations are written
+    ///
+    pub fn is_writing_peak_annotations(&self) -> bool {
+        self.write_peak_annotations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    /// Formats floats in the shortest, fixed and scientific notation.
+    fn test_float_formatting() {
+        assert_eq!(FloatFormat::Shortest.format(824.836730957031_f64), "824.836730957031");
+        assert_eq!(FloatFormat::Shortest.format(20.5_f32), "20.5");
+        assert_eq!(FloatFormat::Fixed(2).format(824.836730957031_f64), "824.84");
+        assert_eq!(FloatFormat::Fixed(0).format(1200.6_f32), "1201");
+        assert_eq!(FloatFormat::Scientific(3).format(15433.2_f64), "1.543e4");
+    }
+}
@@ -0,0 +1,49 @@
+/// Header of a MS1/MS2 file (`H` lines), e.g. `H Extractor RawConverter`.
+/// Keys may occur multiple times, e.g. for comments, so the entries are kept in their original order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ms2Header {
+    entries: Vec<(String, String)>
+}
+
+impl Ms2Header {
+    /// Creates an empty header
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an entry
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key, e.g. `CreationDate`
+    /// * `value` - Value, may be empty
+    ///
+    pub fn add_entry(&mut self, key: String, value: String) {
+        self.entries.push((key, value));
+    }
+
+    /// Returns the entries in their original order
+    ///
+    pub fn get_entries(&self) -> &Vec<(String, String)> {
+        &self.entries
+    }
+
+    /// Returns the value of the first entry with the given key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key
+    ///
+    pub fn get_value(&self, key: &str) -> Option<&str> {
+        self.entries.iter()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns true if there are no entries
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
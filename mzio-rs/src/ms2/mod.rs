@@ -0,0 +1,181 @@
+//! Reading and writing of MS1/MS2 files, the line based text format of the Yates lab
+//! (`H` header, `S` scan, `I` information, `Z` charge and `D` analysis lines followed by the peaks).
+//! The binary CMS1/CMS2 variants are not supported.
+//! Converting from and into MGF spectra maps the scan range to `SCANS=` and the charge states to the precursor charges.
+
+pub mod header;
+pub mod reader;
+pub mod spectrum;
+pub mod writer;
+
+#[cfg(test)]
+mod test {
+    // std imports
+    use std::fs;
+    use std::path::Path;
+
+    // 3rd party imports
+    use fallible_iterator::FallibleIterator;
+
+    // internal imports
+    use super::*;
+    use crate::error::Error;
+    use crate::mgf::reader::Reader as MgfReader;
+    use crate::mgf::spectrum::Spectrum as MgfSpectrum;
+
+    const MS2_FILE_PATH_STR: &str = "../test_files/ms2/small.ms2";
+    const MGF_FILE_PATH_STR: &str = "../test_files/mgf/Velos005137.mgf";
+
+    #[test]
+    /// Reads the header and spectra with scan ranges, `I` lines and multiple charge states.
+    fn test_reading() {
+        let mut reader = reader::Reader::new(Path::new(MS2_FILE_PATH_STR), 1024).unwrap();
+        let header = reader.get_header().unwrap();
+        assert_eq!(header.get_entries().len(), 5);
+        assert_eq!(header.get_value("CreationDate"), Some("2023-02-14 10:12:31"));
+        assert_eq!(header.get_value("Comments"), Some("Converted from small.raw"));
+        assert_eq!(header.get_entries()[4], ("Comments".to_string(), "centroided".to_string()));
+
+        let spectra: Vec<spectrum::Spectrum> = reader.collect().unwrap();
+        assert_eq!(spectra.len(), 3);
+
+        assert_eq!(spectra[0].get_first_scan(), 2);
+        assert_eq!(spectra[0].get_last_scan(), 2);
+        assert_eq!(spectra[0].get_precursor_mz(), Some(445.12));
+        assert_eq!(spectra[0].get_retention_time(), Some(1.0416 * 60.0));
+        assert_eq!(spectra[0].get_precursor_intensity(), Some(120053.0));
+        assert_eq!(spectra[0].get_info_value("ActivationType"), Some("CID"));
+        assert_eq!(
+            spectra[0].get_charge_states(),
+            &vec![spectrum::ChargeState::new(2, 889.232724), spectrum::ChargeState::new(3, 1333.345448)]
+        );
+        assert_eq!(spectra[0].get_mz_list(), &vec![110.0712, 147.1128, 262.1395]);
+        assert_eq!(spectra[0].get_intensity_list(), &vec![500.0, 1200.0, 85.5]);
+
+        assert_eq!(spectra[1].get_first_scan(), 5);
+        assert_eq!(spectra[1].get_last_scan(), 7);
+        assert_eq!(spectra[1].get_precursor_intensity(), None);
+
+        // Spectrum without peaks at the end of the file
+        assert_eq!(spectra[2].get_first_scan(), 9);
+        assert!(spectra[2].get_info().is_empty());
+        assert!(spectra[2].get_mz_list().is_empty());
+    }
+
+    #[test]
+    /// Writes the read header and spectra, which reproduces the file.
+    fn test_writing() {
+        let mut reader = reader::Reader::new(Path::new(MS2_FILE_PATH_STR), 1024).unwrap();
+        let header = reader.get_header().unwrap().clone();
+        let spectra: Vec<spectrum::Spectrum> = reader.collect().unwrap();
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        let written_bytes = writer.write_header(&header).unwrap() + writer.write_all(spectra.iter()).unwrap();
+        writer.flush().unwrap();
+        let buffer = writer.into_inner();
+        assert_eq!(written_bytes, buffer.len());
+        assert_eq!(String::from_utf8(buffer).unwrap(), fs::read_to_string(MS2_FILE_PATH_STR).unwrap());
+    }
+
+    #[test]
+    /// Reads MS1 spectra, which have no precursor.
+    fn test_reading_ms1() {
+        let ms1_content = "S\t1\t1\nI\tRetTime\t0.5\n400.25 1000.5 2 60000\n500.5 2000\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(ms1_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert_eq!(spectra.len(), 1);
+        assert_eq!(spectra[0].get_precursor_mz(), None);
+        assert_eq!(spectra[0].get_retention_time(), Some(30.0));
+        assert_eq!(spectra[0].get_mz_list(), &vec![400.25, 500.5]);
+        assert_eq!(spectra[0].get_intensity_list(), &vec![1000.5, 2000.0]);
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_spectrum(&spectra[0]).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "S\t000001\t000001\nI\tRetTime\t0.5\n400.25 1000.5\n500.5 2000\n"
+        );
+    }
+
+    #[test]
+    /// Keeps repeated `I` lines and the `D` lines of each charge state.
+    fn test_repeated_info_and_analysis_roundtrip() {
+        let ms2_content = "S\t000003\t000003\t500.5\nI\tEZ\t2\t1000.1\t1\t0.5\nI\tEZ\t3\t1499.1\t1\t0.5\n\
+            Z\t2\t1000.1\nD\tDeltaCN\t0.1\nZ\t3\t1499.1\nD\tDeltaCN\t0.2\nD\tXCorr\t2.5\n100.5 20\n";
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(ms2_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert_eq!(
+            spectra[0].get_info(),
+            &vec![
+                ("EZ".to_string(), "2\t1000.1\t1\t0.5".to_string()),
+                ("EZ".to_string(), "3\t1499.1\t1\t0.5".to_string())
+            ]
+        );
+        assert_eq!(spectra[0].get_info_value("EZ"), Some("2\t1000.1\t1\t0.5"));
+        assert_eq!(spectra[0].get_charge_states()[0].get_analysis(), &vec![("DeltaCN".to_string(), "0.1".to_string())]);
+        assert_eq!(spectra[0].get_charge_states()[1].get_analysis().len(), 2);
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), ms2_content);
+    }
+
+    #[test]
+    /// Converts MGF spectra into MS2 spectra and back.
+    fn test_mgf_roundtrip() {
+        let mgf_spectra: Vec<MgfSpectrum> = MgfReader::new(Path::new(MGF_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        for mgf_spectrum in mgf_spectra {
+            let ms2_spectrum = spectrum::Spectrum::from(mgf_spectrum.clone());
+            assert_eq!(ms2_spectrum.get_precursor_mz(), Some(mgf_spectrum.get_precursor_mz()));
+            for (charge_state, charge) in ms2_spectrum.get_charge_states().iter().zip(mgf_spectrum.get_precursor_charges()) {
+                assert_eq!(charge_state.get_charge(), *charge);
+                let mz = (charge_state.get_mass() - spectrum::PROTON_MASS) / *charge as f64 + spectrum::PROTON_MASS;
+                assert!((mz - mgf_spectrum.get_precursor_mz()).abs() < 1e-9);
+            }
+
+            let converted_spectrum = MgfSpectrum::from(ms2_spectrum);
+            assert_eq!(converted_spectrum.get_precursor_mz(), mgf_spectrum.get_precursor_mz());
+            assert_eq!(converted_spectrum.get_precursor_charges(), mgf_spectrum.get_precursor_charges());
+            let retention_time = converted_spectrum.get_retention_time().unwrap();
+            assert!((retention_time - mgf_spectrum.get_retention_time().unwrap()).abs() < 1e-9);
+            assert_eq!(converted_spectrum.get_mz_list(), mgf_spectrum.get_mz_list());
+            assert_eq!(converted_spectrum.get_intensity_list(), mgf_spectrum.get_intensity_list());
+        }
+
+        let ms2_spectrum = reader::Reader::new(Path::new(MS2_FILE_PATH_STR), 1024).unwrap().nth(1).unwrap().unwrap();
+        let mgf_spectrum = MgfSpectrum::from(ms2_spectrum.clone());
+        assert_eq!(mgf_spectrum.get_title(), "scan=5");
+        assert_eq!(mgf_spectrum.get_additional_param("SCANS"), Some("5-7"));
+        let converted_spectrum = spectrum::Spectrum::from(mgf_spectrum);
+        assert_eq!(converted_spectrum.get_first_scan(), 5);
+        assert_eq!(converted_spectrum.get_last_scan(), 7);
+        let charge_state = &converted_spectrum.get_charge_states()[0];
+        assert_eq!(charge_state.get_charge(), 2);
+        assert!((charge_state.get_mass() - ms2_spectrum.get_charge_states()[0].get_mass()).abs() < 1e-5);
+    }
+
+    #[test]
+    /// Checks that malformed lines are reported.
+    fn test_reading_malformed_input() {
+        let error = reader::Reader::from_reader("H\tExtractor\tx\n100.1 20.5\n".as_bytes()).next().unwrap_err();
+        assert!(matches!(error, Error::MissingHeader { .. }));
+
+        let error = reader::Reader::from_reader("S\t1\n".as_bytes()).next().unwrap_err();
+        assert!(matches!(error, Error::MalformedHeader { .. }));
+
+        let error = reader::Reader::from_reader("S\t1\t1\t500.5\nZ\tx\t999.9\n".as_bytes()).next().unwrap_err();
+        assert!(matches!(error, Error::ChargeParse { .. }));
+
+        let error = reader::Reader::from_reader("S\t1\t1\t500.5\n100.1\n".as_bytes()).next().unwrap_err();
+        assert!(matches!(error, Error::MissingPeakValue { .. }));
+        assert_eq!(error.get_position().unwrap().get_line_number(), Some(2));
+
+        let error = reader::Reader::from_reader("S\t1\t1\t500.5\nD\tDeltaCN\t0.1\n".as_bytes()).next().unwrap_err();
+        assert!(matches!(error, Error::InvalidContent { .. }));
+    }
+}
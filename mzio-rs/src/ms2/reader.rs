@@ -0,0 +1,213 @@
+// std imports
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+
+// internal imports
+use crate::compression::open_reader;
+use crate::error::{Error, Result, parse_float};
+use crate::index::parse_int;
+use crate::line_reader::{LineReader, split_first_token};
+use crate::ms2::header::Ms2Header;
+use crate::ms2::spectrum::{ChargeState, Spectrum};
+
+/// Scan numbers and precursor m/z of a `S` line
+type ScanLine = (u32, u32, Option<f64>);
+
+/// Reader for MS1/MS2 files
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+/// `D` lines (charge dependent analysis) are added to the preceding charge state.
+pub struct Reader<R: BufRead> {
+    internal_reader: LineReader<R>,
+    is_header_read: bool,
+    header: Ms2Header,
+    // `S` line consumed while reading the header or the previous spectrum
+    pending_scan_line: Option<ScanLine>
+}
+
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given MS1/MS2 file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
+    ///
+    /// # Arguments
+    ///
+    /// * `ms2_file_path` - Path to MS1/MS2 file
+    /// * `buffer_size` - Size of the internal read buffer
+    ///
+    pub fn new(ms2_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Ok(Self::from_line_reader(LineReader::new(
+            open_reader(ms2_file_path, buffer_size)?,
+            Some(ms2_file_path.to_path_buf())
+        )))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the MS1/MS2 content
+    ///
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self::from_line_reader(LineReader::new(internal_reader, None))
+    }
+
+    /// Creates a new Reader from a line reader
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Line reader providing the MS1/MS2 content
+    ///
+    fn from_line_reader(internal_reader: LineReader<R>) -> Self {
+        Self {
+            internal_reader,
+            is_header_read: false,
+            header: Ms2Header::default(),
+            pending_scan_line: None
+        }
+    }
+
+    /// Returns the header (`H` lines) preceding the first spectrum.
+    /// Reads it if no spectrum was read yet.
+    ///
+    pub fn get_header(&mut self) -> Result<&Ms2Header> {
+        self.read_header()?;
+        Ok(&self.header)
+    }
+
+    /// Reads the `H` lines until the first `S` line, if not done yet.
+    ///
+    fn read_header(&mut self) -> Result<()> {
+        if self.is_header_read {
+            return Ok(());
+        }
+        self.is_header_read = true;
+        let mut line = String::new();
+        while self.internal_reader.read_line(&mut line)? > 0 {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (tag, remainder) = split_first_token(line);
+            match tag {
+                "H" => {
+                    let (key, value) = split_first_token(remainder);
+                    self.header.add_entry(key.to_string(), value.trim_end().to_string());
+                },
+                "S" => {
+                    self.pending_scan_line = Some(self.parse_scan_line(line, remainder)?);
+                    break;
+                },
+                _ => return Err(Error::MissingHeader {
+                    line: line.to_string(),
+                    position: self.internal_reader.get_position()
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the fields of a `S` line, i.e. first and last scan and the optional precursor m/z.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - Complete line, used for error reporting
+    /// * `fields` - Line without the `S` tag
+    ///
+    fn parse_scan_line(&self, line: &str, fields: &str) -> Result<ScanLine> {
+        let position = self.internal_reader.get_position();
+        let mut fields = fields.split_ascii_whitespace();
+        let (first_scan, last_scan) = match (fields.next(), fields.next()) {
+            (Some(first_scan), Some(last_scan)) => (parse_int(first_scan, &position)?, parse_int(last_scan, &position)?),
+            _ => return Err(Error::MalformedHeader {
+                header: line.to_string(),
+                position
+            })
+        };
+        let precursor_mz = match fields.next() {
+            Some(precursor_mz) => Some(parse_float(precursor_mz, || position.clone())?),
+            None => None
+        };
+        Ok((first_scan, last_scan, precursor_mz))
+    }
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
+    type Item = Spectrum;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        self.read_header()?;
+
+        let (first_scan, last_scan, precursor_mz) = match self.pending_scan_line.take() {
+            Some(scan_line) => scan_line,
+            None => return Ok(None)
+        };
+        let mut charge_states: Vec<ChargeState> = Vec::new();
+        let mut info: Vec<(String, String)> = Vec::new();
+        let mut mz_list: Vec<f64> = Vec::new();
+        let mut intensity_list: Vec<f32> = Vec::new();
+
+        let mut line = String::new();
+        while self.internal_reader.read_line(&mut line)? > 0 {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with(|c: char| c.is_ascii_digit()) {
+                // Additional columns, e.g. the charge or resolution of MS1 peaks, are ignored
+                let mut split = line.split_ascii_whitespace();
+                mz_list.push(parse_float(split.next().unwrap_or_default(), || self.internal_reader.get_position())?);
+                let intensity = split.next().ok_or_else(|| Error::MissingPeakValue {
+                    value_name: "intensity",
+                    position: self.internal_reader.get_position()
+                })?;
+                intensity_list.push(parse_float(intensity, || self.internal_reader.get_position())?);
+                continue;
+            }
+
+            let (tag, remainder) = split_first_token(line);
+            match tag {
+                "S" => {
+                    self.pending_scan_line = Some(self.parse_scan_line(line, remainder)?);
+                    break;
+                },
+                "I" => {
+                    let (key, value) = split_first_token(remainder);
+                    info.push((key.to_string(), value.trim_end().to_string()));
+                },
+                "Z" => {
+                    let (charge, mass) = split_first_token(remainder);
+                    let charge: i8 = charge.parse().map_err(|_| Error::ChargeParse {
+                        value: charge.to_string(),
+                        position: self.internal_reader.get_position()
+                    })?;
+                    let (mass, _) = split_first_token(mass);
+                    let mass = parse_float(mass, || self.internal_reader.get_position())?;
+                    charge_states.push(ChargeState::new(charge, mass));
+                },
+                "D" => {
+                    let charge_state = charge_states.last_mut().ok_or_else(|| Error::InvalidContent {
+                        message: "`D` line without preceding `Z` line".to_string(),
+                        position: self.internal_reader.get_position()
+                    })?;
+                    let (key, value) = split_first_token(remainder);
+                    charge_state.get_analysis_mut().push((key.to_string(), value.trim_end().to_string()));
+                },
+                _ => return Err(Error::MalformedHeader {
+                    header: line.to_string(),
+                    position: self.internal_reader.get_position()
+                })
+            }
+        }
+        Ok(Some(
+            Spectrum::new(first_scan, last_scan, precursor_mz, mz_list, intensity_list)
+                .with_charge_states(charge_states)
+                .with_info(info)
+        ))
+    }
+}
@@ -0,0 +1,312 @@
+// internal imports
+use crate::mgf::spectrum::{Precursor as MgfPrecursor, Spectrum as MgfSpectrum};
+
+/// Mass of a proton, used to convert between precursor m/z and `[M+H]+` mass
+pub const PROTON_MASS: f64 = 1.007276466812;
+
+/// Key of the `I` line with the retention time in minutes
+pub const RETENTION_TIME_KEY: &str = "RetTime";
+
+/// Key of the `I` line with the precursor intensity
+pub const PRECURSOR_INTENSITY_KEY: &str = "PrecursorInt";
+
+/// Charge state of the precursor (`Z` line), e.g. `Z 2 889.2327`,
+/// with the charge dependent analysis (following `D` lines)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChargeState {
+    charge: i8,
+    mass: f64,
+    analysis: Vec<(String, String)>
+}
+
+impl ChargeState {
+    /// Creates a new charge state
+    ///
+    /// # Arguments
+    ///
+    /// * `charge` - Charge
+    /// * `mass` - Singly protonated mass (`[M+H]+`) for the charge
+    ///
+    pub fn new(charge: i8, mass: f64) -> Self {
+        Self {
+            charge,
+            mass,
+            analysis: Vec::new()
+        }
+    }
+
+    /// Sets the charge dependent analysis (`D` lines), e.g. `DeltaCN`,
+    /// which is written back in the given order
+    ///
+    /// # Arguments
+    ///
+    /// * `analysis` - Ordered key-value pairs
+    ///
+    pub fn with_analysis(mut self, analysis: Vec<(String, String)>) -> Self {
+        self.analysis = analysis;
+        self
+    }
+
+    /// Creates a charge state, calculating the `[M+H]+` mass from the precursor m/z
+    ///
+    /// # Arguments
+    ///
+    /// * `charge` - Charge
+    /// * `precursor_mz` - Precursor m/z
+    ///
+    pub fn from_precursor_mz(charge: i8, precursor_mz: f64) -> Self {
+        Self::new(charge, (precursor_mz - PROTON_MASS) * charge as f64 + PROTON_MASS)
+    }
+
+    /// Returns the charge
+    ///
+    pub fn get_charge(&self) -> i8 {
+        self.charge
+    }
+
+    /// Returns the singly protonated mass (`[M+H]+`)
+    ///
+    pub fn get_mass(&self) -> f64 {
+        self.mass
+    }
+
+    /// Returns the charge dependent analysis (`D` lines)
+    ///
+    pub fn get_analysis(&self) -> &Vec<(String, String)> {
+        &self.analysis
+    }
+
+    /// Returns the charge dependent analysis (`D` lines) for modification
+    ///
+    pub fn get_analysis_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.analysis
+    }
+}
+
+/// Spectrum representation for MS1/MS2 files (`S` line with the following `I`, `Z` and peak lines).
+/// Can be converted from and into a MGF spectrum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    first_scan: u32,
+    last_scan: u32,
+    precursor_mz: Option<f64>,
+    charge_states: Vec<ChargeState>,
+    info: Vec<(String, String)>,
+    mz_list: Vec<f64>,
+    intensity_list: Vec<f32>
+}
+
+impl Spectrum {
+    /// Creates a new spectrum
+    ///
+    /// # Arguments
+    ///
+    /// * `first_scan` - First scan number
+    /// * `last_scan` - Last scan number, equal to the first one if the spectrum is not merged
+    /// * `precursor_mz` - Precursor m/z, None for MS1 spectra
+    /// * `mz_list` - M/Z list
+    /// * `intensity_list` - Intensity list
+    ///
+    pub fn new(first_scan: u32, last_scan: u32, precursor_mz: Option<f64>, mz_list: Vec<f64>,
+        intensity_list: Vec<f32>) -> Self {
+        Self {
+            first_scan,
+            last_scan,
+            precursor_mz,
+            charge_states: Vec::new(),
+            info: Vec::new(),
+            mz_list,
+            intensity_list
+        }
+    }
+
+    /// Sets the charge states (`Z` lines)
+    ///
+    /// # Arguments
+    ///
+    /// * `charge_states` - Charge states
+    ///
+    pub fn with_charge_states(mut self, charge_states: Vec<ChargeState>) -> Self {
+        self.charge_states = charge_states;
+        self
+    }
+
+    /// Sets the information (`I` lines), e.g. `RetTime` or `IonInjectionTime`,
+    /// which are written back in the given order. Keys may repeat, e.g. `EZ`.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - Ordered key-value pairs
+    ///
+    pub fn with_info(mut self, info: Vec<(String, String)>) -> Self {
+        self.info = info;
+        self
+    }
+
+    /// Returns the first scan number
+    ///
+    pub fn get_first_scan(&self) -> u32 {
+        self.first_scan
+    }
+
+    /// Returns the last scan number
+    ///
+    pub fn get_last_scan(&self) -> u32 {
+        self.last_scan
+    }
+
+    /// Returns the precursor m/z, None for MS1 spectra
+    ///
+    pub fn get_precursor_mz(&self) -> Option<f64> {
+        self.precursor_mz
+    }
+
+    /// Returns the charge states
+    ///
+    pub fn get_charge_states(&self) -> &Vec<ChargeState> {
+        &self.charge_states
+    }
+
+    /// Returns the information (`I` lines)
+    ///
+    pub fn get_info(&self) -> &Vec<(String, String)> {
+        &self.info
+    }
+
+    /// Returns the value of the first `I` line with the given key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key
+    ///
+    pub fn get_info_value(&self, key: &str) -> Option<&str> {
+        self.info.iter()
+            .find(|(info_key, _)| info_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the information (`I` lines) for modification
+    ///
+    pub fn get_info_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.info
+    }
+
+    /// Returns the retention time in seconds, taken from `I RetTime` (minutes).
+    /// None if missing or not a number.
+    ///
+    pub fn get_retention_time(&self) -> Option<f64> {
+        self.get_info_as_f64(RETENTION_TIME_KEY).map(|retention_time| retention_time * 60.0)
+    }
+
+    /// Returns the precursor intensity, taken from `I PrecursorInt`.
+    /// None if missing or not a number.
+    ///
+    pub fn get_precursor_intensity(&self) -> Option<f64> {
+        self.get_info_as_f64(PRECURSOR_INTENSITY_KEY)
+    }
+
+    /// Returns the M/Z list
+    ///
+    pub fn get_mz_list(&self) -> &Vec<f64> {
+        &self.mz_list
+    }
+
+    /// Returns the intensity list
+    ///
+    pub fn get_intensity_list(&self) -> &Vec<f32> {
+        &self.intensity_list
+    }
+
+    /// Returns the value of the given `I` line as float
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key
+    ///
+    fn get_info_as_f64(&self, key: &str) -> Option<f64> {
+        self.get_info_value(key).and_then(|value| fast_float::parse(value).ok())
+    }
+}
+
+impl From<Spectrum> for MgfSpectrum {
+    /// Converts the spectrum into a MGF spectrum. The title is `scan=<first scan>`,
+    /// the scan range is written as `SCANS=` and the `I` lines without dedicated
+    /// MGF field as additional parameters.
+    ///
+    fn from(spectrum: Spectrum) -> Self {
+        let retention_time = spectrum.get_retention_time();
+        let precursors: Vec<MgfPrecursor> = spectrum.precursor_mz.iter()
+            .map(|mz| MgfPrecursor::new(*mz, spectrum.get_precursor_intensity()))
+            .collect();
+        let mut precursor_charges: Vec<i8> = Vec::new();
+        for charge_state in spectrum.charge_states.iter() {
+            if !precursor_charges.contains(&charge_state.charge) {
+                precursor_charges.push(charge_state.charge);
+            }
+        }
+        let mut additional_params: Vec<(String, String)> = Vec::new();
+        let scans = if spectrum.first_scan == spectrum.last_scan {
+            spectrum.first_scan.to_string()
+        } else {
+            format!("{}-{}", spectrum.first_scan, spectrum.last_scan)
+        };
+        additional_params.push(("SCANS".to_string(), scans));
+        for (key, value) in spectrum.info {
+            if key != RETENTION_TIME_KEY && key != PRECURSOR_INTENSITY_KEY {
+                additional_params.push((key, value));
+            }
+        }
+        MgfSpectrum::new(
+            format!("scan={}", spectrum.first_scan),
+            precursors,
+            precursor_charges,
+            retention_time,
+            spectrum.mz_list,
+            spectrum.intensity_list
+        )
+            .with_additional_params(additional_params)
+    }
+}
+
+impl From<MgfSpectrum> for Spectrum {
+    /// Converts a MGF spectrum into a MS2 spectrum. The scan range is taken from `SCANS=`
+    /// (0 if missing), the first precursor becomes the precursor m/z and each charge a charge state.
+    /// The retention time, precursor intensity and additional parameters become `I` lines.
+    ///
+    fn from(mgf_spectrum: MgfSpectrum) -> Self {
+        let (first_scan, last_scan) = mgf_spectrum.get_additional_param("SCANS")
+            .and_then(|scans| {
+                let (first_scan, last_scan) = scans.split_once('-').unwrap_or((scans, scans));
+                Some((first_scan.trim().parse().ok()?, last_scan.trim().parse().ok()?))
+            })
+            .unwrap_or((0, 0));
+        let precursor = mgf_spectrum.get_precursors().first().copied();
+        let charge_states: Vec<ChargeState> = match precursor {
+            Some(precursor) => mgf_spectrum.get_precursor_charges().iter()
+                .map(|charge| ChargeState::from_precursor_mz(*charge, precursor.get_mz()))
+                .collect(),
+            None => Vec::new()
+        };
+        let mut info: Vec<(String, String)> = Vec::new();
+        if let Some(retention_time) = mgf_spectrum.get_retention_time() {
+            info.push((RETENTION_TIME_KEY.to_string(), (retention_time / 60.0).to_string()));
+        }
+        if let Some(intensity) = precursor.and_then(|precursor| precursor.get_intensity()) {
+            info.push((PRECURSOR_INTENSITY_KEY.to_string(), intensity.to_string()));
+        }
+        for (key, value) in mgf_spectrum.get_additional_params() {
+            if key != "SCANS" {
+                info.push((key.clone(), value.clone()));
+            }
+        }
+        Self::new(
+            first_scan,
+            last_scan,
+            precursor.map(|precursor| precursor.get_mz()),
+            mgf_spectrum.get_mz_list().clone(),
+            mgf_spectrum.get_intensity_list().clone()
+        )
+            .with_charge_states(charge_states)
+            .with_info(info)
+    }
+}
@@ -0,0 +1,155 @@
+// std imports
+use std::fs::File;
+use std::iter::zip;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use std::path::Path;
+
+// internal imports
+use crate::compression::{Compression, Encoder};
+use crate::error::{Error, Result};
+use crate::ms2::header::Ms2Header;
+use crate::ms2::spectrum::Spectrum;
+
+/// Writer for MS1/MS2 files
+/// Works on any target implementing `Write`, e.g. files, stdout or `Vec<u8>`.
+/// Use flush() to make sure the buffer is written completely.
+pub struct Writer<W: Write> {
+    internal_writer: W
+}
+
+impl Writer<BufWriter<File>> {
+    /// Creates a new buffered Writer for the given MS1/MS2 file
+    ///
+    /// # Arguments
+    ///
+    /// * `ms2_file_path` - Path to MS1/MS2 file
+    ///
+    pub fn new(ms2_file_path: &Path) -> Result<Self> {
+        let ms2_file: File = File::create(ms2_file_path)
+            .map_err(|err| Error::io_with_path(err, ms2_file_path))?;
+        Ok(Self::from_writer(BufWriter::new(ms2_file)))
+    }
+}
+
+impl Writer<Encoder<BufWriter<File>>> {
+    /// Creates a new buffered Writer for the given MS1/MS2 file, compressing the output.
+    /// Use finish() to complete the compressed file.
+    ///
+    /// # Arguments
+    ///
+    /// * `ms2_file_path` - Path to MS1/MS2 file
+    /// * `compression` - Compression of the written file, see `Compression::from_path()`
+    ///
+    pub fn with_compression(ms2_file_path: &Path, compression: Compression) -> Result<Self> {
+        Ok(Self::from_writer(Encoder::create(ms2_file_path, compression)?))
+    }
+}
+
+impl<W: Write> Writer<Encoder<W>> {
+    /// Flushes the buffer, completes the compressed stream and returns the underlying write target.
+    ///
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        self.internal_writer.finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new Writer for any write target.
+    /// The target is used as is, so wrap unbuffered targets in a `BufWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_writer` - Write target
+    ///
+    pub fn from_writer(internal_writer: W) -> Self {
+        Self {
+            internal_writer
+        }
+    }
+
+    /// Returns the underlying write target, e.g. to access an in-memory buffer.
+    /// Make sure to call flush() first.
+    ///
+    pub fn into_inner(self) -> W {
+        self.internal_writer
+    }
+
+    /// Writes the complete byte slice.
+    /// Returns the number of written bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to write
+    ///
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.internal_writer.write_all(bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Writes the header (`H` lines), which must precede the first spectrum.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - Header
+    ///
+    pub fn write_header(&mut self, header: &Ms2Header) -> Result<usize> {
+        let mut content = String::new();
+        for (key, value) in header.get_entries() {
+            content.push_str(&format!("H\t{}\t{}\n", key, value));
+        }
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Writes a spectrum into the file. Scan numbers are padded to six digits.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectrum` - Spectrum
+    ///
+    pub fn write_spectrum(&mut self, spectrum: &Spectrum) -> Result<usize> {
+        let mut content = format!("S\t{:06}\t{:06}", spectrum.get_first_scan(), spectrum.get_last_scan());
+        if let Some(precursor_mz) = spectrum.get_precursor_mz() {
+            content.push_str(&format!("\t{}", precursor_mz));
+        }
+        content.push('\n');
+        for (key, value) in spectrum.get_info() {
+            content.push_str(&format!("I\t{}\t{}\n", key, value));
+        }
+        for charge_state in spectrum.get_charge_states() {
+            content.push_str(&format!("Z\t{}\t{}\n", charge_state.get_charge(), charge_state.get_mass()));
+            for (key, value) in charge_state.get_analysis() {
+                content.push_str(&format!("D\t{}\t{}\n", key, value));
+            }
+        }
+        for (mz, intensity) in zip(spectrum.get_mz_list(), spectrum.get_intensity_list()) {
+            content.push_str(&format!("{} {}\n", mz, intensity));
+        }
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Writes multiple spectra to file.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectra` - Iterator of spectra
+    ///
+    pub fn write_all<'b, I>(&mut self, spectra: I) -> Result<usize>
+    where
+        I: Iterator<Item = &'b Spectrum>,
+    {
+        let mut written_bytes: usize = 0;
+        for spectrum in spectra {
+            written_bytes += self.write_spectrum(spectrum)?;
+        }
+        Ok(written_bytes)
+    }
+
+    /// Flushes the buffer
+    ///
+    pub fn flush(&mut self) -> Result<()> {
+        self.internal_writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+//! Coding of the `Comment:` field of spectral libraries,
+//! e.g. `Comment: Spec=Consensus Mods=1/4,C,CAM Parent=870.949 Protein="sp|O43707|ACTN4_HUMAN"`
+
+// internal imports
+use crate::msp::spectrum::{LibrarySpectrum, Modification};
+
+/// Key of the modifications within the comment
+pub(crate) const MODIFICATIONS_KEY: &str = "Mods";
+
+/// Key of the precursor charge within the comment, used if the name has no charge
+pub(crate) const CHARGE_KEY: &str = "Charge";
+
+/// Key of the precursor m/z within the comment
+pub(crate) const PARENT_KEY: &str = "Parent";
+
+/// Key of the retention time within the comment, e.g. `RetentionTime=1234.5,1200.0,1250.0`
+/// (median, minimum and maximum of the replicates in seconds)
+pub(crate) const RETENTION_TIME_KEY: &str = "RetentionTime";
+
+/// Parses the space separated `key=value` entries of a comment in their original order.
+/// Spaces within quotes are kept, as are the quotes themselves.
+/// Entries without `=` get an empty value.
+///
+/// # Arguments
+///
+/// * `comment` - Comment
+///
+pub(crate) fn parse_comment(comment: &str) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    let mut entry = String::new();
+    let mut is_quoted = false;
+    for c in comment.trim().chars().chain(std::iter::once(' ')) {
+        match c {
+            '"' => {
+                is_quoted = !is_quoted;
+                entry.push(c);
+            },
+            ' ' if !is_quoted => {
+                if !entry.is_empty() {
+                    let (key, value) = entry.split_once('=').unwrap_or((&entry, ""));
+                    entries.push((key.to_string(), value.to_string()));
+                    entry.clear();
+                }
+            },
+            _ => entry.push(c)
+        }
+    }
+    entries
+}
+
+/// Formats comment entries, the inverse of `parse_comment()`
+///
+/// # Arguments
+///
+/// * `entries` - Comment entries
+///
+pub(crate) fn format_comment(entries: &[(String, String)]) -> String {
+    entries.iter()
+        .map(|(key, value)| if value.is_empty() { key.clone() } else { format!("{}={}", key, value) })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Returns the value of the first entry with the given key
+///
+/// # Arguments
+///
+/// * `entries` - Ordered key-value pairs
+/// * `key` - Key
+///
+pub(crate) fn get_entry<'a>(entries: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    entries.iter()
+        .find(|(entry_key, _)| entry_key == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Sets the value of the first entry with the given key or appends the entry if the key is missing
+///
+/// # Arguments
+///
+/// * `entries` - Ordered key-value pairs
+/// * `key` - Key
+/// * `value` - Value
+///
+pub(crate) fn set_entry(entries: &mut Vec<(String, String)>, key: &str, value: String) {
+    match entries.iter_mut().find(|(entry_key, _)| entry_key == key) {
+        Some(entry) => entry.1 = value,
+        None => entries.push((key.to_string(), value))
+    }
+}
+
+/// Parses modifications, e.g. `2/4,C,CAM/9,M,Oxidation`. None if malformed.
+///
+/// # Arguments
+///
+/// * `value` - Value of `Mods=`
+///
+pub(crate) fn parse_modifications(value: &str) -> Option<Vec<Modification>> {
+    let mut parts = value.split('/');
+    let count: usize = parts.next()?.parse().ok()?;
+    let modifications = parts
+        .map(|modification| {
+            let mut fields = modification.splitn(3, ',');
+            let position = fields.next()?.parse().ok()?;
+            let mut residue = fields.next()?.chars();
+            let modification = Modification::new(position, residue.next()?, fields.next()?.to_string());
+            match residue.next() {
+                Some(_) => None,
+                None => Some(modification)
+            }
+        })
+        .collect::<Option<Vec<Modification>>>()?;
+    if modifications.len() != count {
+        return None;
+    }
+    Some(modifications)
+}
+
+/// Formats modifications, the inverse of `parse_modifications()`
+///
+/// # Arguments
+///
+/// * `modifications` - Modifications
+///
+pub(crate) fn format_modifications(modifications: &[Modification]) -> String {
+    let mut value = modifications.len().to_string();
+    for modification in modifications {
+        value.push_str(&format!(
+            "/{},{},{}",
+            modification.get_position(),
+            modification.get_residue(),
+            modification.get_name()
+        ));
+    }
+    value
+}
+
+/// Parses the retention time, i.e. the first value of `RetentionTime=`. None if malformed.
+///
+/// # Arguments
+///
+/// * `value` - Value of `RetentionTime=`
+///
+pub(crate) fn parse_retention_time(value: &str) -> Option<f64> {
+    fast_float::parse(value.split(',').next()?).ok()
+}
+
+/// Returns the comment entries of the spectrum with `Mods=`, `RetentionTime=` and an existing `Charge=`
+/// updated from the dedicated members. Further values of `RetentionTime=`, e.g. the minimum and maximum, are kept.
+/// A `Mods=` which cannot be parsed, e.g. `1(4,C,CAM)`, is kept unchanged unless modifications are set.
+///
+/// # Arguments
+///
+/// * `spectrum` - Library spectrum
+///
+pub(crate) fn build_comment(spectrum: &LibrarySpectrum) -> Vec<(String, String)> {
+    let mut comment = spectrum.get_comment().clone();
+    let modifications = spectrum.get_modifications();
+    let is_modifications_updated = match get_entry(&comment, MODIFICATIONS_KEY) {
+        Some(raw_modifications) => !modifications.is_empty() || parse_modifications(raw_modifications).is_some(),
+        None => !modifications.is_empty()
+    };
+    if is_modifications_updated {
+        set_entry(&mut comment, MODIFICATIONS_KEY, format_modifications(modifications));
+    }
+    if let Some(retention_time) = spectrum.get_retention_time() {
+        let value = match get_entry(&comment, RETENTION_TIME_KEY).and_then(|value| value.split_once(',')) {
+            Some((_, range)) => format!("{},{}", retention_time, range),
+            None => retention_time.to_string()
+        };
+        set_entry(&mut comment, RETENTION_TIME_KEY, value);
+    }
+    if let (Some(charge), Some(_)) = (spectrum.get_charge(), get_entry(&comment, CHARGE_KEY)) {
+        set_entry(&mut comment, CHARGE_KEY, charge.to_string());
+    }
+    comment
+}
@@ -0,0 +1,198 @@
+//! Reading and writing of NIST/PRIDE MSP spectral libraries.
+//! Peptide, charge, modifications and peak annotations are parsed into a `LibrarySpectrum`,
+//! which can be converted into a MGF spectrum.
+
+pub(crate) mod comment;
+pub mod reader;
+pub mod spectrum;
+pub mod writer;
+
+#[cfg(test)]
+mod test {
+    // std imports
+    use std::fs;
+    use std::path::Path;
+
+    // 3rd party imports
+    use fallible_iterator::FallibleIterator;
+
+    // internal imports
+    use super::*;
+    use crate::error::Error;
+    use crate::mgf::spectrum::Spectrum as MgfSpectrum;
+    use crate::mgf::writer::Writer as MgfWriter;
+
+    const MSP_FILE_PATH_STR: &str = "../test_files/msp/small.msp";
+
+    #[test]
+    /// Reads peptide and small molecule entries.
+    fn test_reading() {
+        let spectra: Vec<spectrum::LibrarySpectrum> = reader::Reader::new(Path::new(MSP_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(spectra.len(), 3);
+
+        let spectrum = &spectra[0];
+        assert_eq!(spectrum.get_name(), "AAAACALTPGPLADLAAR/2_1(4,C,CAM)");
+        assert_eq!(spectrum.get_peptide(), Some("AAAACALTPGPLADLAAR"));
+        assert_eq!(spectrum.get_charge(), Some(2));
+        assert_eq!(spectrum.get_precursor_mz(), Some(870.949));
+        assert_eq!(spectrum.get_molecular_weight(), Some(1740.8924));
+        assert_eq!(spectrum.get_modifications(), &vec![spectrum::Modification::new(4, 'C', "CAM".to_string())]);
+        assert_eq!(
+            spectrum.get_comment_value("Protein"),
+            Some("\"sp|O43707|ACTN4_HUMAN Alpha-actinin-4\"")
+        );
+        assert_eq!(spectrum.get_comment_value("Nreps"), Some("2/3"));
+        assert_eq!(spectrum.get_mz_list(), &vec![110.0712, 147.1128, 262.1395]);
+        assert_eq!(spectrum.get_intensity_list(), &vec![500.0, 1200.0, 85.5]);
+        assert_eq!(
+            spectrum.get_peak_annotation_list(),
+            &vec![Some("y1/0.01".to_string()), Some("b2/-0.01,y1-H2O/0.02".to_string()), Some("?".to_string())]
+        );
+
+        let spectrum = &spectra[1];
+        assert_eq!(spectrum.get_peptide(), Some("LMEPDAVSAMK"));
+        assert_eq!(spectrum.get_charge(), Some(3));
+        assert_eq!(spectrum.get_modifications().len(), 2);
+        assert_eq!(spectrum.get_modifications()[0].get_name(), "Glu->pyro-Glu");
+        assert_eq!(spectrum.get_modifications()[1].get_position(), 9);
+        assert_eq!(spectrum.get_additional_field("Synon"), Some("$Mod2=1"));
+        assert_eq!(spectrum.get_peak_annotation_list(), &vec![None, Some("y3/0.00".to_string())]);
+
+        let spectrum = &spectra[2];
+        assert_eq!(spectrum.get_name(), "Caffeine");
+        assert_eq!(spectrum.get_peptide(), None);
+        assert_eq!(spectrum.get_charge(), None);
+        assert_eq!(spectrum.get_precursor_mz(), Some(195.0877));
+        assert!(spectrum.get_modifications().is_empty());
+        assert!(spectrum.get_peak_annotation_list().is_empty());
+    }
+
+    #[test]
+    /// Writes the read spectra, which reproduces the file.
+    fn test_writing() {
+        let spectra: Vec<spectrum::LibrarySpectrum> = reader::Reader::new(Path::new(MSP_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        let written_bytes = writer.write_all(spectra.iter()).unwrap();
+        writer.flush().unwrap();
+        let buffer = writer.into_inner();
+        assert_eq!(written_bytes, buffer.len());
+        assert_eq!(String::from_utf8(buffer).unwrap(), fs::read_to_string(MSP_FILE_PATH_STR).unwrap());
+
+        // Modifications and precursor of a new spectrum
+        let spectrum = spectrum::LibrarySpectrum::new("PEPMK/2".to_string(), vec![100.5], vec![20.0])
+            .with_precursor_mz(Some(310.1))
+            .with_modifications(vec![spectrum::Modification::new(3, 'M', "Oxidation".to_string())]);
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_spectrum(&spectrum).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "Name: PEPMK/2\nPrecursorMZ: 310.1\nComment: Mods=1/3,M,Oxidation\nNum peaks: 1\n100.5\t20\n\n"
+        );
+    }
+
+    #[test]
+    /// Converts library spectra into MGF spectra and writes them.
+    fn test_conversion_to_mgf() {
+        let spectra: Vec<MgfSpectrum> = reader::Reader::new(Path::new(MSP_FILE_PATH_STR), 1024)
+            .unwrap()
+            .map(|spectrum| Ok(MgfSpectrum::from(spectrum)))
+            .collect()
+            .unwrap();
+        assert_eq!(spectra[2].get_title(), "Caffeine");
+        assert!(spectra[2].get_precursor_charges().is_empty());
+
+        let mut writer = MgfWriter::from_writer(Vec::new());
+        writer.write_spectrum(&spectra[0]).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "BEGIN IONS\nTITLE=AAAACALTPGPLADLAAR/2_1(4,C,CAM)\nPEPMASS=870.949\nCHARGE=2+\nSEQ=AAAACALTPGPLADLAAR\n\
+            110.0712 500 y1/0.01\n147.1128 1200 b2/-0.01,y1-H2O/0.02\n262.1395 85.5 ?\nEND IONS\n"
+        );
+    }
+
+    #[test]
+    /// Parses and formats comments and modifications.
+    fn test_comment_coding() {
+        let entries = comment::parse_comment(" Spec=Consensus  Protein=\"a b\" Flag Mods=0 Protein=\"c\" ");
+        assert_eq!(entries.len(), 5);
+        assert_eq!(comment::get_entry(&entries, "Protein"), Some("\"a b\""));
+        assert_eq!(entries[4], ("Protein".to_string(), "\"c\"".to_string()));
+        assert_eq!(comment::get_entry(&entries, "Flag"), Some(""));
+        assert_eq!(comment::format_comment(&entries), "Spec=Consensus Protein=\"a b\" Flag Mods=0 Protein=\"c\"");
+
+        assert_eq!(comment::parse_modifications("0"), Some(Vec::new()));
+        assert_eq!(comment::parse_modifications("2/4,C,CAM"), None);
+        assert_eq!(comment::parse_modifications("1/x,C,CAM"), None);
+        assert_eq!(comment::parse_modifications("1/4,CM,CAM"), None);
+    }
+
+    #[test]
+    /// Writes back repeated fields and comment entries and a `Mods=` which cannot be parsed unchanged.
+    fn test_unparsable_modifications_roundtrip() {
+        let msp_content = "Name: AAAACALTPGPLADLAAR/2\nSynon: a\nSynon: b\n\
+            Comment: Mods=1(4,C,CAM) Protein=\"x\" Protein=\"y\"\nNum peaks: 1\n110.0712\t500\n\n";
+        let spectra: Vec<spectrum::LibrarySpectrum> = reader::Reader::from_reader(msp_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert!(spectra[0].get_modifications().is_empty());
+        assert_eq!(spectra[0].get_comment_value("Mods"), Some("1(4,C,CAM)"));
+        assert_eq!(spectra[0].get_additional_fields().len(), 2);
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), msp_content);
+
+        // Modifications set explicitly replace the raw value
+        let spectrum = spectra[0].clone()
+            .with_modifications(vec![spectrum::Modification::new(4, 'C', "CAM".to_string())]);
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_spectrum(&spectrum).unwrap();
+        assert!(String::from_utf8(writer.into_inner()).unwrap().contains("Comment: Mods=1/4,C,CAM Protein="));
+    }
+
+    #[test]
+    /// Takes the charge from `Charge=` of the comment if the name has no charge.
+    fn test_charge_in_comment() {
+        let msp_content = "Name: Caffeine\nComment: Charge=1 Formula=C8H10N4O2\nNum peaks: 1\n110.0712\t500\n\n";
+        let spectra: Vec<spectrum::LibrarySpectrum> = reader::Reader::from_reader(msp_content.as_bytes())
+            .collect()
+            .unwrap();
+        assert_eq!(spectra[0].get_peptide(), None);
+        assert_eq!(spectra[0].get_charge(), Some(1));
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), msp_content);
+
+        let error = reader::Reader::from_reader("Name: Caffeine\nComment: Charge=x\nNum peaks: 0\n".as_bytes())
+            .next()
+            .unwrap_err();
+        assert!(matches!(&error, Error::ChargeParse { value, .. } if value == "x"));
+        assert_eq!(error.get_position().unwrap().get_line_number(), Some(2));
+    }
+
+    #[test]
+    /// Checks that malformed entries are reported.
+    fn test_reading_malformed_input() {
+        let error = reader::Reader::from_reader("MW: 100\nName: A/1\n".as_bytes()).next().unwrap_err();
+        assert!(matches!(error, Error::MissingHeader { .. }));
+
+        let error = reader::Reader::from_reader("Name: A/1\nNum peaks: 2\n100 20\n\nName: B/1\n".as_bytes())
+            .next()
+            .unwrap_err();
+        assert!(matches!(&error, Error::MalformedHeader { header, .. } if header == "Num peaks: 2 (found 1 peaks)"));
+        assert_eq!(error.get_position().unwrap().get_line_number(), Some(2));
+
+        let error = reader::Reader::from_reader("Name: A/1\nNum peaks: 1\n100\n".as_bytes()).next().unwrap_err();
+        assert!(matches!(error, Error::MissingPeakValue { .. }));
+
+        let error = reader::Reader::from_reader("Name: A/1\nMW: 100\n".as_bytes()).next().unwrap_err();
+        assert!(matches!(error, Error::MalformedHeader { .. }));
+    }
+}
@@ -0,0 +1,280 @@
+// std imports
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+
+// internal imports
+use crate::compression::open_reader;
+use crate::error::{Error, Position, Result, parse_float};
+use crate::index::parse_int;
+use crate::line_reader::{LineReader, split_first_token};
+use crate::msp::comment::{
+    CHARGE_KEY, MODIFICATIONS_KEY, PARENT_KEY, RETENTION_TIME_KEY, get_entry, parse_comment, parse_modifications,
+    parse_retention_time
+};
+use crate::msp::spectrum::LibrarySpectrum;
+
+/// M/Z, intensity and annotation lists of the peak lines
+type Peaks = (Vec<f64>, Vec<f32>, Vec<Option<String>>);
+
+/// Reader for NIST/PRIDE MSP spectral libraries
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+/// Lines starting with `#` preceding an entry are skipped.
+pub struct Reader<R: BufRead> {
+    internal_reader: LineReader<R>
+}
+
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given MSP file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
+    ///
+    /// # Arguments
+    ///
+    /// * `msp_file_path` - Path to MSP file
+    /// * `buffer_size` - Size of the internal read buffer
+    ///
+    pub fn new(msp_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Ok(Self {
+            internal_reader: LineReader::new(
+                open_reader(msp_file_path, buffer_size)?,
+                Some(msp_file_path.to_path_buf())
+            )
+        })
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the MSP content
+    ///
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self {
+            internal_reader: LineReader::new(internal_reader, None)
+        }
+    }
+
+    /// Reads the given number of peak lines, i.e. m/z, intensity and an optional quoted annotation.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_peaks` - Number of peaks
+    /// * `num_peaks_position` - Position of the `Num peaks:` line
+    ///
+    fn read_peaks(&mut self, num_peaks: usize, num_peaks_position: Position) -> Result<Peaks> {
+        let mut mz_list: Vec<f64> = Vec::with_capacity(num_peaks);
+        let mut intensity_list: Vec<f32> = Vec::with_capacity(num_peaks);
+        let mut peak_annotation_list: Vec<Option<String>> = Vec::new();
+        let mut line = String::new();
+        while mz_list.len() < num_peaks {
+            if self.internal_reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if !line.starts_with(|c: char| c.is_ascii_digit()) {
+                break;
+            }
+            let (mz, remainder) = split_first_token(line);
+            mz_list.push(parse_float(mz, || self.internal_reader.get_position())?);
+            let (intensity, annotation) = split_first_token(remainder);
+            if intensity.is_empty() {
+                return Err(Error::MissingPeakValue {
+                    value_name: "intensity",
+                    position: self.internal_reader.get_position()
+                });
+            }
+            intensity_list.push(parse_float(intensity, || self.internal_reader.get_position())?);
+            if !annotation.is_empty() {
+                let annotation = annotation.strip_prefix('"')
+                    .and_then(|annotation| annotation.strip_suffix('"'))
+                    .unwrap_or(annotation);
+                peak_annotation_list.resize(mz_list.len() - 1, None);
+                peak_annotation_list.push(Some(annotation.to_string()));
+            }
+        }
+        if mz_list.len() < num_peaks {
+            return Err(Error::MalformedHeader {
+                header: format!("Num peaks: {} (found {} peaks)", num_peaks, mz_list.len()),
+                position: num_peaks_position
+            });
+        }
+        if !peak_annotation_list.is_empty() {
+            peak_annotation_list.resize(mz_list.len(), None);
+        }
+        Ok((mz_list, intensity_list, peak_annotation_list))
+    }
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
+    type Item = LibrarySpectrum;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        let mut name: Option<String> = None;
+        let mut name_position = Position::default();
+        let mut precursor_mz: Option<f64> = None;
+        let mut molecular_weight: Option<f64> = None;
+        let mut comment: Vec<(String, String)> = Vec::new();
+        let mut comment_position = Position::default();
+        let mut additional_fields: Vec<(String, String)> = Vec::new();
+
+        let mut line = String::new();
+        loop {
+            if self.internal_reader.read_line(&mut line)? == 0 {
+                // Entry without `Num peaks:`
+                if let Some(name) = name {
+                    return Err(Error::MalformedHeader {
+                        header: format!("Name: {}", name),
+                        position: self.internal_reader.get_position()
+                    });
+                }
+                return Ok(None);
+            }
+            let line = line.trim();
+            if line.is_empty() || (name.is_none() && line.starts_with('#')) {
+                continue;
+            }
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) if name.is_some() || key.eq_ignore_ascii_case("Name") => (key.trim(), value.trim()),
+                _ => return Err(Error::MissingHeader {
+                    line: line.to_string(),
+                    position: self.internal_reader.get_position()
+                })
+            };
+            match key.to_ascii_lowercase().as_str() {
+                "name" => {
+                    name = Some(value.to_string());
+                    name_position = self.internal_reader.get_position();
+                },
+                "mw" => molecular_weight = Some(parse_float(value, || self.internal_reader.get_position())?),
+                "precursormz" => precursor_mz = Some(parse_float(value, || self.internal_reader.get_position())?),
+                "comment" => {
+                    comment = parse_comment(value);
+                    comment_position = self.internal_reader.get_position();
+                },
+                "num peaks" | "numpeaks" => {
+                    let num_peaks: usize = parse_int(value, &self.internal_reader.get_position())?;
+                    let (mz_list, intensity_list, peak_annotation_list) = self.read_peaks(
+                        num_peaks,
+                        self.internal_reader.get_position()
+                    )?;
+                    let name = name.unwrap_or_default();
+                    let (peptide, charge) = parse_peptide_and_charge(&name, &name_position, &comment, &comment_position)?;
+                    break Ok(Some(build_spectrum(
+                        name,
+                        precursor_mz,
+                        molecular_weight,
+                        comment,
+                        additional_fields,
+                        mz_list,
+                        intensity_list
+                    ).with_peptide(peptide)
+                        .with_charge(charge)
+                        .with_peak_annotations(peak_annotation_list)));
+                },
+                _ => {
+                    additional_fields.push((key.to_string(), value.to_string()));
+                }
+            }
+        }
+    }
+}
+
+/// Parses peptide and charge from the name (`<peptide>/<charge>`). Names without charge,
+/// e.g. of non-peptide compounds, have no peptide and take the charge from `Charge=` of the comment.
+///
+/// # Arguments
+///
+/// * `name` - Name
+/// * `name_position` - Position of the `Name:` line
+/// * `comment` - Comment entries
+/// * `comment_position` - Position of the `Comment:` line
+///
+pub(crate) fn parse_peptide_and_charge(name: &str, name_position: &Position, comment: &[(String, String)],
+    comment_position: &Position) -> Result<(Option<String>, Option<i8>)> {
+    // Name of peptides is `<peptide>/<charge>`, optionally followed by `_<modifications>`.
+    // Modifications within the peptide, e.g. `n[43]PEPS[167]K`, are removed.
+    if let Some((peptide, charge)) = name.split_once('/') {
+        let charge = charge.split('_').next().unwrap_or_default();
+        let charge = charge.parse::<i8>().map_err(|_| Error::ChargeParse {
+            value: charge.to_string(),
+            position: name_position.clone()
+        })?;
+        return Ok((Some(strip_modifications(peptide)), Some(charge)));
+    }
+    match get_entry(comment, CHARGE_KEY) {
+        Some(charge) => match charge.parse::<i8>() {
+            Ok(charge) => Ok((None, Some(charge))),
+            Err(_) => Err(Error::ChargeParse {
+                value: charge.to_string(),
+                position: comment_position.clone()
+            })
+        },
+        None => Ok((None, None))
+    }
+}
+
+/// Creates the library spectrum, taking the modifications from `Mods=` (none if it cannot be parsed,
+/// the raw value stays in the comment), the retention time from `RetentionTime=`
+/// and the precursor m/z from `Parent=`, if not given by `PrecursorMZ:`.
+///
+/// # Arguments
+///
+/// * `name` - Name
+/// * `precursor_mz` - Value of `PrecursorMZ:`
+/// * `molecular_weight` - Value of `MW:`
+/// * `comment` - Comment entries
+/// * `additional_fields` - Fields without dedicated member
+/// * `mz_list` - M/Z list
+/// * `intensity_list` - Intensity list
+///
+fn build_spectrum(name: String, precursor_mz: Option<f64>, molecular_weight: Option<f64>,
+    comment: Vec<(String, String)>, additional_fields: Vec<(String, String)>,
+    mz_list: Vec<f64>, intensity_list: Vec<f32>) -> LibrarySpectrum {
+    let modifications = get_entry(&comment, MODIFICATIONS_KEY)
+        .and_then(parse_modifications)
+        .unwrap_or_default();
+    let retention_time = get_entry(&comment, RETENTION_TIME_KEY)
+        .and_then(parse_retention_time);
+    let precursor_mz = precursor_mz.or_else(|| {
+        get_entry(&comment, PARENT_KEY).and_then(|parent| fast_float::parse(parent).ok())
+    });
+    LibrarySpectrum::new(name, mz_list, intensity_list)
+        .with_precursor_mz(precursor_mz)
+        .with_retention_time(retention_time)
+        .with_molecular_weight(molecular_weight)
+        .with_modifications(modifications)
+        .with_comment(comment)
+        .with_additional_fields(additional_fields)
+}
+
+/// Removes modifications in brackets and terminal markers from a peptide,
+/// e.g. `n[43]PEPS[167]K` => `PEPSK`
+///
+/// # Arguments
+///
+/// * `peptide` - Peptide as given in the name
+///
+fn strip_modifications(peptide: &str) -> String {
+    let mut depth: usize = 0;
+    peptide.chars()
+        .filter(|c| match c {
+            '[' => {
+                depth += 1;
+                false
+            },
+            ']' => {
+                depth = depth.saturating_sub(1);
+                false
+            },
+            _ => depth == 0 && c.is_ascii_uppercase()
+        })
+        .collect()
+}
@@ -0,0 +1,343 @@
+// internal imports
+use crate::mgf::spectrum::{Precursor as MgfPrecursor, Spectrum as MgfSpectrum};
+use crate::msp::comment::get_entry;
+
+/// Modification of a library peptide, e.g. `4,C,CAM` in `Mods=1/4,C,CAM`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Modification {
+    position: i32,
+    residue: char,
+    name: String
+}
+
+impl Modification {
+    /// Creates a new modification
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Position of the modified residue (0-based, -1 for the N-terminus in SpectraST libraries)
+    /// * `residue` - Modified residue
+    /// * `name` - Name of the modification, e.g. `CAM` or `Oxidation`
+    ///
+    pub fn new(position: i32, residue: char, name: String) -> Self {
+        Self {
+            position,
+            residue,
+            name
+        }
+    }
+
+    /// Returns the position of the modified residue (0-based, -1 for the N-terminus in SpectraST libraries)
+    ///
+    pub fn get_position(&self) -> i32 {
+        self.position
+    }
+
+    /// Returns the modified residue
+    ///
+    pub fn get_residue(&self) -> char {
+        self.residue
+    }
+
+    /// Returns the name of the modification
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Spectrum of a spectral library, e.g. an entry of a MSP or sptxt file.
+/// Can be converted from and into a MGF spectrum, e.g. to write it with `crate::mgf::writer::Writer`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibrarySpectrum {
+    name: String,
+    peptide: Option<String>,
+    charge: Option<i8>,
+    precursor_mz: Option<f64>,
+    retention_time: Option<f64>,
+    molecular_weight: Option<f64>,
+    modifications: Vec<Modification>,
+    comment: Vec<(String, String)>,
+    additional_fields: Vec<(String, String)>,
+    mz_list: Vec<f64>,
+    intensity_list: Vec<f32>,
+    peak_annotation_list: Vec<Option<String>>
+}
+
+impl LibrarySpectrum {
+    /// Creates a new library spectrum
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name, for peptides usually `<peptide>/<charge>`, e.g. `AAAAK/2`
+    /// * `mz_list` - M/Z list
+    /// * `intensity_list` - Intensity list
+    ///
+    pub fn new(name: String, mz_list: Vec<f64>, intensity_list: Vec<f32>) -> Self {
+        Self {
+            name,
+            peptide: None,
+            charge: None,
+            precursor_mz: None,
+            retention_time: None,
+            molecular_weight: None,
+            modifications: Vec::new(),
+            comment: Vec::new(),
+            additional_fields: Vec::new(),
+            mz_list,
+            intensity_list,
+            peak_annotation_list: Vec::new()
+        }
+    }
+
+    /// Sets the peptide sequence
+    ///
+    /// # Arguments
+    ///
+    /// * `peptide` - Unmodified peptide sequence, see `with_modifications()`
+    ///
+    pub fn with_peptide(mut self, peptide: Option<String>) -> Self {
+        self.peptide = peptide;
+        self
+    }
+
+    /// Sets the precursor charge
+    ///
+    /// # Arguments
+    ///
+    /// * `charge` - Precursor charge
+    ///
+    pub fn with_charge(mut self, charge: Option<i8>) -> Self {
+        self.charge = charge;
+        self
+    }
+
+    /// Sets the precursor m/z
+    ///
+    /// # Arguments
+    ///
+    /// * `precursor_mz` - Precursor m/z
+    ///
+    pub fn with_precursor_mz(mut self, precursor_mz: Option<f64>) -> Self {
+        self.precursor_mz = precursor_mz;
+        self
+    }
+
+    /// Sets the retention time
+    ///
+    /// # Arguments
+    ///
+    /// * `retention_time` - Retention time in seconds
+    ///
+    pub fn with_retention_time(mut self, retention_time: Option<f64>) -> Self {
+        self.retention_time = retention_time;
+        self
+    }
+
+    /// Sets the molecular weight (`MW:`)
+    ///
+    /// # Arguments
+    ///
+    /// * `molecular_weight` - Molecular weight
+    ///
+    pub fn with_molecular_weight(mut self, molecular_weight: Option<f64>) -> Self {
+        self.molecular_weight = molecular_weight;
+        self
+    }
+
+    /// Sets the modifications of the peptide
+    ///
+    /// # Arguments
+    ///
+    /// * `modifications` - Modifications
+    ///
+    pub fn with_modifications(mut self, modifications: Vec<Modification>) -> Self {
+        self.modifications = modifications;
+        self
+    }
+
+    /// Sets the comment entries, e.g. `Fullname` or `Protein`, which are written back in the given order.
+    /// Values keep their quotes, e.g. `"sp|P02768|ALBU_HUMAN"`. Keys may repeat.
+    ///
+    /// # Arguments
+    ///
+    /// * `comment` - Ordered key-value pairs (value empty for keys without `=`)
+    ///
+    pub fn with_comment(mut self, comment: Vec<(String, String)>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Sets fields without dedicated member, e.g. `Synon` or `DB#`,
+    /// which are written back in the given order. Keys may repeat.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional_fields` - Ordered fields (key without `:`)
+    ///
+    pub fn with_additional_fields(mut self, additional_fields: Vec<(String, String)>) -> Self {
+        self.additional_fields = additional_fields;
+        self
+    }
+
+    /// Sets the annotations of the peaks, e.g. `b2/-0.01,y1-H2O/0.02`.
+    /// Empty if no peak is annotated, otherwise one optional annotation per peak.
+    ///
+    /// # Arguments
+    ///
+    /// * `peak_annotation_list` - Peak annotations without quotes
+    ///
+    pub fn with_peak_annotations(mut self, peak_annotation_list: Vec<Option<String>>) -> Self {
+        self.peak_annotation_list = peak_annotation_list;
+        self
+    }
+
+    /// Returns the name
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the unmodified peptide sequence
+    ///
+    pub fn get_peptide(&self) -> Option<&str> {
+        self.peptide.as_deref()
+    }
+
+    /// Returns the precursor charge
+    ///
+    pub fn get_charge(&self) -> Option<i8> {
+        self.charge
+    }
+
+    /// Returns the precursor m/z
+    ///
+    pub fn get_precursor_mz(&self) -> Option<f64> {
+        self.precursor_mz
+    }
+
+    /// Returns the retention time in seconds
+    ///
+    pub fn get_retention_time(&self) -> Option<f64> {
+        self.retention_time
+    }
+
+    /// Returns the molecular weight
+    ///
+    pub fn get_molecular_weight(&self) -> Option<f64> {
+        self.molecular_weight
+    }
+
+    /// Returns the modifications of the peptide
+    ///
+    pub fn get_modifications(&self) -> &Vec<Modification> {
+        &self.modifications
+    }
+
+    /// Returns the comment entries
+    ///
+    pub fn get_comment(&self) -> &Vec<(String, String)> {
+        &self.comment
+    }
+
+    /// Returns the value of the first comment entry with the given key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key
+    ///
+    pub fn get_comment_value(&self, key: &str) -> Option<&str> {
+        get_entry(&self.comment, key)
+    }
+
+    /// Returns the comment entries for modification
+    ///
+    pub fn get_comment_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.comment
+    }
+
+    /// Returns the fields without dedicated member
+    ///
+    pub fn get_additional_fields(&self) -> &Vec<(String, String)> {
+        &self.additional_fields
+    }
+
+    /// Returns the value of the first field without dedicated member with the given key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key (without `:`)
+    ///
+    pub fn get_additional_field(&self, key: &str) -> Option<&str> {
+        get_entry(&self.additional_fields, key)
+    }
+
+    /// Returns the fields without dedicated member for modification
+    ///
+    pub fn get_additional_fields_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.additional_fields
+    }
+
+    /// Returns the M/Z list
+    ///
+    pub fn get_mz_list(&self) -> &Vec<f64> {
+        &self.mz_list
+    }
+
+    /// Returns the intensity list
+    ///
+    pub fn get_intensity_list(&self) -> &Vec<f32> {
+        &self.intensity_list
+    }
+
+    /// Returns the peak annotations, empty if no peak is annotated
+    ///
+    pub fn get_peak_annotation_list(&self) -> &Vec<Option<String>> {
+        &self.peak_annotation_list
+    }
+}
+
+impl From<LibrarySpectrum> for MgfSpectrum {
+    /// Converts the library spectrum into a MGF spectrum. The name becomes the title,
+    /// the peptide `SEQ=` and the peak annotations are kept.
+    ///
+    fn from(spectrum: LibrarySpectrum) -> Self {
+        let additional_params: Vec<(String, String)> = spectrum.peptide.into_iter()
+            .map(|peptide| ("SEQ".to_string(), peptide))
+            .collect();
+        let precursors: Vec<MgfPrecursor> = spectrum.precursor_mz.iter()
+            .map(|mz| MgfPrecursor::new(*mz, None))
+            .collect();
+        MgfSpectrum::new(
+            spectrum.name,
+            precursors,
+            spectrum.charge.into_iter().collect(),
+            spectrum.retention_time,
+            spectrum.mz_list,
+            spectrum.intensity_list
+        )
+            .with_peak_annotations(spectrum.peak_annotation_list)
+            .with_additional_params(additional_params)
+    }
+}
+
+impl From<MgfSpectrum> for LibrarySpectrum {
+    /// Converts a MGF spectrum into a library spectrum. The peptide is taken from `SEQ=`,
+    /// the charge is the first precursor charge and the name is `<peptide>/<charge>`,
+    /// or the title if the peptide or charge is unknown.
+    ///
+    fn from(mgf_spectrum: MgfSpectrum) -> Self {
+        let peptide = mgf_spectrum.get_additional_param("SEQ").map(|peptide| peptide.to_string());
+        let charge = mgf_spectrum.get_precursor_charges().first().copied();
+        let name = match (&peptide, charge) {
+            (Some(peptide), Some(charge)) => format!("{}/{}", peptide, charge),
+            _ => mgf_spectrum.get_title().to_string()
+        };
+        Self::new(name, mgf_spectrum.get_mz_list().clone(), mgf_spectrum.get_intensity_list().clone())
+            .with_peptide(peptide)
+            .with_charge(charge)
+            .with_precursor_mz(mgf_spectrum.get_precursors().first().map(|precursor| precursor.get_mz()))
+            .with_retention_time(*mgf_spectrum.get_retention_time())
+            .with_peak_annotations(mgf_spectrum.get_peak_annotation_list().clone())
+    }
+}
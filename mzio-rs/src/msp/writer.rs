@@ -0,0 +1,155 @@
+// std imports
+use std::fs::File;
+use std::iter::zip;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use std::path::Path;
+
+// internal imports
+use crate::compression::{Compression, Encoder};
+use crate::error::{Error, Result};
+use crate::msp::comment::{PARENT_KEY, build_comment, format_comment, get_entry, set_entry};
+use crate::msp::spectrum::LibrarySpectrum;
+
+/// Writer for MSP spectral libraries
+/// Works on any target implementing `Write`, e.g. files, stdout or `Vec<u8>`.
+/// Use flush() to make sure the buffer is written completely.
+pub struct Writer<W: Write> {
+    internal_writer: W
+}
+
+impl Writer<BufWriter<File>> {
+    /// Creates a new buffered Writer for the given MSP file
+    ///
+    /// # Arguments
+    ///
+    /// * `msp_file_path` - Path to MSP file
+    ///
+    pub fn new(msp_file_path: &Path) -> Result<Self> {
+        let msp_file: File = File::create(msp_file_path)
+            .map_err(|err| Error::io_with_path(err, msp_file_path))?;
+        Ok(Self::from_writer(BufWriter::new(msp_file)))
+    }
+}
+
+impl Writer<Encoder<BufWriter<File>>> {
+    /// Creates a new buffered Writer for the given MSP file, compressing the output.
+    /// Use finish() to complete the compressed file.
+    ///
+    /// # Arguments
+    ///
+    /// * `msp_file_path` - Path to MSP file
+    /// * `compression` - Compression of the written file, see `Compression::from_path()`
+    ///
+    pub fn with_compression(msp_file_path: &Path, compression: Compression) -> Result<Self> {
+        Ok(Self::from_writer(Encoder::create(msp_file_path, compression)?))
+    }
+}
+
+impl<W: Write> Writer<Encoder<W>> {
+    /// Flushes the buffer, completes the compressed stream and returns the underlying write target.
+    ///
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        self.internal_writer.finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new Writer for any write target.
+    /// The target is used as is, so wrap unbuffered targets in a `BufWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_writer` - Write target
+    ///
+    pub fn from_writer(internal_writer: W) -> Self {
+        Self {
+            internal_writer
+        }
+    }
+
+    /// Returns the underlying write target, e.g. to access an in-memory buffer.
+    /// Make sure to call flush() first.
+    ///
+    pub fn into_inner(self) -> W {
+        self.internal_writer
+    }
+
+    /// Writes the complete byte slice.
+    /// Returns the number of written bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to write
+    ///
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.internal_writer.write_all(bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Writes a library spectrum followed by an empty line.
+    /// The modifications, retention time and precursor m/z are written as `Mods=`, `RetentionTime=`
+    /// and `Parent=` of the comment, the precursor m/z as `PrecursorMZ:` if the comment contains no `Parent=`.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectrum` - Library spectrum
+    ///
+    pub fn write_spectrum(&mut self, spectrum: &LibrarySpectrum) -> Result<usize> {
+        let mut comment = build_comment(spectrum);
+        let mut lines: Vec<String> = vec![format!("Name: {}", spectrum.get_name())];
+        if let Some(molecular_weight) = spectrum.get_molecular_weight() {
+            lines.push(format!("MW: {}", molecular_weight));
+        }
+        if let Some(precursor_mz) = spectrum.get_precursor_mz() {
+            if get_entry(&comment, PARENT_KEY).is_some() {
+                set_entry(&mut comment, PARENT_KEY, precursor_mz.to_string());
+            } else {
+                lines.push(format!("PrecursorMZ: {}", precursor_mz));
+            }
+        }
+        for (key, value) in spectrum.get_additional_fields() {
+            lines.push(format!("{}: {}", key, value));
+        }
+        if !comment.is_empty() {
+            lines.push(format!("Comment: {}", format_comment(&comment)));
+        }
+        lines.push(format!("Num peaks: {}", spectrum.get_mz_list().len()));
+        for (idx, (mz, intensity)) in zip(spectrum.get_mz_list(), spectrum.get_intensity_list()).enumerate() {
+            match spectrum.get_peak_annotation_list().get(idx) {
+                Some(Some(annotation)) => lines.push(format!("{}\t{}\t\"{}\"", mz, intensity, annotation)),
+                _ => lines.push(format!("{}\t{}", mz, intensity))
+            }
+        }
+        lines.push(String::new());
+
+        let mut content = lines.join("\n");
+        content.push('\n');
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Writes multiple library spectra to file.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectra` - Iterator of library spectra
+    ///
+    pub fn write_all<'b, I>(&mut self, spectra: I) -> Result<usize>
+    where
+        I: Iterator<Item = &'b LibrarySpectrum>,
+    {
+        let mut written_bytes: usize = 0;
+        for spectrum in spectra {
+            written_bytes += self.write_spectrum(spectrum)?;
+        }
+        Ok(written_bytes)
+    }
+
+    /// Flushes the buffer
+    ///
+    pub fn flush(&mut self) -> Result<()> {
+        self.internal_writer.flush()?;
+        Ok(())
+    }
+}
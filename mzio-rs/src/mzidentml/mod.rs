@@ -0,0 +1,165 @@
+//! Streaming reading of mzIdentML files (version 1.1 and 1.2).
+//! Each `<SpectrumIdentificationItem>` is returned as a flat PSM with the resolved peptide, modifications,
+//! proteins, scores and further parameters. Spectra of MGF files are linked via the spectrum title (`TITLE`) and index.
+
+pub mod psm;
+pub mod reader;
+
+#[cfg(test)]
+mod test {
+    // std imports
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    // 3rd party imports
+    use fallible_iterator::FallibleIterator;
+
+    // internal imports
+    use super::*;
+    use crate::error::Error;
+    use crate::mgf::reader::Reader as MgfReader;
+    use crate::mgf::spectrum::Spectrum as MgfSpectrum;
+    use crate::mgf::writer::Writer as MgfWriter;
+
+    const MZIDENTML_FILE_PATH_STR: &str = "../test_files/mzidentml/small.mzid";
+    const MGF_FILE_PATH_STR: &str = "../test_files/mgf/Velos005137.mgf";
+
+    #[test]
+    /// Reads all PSMs and resolves their peptides, proteins and scores.
+    fn test_reading() {
+        let psms: Vec<psm::Psm> = reader::Reader::new(Path::new(MZIDENTML_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(psms.len(), 3);
+
+        let psm = &psms[0];
+        assert_eq!(psm.get_spectrum_id(), "index=0");
+        assert_eq!(psm.get_spectrum_index(), Some(0));
+        assert_eq!(psm.get_spectrum_title(), Some("824.836730957031_212.9232"));
+        assert_eq!(psm.get_spectra_data_location(), Some("/data/Velos005137.mgf"));
+        assert!((psm.get_retention_time().unwrap() - 212.9232).abs() < 1e-6);
+        assert_eq!(psm.get_rank(), 1);
+        assert_eq!(psm.get_charge(), 2);
+        assert_eq!(psm.get_experimental_mz(), 824.836730957031);
+        assert_eq!(psm.get_calculated_mz(), Some(824.8351));
+        assert!(psm.is_passing_threshold());
+        assert_eq!(psm.get_sequence(), "LVNEVTEFAK");
+        assert!(psm.get_modifications().is_empty());
+        assert_eq!(psm.get_proteins().len(), 2);
+        assert_eq!(psm.get_proteins()[0].get_accession(), "sp|P02768|ALBU_HUMAN");
+        assert_eq!(psm.get_proteins()[0].get_description(), Some("Serum albumin OS=Homo sapiens"));
+        assert_eq!(psm.get_proteins()[0].get_start(), Some(66));
+        assert_eq!(psm.get_proteins()[0].get_end(), Some(75));
+        assert_eq!(psm.get_proteins()[1].get_post(), Some('S'));
+        assert_eq!(psm.get_proteins()[1].get_description(), None);
+        assert!(!psm.is_decoy());
+        assert_eq!(psm.get_scores().len(), 2);
+        assert_eq!(psm.get_score("MS:1002049"), Some(152.0));
+        assert_eq!(psm.get_score("MS-GF:EValue"), Some(1.2e-12));
+        assert_eq!(psm.get_score("IsotopeError"), None);
+        assert_eq!(psm.get_params().len(), 2);
+        assert_eq!(psm.get_param("IsotopeError"), Some("0"));
+        assert_eq!(psm.get_param("AssumedDissociationMethod"), Some("HCD"));
+
+        let psm = &psms[1];
+        assert_eq!(psm.get_rank(), 2);
+        assert_eq!(psm.get_spectrum_title(), Some("824.836730957031_212.9232"));
+        assert!(!psm.is_passing_threshold());
+        assert!(psm.is_decoy());
+        assert_eq!(psm.get_proteins()[0].get_pre(), Some('-'));
+        let modification = &psm.get_modifications()[0];
+        assert_eq!(modification.get_location(), Some(0));
+        assert!(modification.get_residues().is_empty());
+        assert_eq!(modification.get_monoisotopic_mass_delta(), Some(42.010565));
+        assert_eq!(modification.get_name(), None);
+
+        let psm = &psms[2];
+        assert_eq!(psm.get_spectrum_index(), Some(2));
+        assert_eq!(psm.get_retention_time(), Some(251.9018));
+        assert_eq!(psm.get_sequence(), "MCTVASLR");
+        assert_eq!(psm.get_modifications().len(), 2);
+        let modification = &psm.get_modifications()[1];
+        assert_eq!(modification.get_location(), Some(2));
+        assert_eq!(modification.get_residues(), &vec!['C']);
+        assert_eq!(modification.get_accession(), Some("UNIMOD:4"));
+        assert_eq!(modification.get_name(), Some("Carbamidomethyl"));
+    }
+
+    #[test]
+    /// Reads items without content and keeps only PSM scores and score-like user parameters as scores.
+    fn test_reading_scores_and_params() {
+        let content = "<MzIdentML><SequenceCollection><Peptide id=\"P_1\"><PeptideSequence>PEPTIDE</PeptideSequence>\
+            </Peptide></SequenceCollection><SpectrumIdentificationResult id=\"SIR_1\" spectrumID=\"index=0\">\
+            <SpectrumIdentificationItem id=\"SII_1\" rank=\"1\" chargeState=\"2\" experimentalMassToCharge=\"400.7\" \
+            peptide_ref=\"P_1\"/>\
+            <SpectrumIdentificationItem id=\"SII_2\" rank=\"2\" chargeState=\"2\" experimentalMassToCharge=\"400.7\" \
+            peptide_ref=\"P_1\">\
+            <cvParam accession=\"MS:1001330\" name=\"X!Tandem:expect\" value=\"0.01\"/>\
+            <cvParam accession=\"MS:1001117\" name=\"theoretical mass\" value=\"799.36\"/>\
+            <userParam name=\"Andromeda:score\" value=\"95.5\"/>\
+            <userParam name=\"NumMatchedMainIons\" value=\"7\"/>\
+            </SpectrumIdentificationItem></SpectrumIdentificationResult></MzIdentML>";
+        let psms: Vec<psm::Psm> = reader::Reader::from_reader(content.as_bytes()).collect().unwrap();
+        assert_eq!(psms.len(), 2);
+        assert_eq!(psms[0].get_rank(), 1);
+        assert!(psms[0].get_scores().is_empty());
+        assert!(psms[0].get_params().is_empty());
+
+        let psm = &psms[1];
+        assert_eq!(psm.get_scores().len(), 2);
+        assert_eq!(psm.get_score("X!Tandem:expect"), Some(0.01));
+        assert_eq!(psm.get_score("Andromeda:score"), Some(95.5));
+        assert_eq!(psm.get_params().len(), 2);
+        assert_eq!(psm.get_param("MS:1001117"), Some("799.36"));
+        assert_eq!(psm.get_param("NumMatchedMainIons"), Some("7"));
+    }
+
+    #[test]
+    /// Links the PSMs to the spectra of a MGF file written by the MGF writer, via title and index.
+    fn test_linking_to_mgf() {
+        let spectra: Vec<MgfSpectrum> = MgfReader::new(Path::new(MGF_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let mut writer = MgfWriter::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        writer.flush().unwrap();
+        let buffer = writer.into_inner();
+        let written_spectra: Vec<MgfSpectrum> = MgfReader::from_reader(buffer.as_slice()).collect().unwrap();
+        let spectra_by_title: HashMap<&str, (usize, &MgfSpectrum)> = written_spectra.iter()
+            .enumerate()
+            .map(|(idx, spectrum)| (spectrum.get_title(), (idx, spectrum)))
+            .collect();
+
+        let psms: Vec<psm::Psm> = reader::Reader::new(Path::new(MZIDENTML_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        for psm in psms.iter() {
+            let (idx, spectrum) = spectra_by_title[psm.get_spectrum_title().unwrap()];
+            assert_eq!(psm.get_spectrum_index(), Some(idx));
+            assert_eq!(spectrum.get_precursor_mz(), psm.get_experimental_mz());
+        }
+    }
+
+    #[test]
+    /// Checks that unresolved references and truncated files are reported.
+    fn test_reading_malformed_input() {
+        let content = "<MzIdentML><SequenceCollection>\
+            <PeptideEvidence id=\"PE_1\" peptide_ref=\"P_1\" dBSequence_ref=\"DB_1\"/>\
+            </SequenceCollection></MzIdentML>";
+        let error = reader::Reader::from_reader(content.as_bytes()).next().unwrap_err();
+        assert!(matches!(&error, Error::Xml { message, .. } if message == "unknown reference `DB_1`"));
+
+        let content = "<MzIdentML><SpectrumIdentificationResult id=\"SIR_1\" spectrumID=\"index=0\">\
+            <SpectrumIdentificationItem id=\"SII_1\" rank=\"1\" chargeState=\"2\" experimentalMassToCharge=\"500.2\" \
+            peptide_ref=\"P_1\"></SpectrumIdentificationItem></SpectrumIdentificationResult></MzIdentML>";
+        let error = reader::Reader::from_reader(content.as_bytes()).next().unwrap_err();
+        assert!(matches!(&error, Error::Xml { message, .. } if message == "unknown reference `P_1`"));
+
+        let content = "<MzIdentML><SequenceCollection><Peptide id=\"P_1\"><PeptideSequence>PEPTIDE</PeptideSequence>";
+        let error = reader::Reader::from_reader(content.as_bytes()).next().unwrap_err();
+        assert!(matches!(&error, Error::Xml { message, .. } if message == "unexpected end of file within `<Peptide>`"));
+    }
+}
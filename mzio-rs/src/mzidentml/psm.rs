@@ -0,0 +1,531 @@
+/// Modification of a peptide (`<Modification>`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Modification {
+    location: Option<u32>,
+    residues: Vec<char>,
+    monoisotopic_mass_delta: Option<f64>,
+    accession: Option<String>,
+    name: Option<String>
+}
+
+impl Modification {
+    /// Creates a new modification
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - Location of the modified residue (1-based, 0 for the N-terminus, length + 1 for the C-terminus)
+    /// * `residues` - Possible residues, empty for termini
+    /// * `monoisotopic_mass_delta` - Monoisotopic mass delta
+    /// * `accession` - Accession of the modification, e.g. `UNIMOD:35`
+    /// * `name` - Name of the modification, e.g. `Oxidation`
+    ///
+    pub fn new(location: Option<u32>, residues: Vec<char>, monoisotopic_mass_delta: Option<f64>, accession: Option<String>,
+        name: Option<String>) -> Self {
+        Self {
+            location,
+            residues,
+            monoisotopic_mass_delta,
+            accession,
+            name
+        }
+    }
+
+    /// Returns the location of the modified residue (1-based, 0 for the N-terminus, length + 1 for the C-terminus)
+    ///
+    pub fn get_location(&self) -> Option<u32> {
+        self.location
+    }
+
+    /// Returns the possible residues, empty for termini
+    ///
+    pub fn get_residues(&self) -> &Vec<char> {
+        &self.residues
+    }
+
+    /// Returns the monoisotopic mass delta
+    ///
+    pub fn get_monoisotopic_mass_delta(&self) -> Option<f64> {
+        self.monoisotopic_mass_delta
+    }
+
+    /// Returns the accession, e.g. `UNIMOD:35`
+    ///
+    pub fn get_accession(&self) -> Option<&str> {
+        self.accession.as_deref()
+    }
+
+    /// Returns the name, e.g. `Oxidation`
+    ///
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// Protein a PSM maps to, resolved from `<PeptideEvidence>` and `<DBSequence>`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProteinEvidence {
+    accession: String,
+    description: Option<String>,
+    start: Option<usize>,
+    end: Option<usize>,
+    pre: Option<char>,
+    post: Option<char>,
+    is_decoy: bool
+}
+
+impl ProteinEvidence {
+    /// Creates a new protein evidence
+    ///
+    /// # Arguments
+    ///
+    /// * `accession` - Accession of the protein
+    /// * `description` - Description of the protein
+    ///
+    pub fn new(accession: String, description: Option<String>) -> Self {
+        Self {
+            accession,
+            description,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the location of the peptide within the protein
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Start (1-based)
+    /// * `end` - End (1-based, inclusive)
+    ///
+    pub fn with_location(mut self, start: Option<usize>, end: Option<usize>) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Sets the flanking residues, `-` marks a protein terminus
+    ///
+    /// # Arguments
+    ///
+    /// * `pre` - Residue preceding the peptide
+    /// * `post` - Residue following the peptide
+    ///
+    pub fn with_flanking_residues(mut self, pre: Option<char>, post: Option<char>) -> Self {
+        self.pre = pre;
+        self.post = post;
+        self
+    }
+
+    /// Sets the decoy flag
+    ///
+    /// # Arguments
+    ///
+    /// * `is_decoy` - True if the protein is a decoy
+    ///
+    pub fn with_decoy(mut self, is_decoy: bool) -> Self {
+        self.is_decoy = is_decoy;
+        self
+    }
+
+    /// Returns the accession of the protein
+    ///
+    pub fn get_accession(&self) -> &str {
+        &self.accession
+    }
+
+    /// Returns the description of the protein
+    ///
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the start of the peptide within the protein (1-based)
+    ///
+    pub fn get_start(&self) -> Option<usize> {
+        self.start
+    }
+
+    /// Returns the end of the peptide within the protein (1-based, inclusive)
+    ///
+    pub fn get_end(&self) -> Option<usize> {
+        self.end
+    }
+
+    /// Returns the residue preceding the peptide, `-` for the protein N-terminus
+    ///
+    pub fn get_pre(&self) -> Option<char> {
+        self.pre
+    }
+
+    /// Returns the residue following the peptide, `-` for the protein C-terminus
+    ///
+    pub fn get_post(&self) -> Option<char> {
+        self.post
+    }
+
+    /// Returns true if the protein is a decoy
+    ///
+    pub fn is_decoy(&self) -> bool {
+        self.is_decoy
+    }
+}
+
+/// Score of a PSM, i.e. a numeric PSM score `<cvParam>` or score-like `<userParam>` of the `<SpectrumIdentificationItem>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    accession: Option<String>,
+    name: String,
+    value: f64
+}
+
+impl Score {
+    /// Creates a new score
+    ///
+    /// # Arguments
+    ///
+    /// * `accession` - Accession, e.g. `MS:1002049`, None for user parameters
+    /// * `name` - Name, e.g. `MS-GF:RawScore`
+    /// * `value` - Value
+    ///
+    pub fn new(accession: Option<String>, name: String, value: f64) -> Self {
+        Self {
+            accession,
+            name,
+            value
+        }
+    }
+
+    /// Returns the accession, None for user parameters
+    ///
+    pub fn get_accession(&self) -> Option<&str> {
+        self.accession.as_deref()
+    }
+
+    /// Returns the name
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value
+    ///
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+}
+
+/// Parameter of a PSM, which is no score, i.e. a `<cvParam>` or `<userParam>` of the `<SpectrumIdentificationItem>`,
+/// e.g. `IsotopeError`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    accession: Option<String>,
+    name: String,
+    value: Option<String>
+}
+
+impl Param {
+    /// Creates a new parameter
+    ///
+    /// # Arguments
+    ///
+    /// * `accession` - Accession, None for user parameters
+    /// * `name` - Name, e.g. `IsotopeError`
+    /// * `value` - Value
+    ///
+    pub fn new(accession: Option<String>, name: String, value: Option<String>) -> Self {
+        Self {
+            accession,
+            name,
+            value
+        }
+    }
+
+    /// Returns the accession, None for user parameters
+    ///
+    pub fn get_accession(&self) -> Option<&str> {
+        self.accession.as_deref()
+    }
+
+    /// Returns the name
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value
+    ///
+    pub fn get_value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+}
+
+/// Peptide spectrum match, flattened from a `<SpectrumIdentificationItem>`, its `<SpectrumIdentificationResult>`
+/// and the referenced peptide, peptide evidences and proteins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Psm {
+    spectrum_id: String,
+    spectrum_title: Option<String>,
+    spectra_data_location: Option<String>,
+    retention_time: Option<f64>,
+    rank: u32,
+    charge: i32,
+    experimental_mz: f64,
+    calculated_mz: Option<f64>,
+    pass_threshold: bool,
+    sequence: String,
+    modifications: Vec<Modification>,
+    proteins: Vec<ProteinEvidence>,
+    scores: Vec<Score>,
+    params: Vec<Param>
+}
+
+impl Psm {
+    /// Creates a new PSM
+    ///
+    /// # Arguments
+    ///
+    /// * `spectrum_id` - ID of the spectrum within the spectra file, e.g. `index=5`
+    /// * `rank` - Rank among the PSMs of the spectrum
+    /// * `charge` - Charge
+    /// * `experimental_mz` - Experimental m/z
+    /// * `sequence` - Peptide sequence
+    ///
+    pub fn new(spectrum_id: String, rank: u32, charge: i32, experimental_mz: f64, sequence: String) -> Self {
+        Self {
+            spectrum_id,
+            spectrum_title: None,
+            spectra_data_location: None,
+            retention_time: None,
+            rank,
+            charge,
+            experimental_mz,
+            calculated_mz: None,
+            pass_threshold: false,
+            sequence,
+            modifications: Vec::new(),
+            proteins: Vec::new(),
+            scores: Vec::new(),
+            params: Vec::new()
+        }
+    }
+
+    /// Sets the spectrum title, i.e. the `TITLE` of MGF spectra
+    ///
+    /// # Arguments
+    ///
+    /// * `spectrum_title` - Spectrum title
+    ///
+    pub fn with_spectrum_title(mut self, spectrum_title: Option<String>) -> Self {
+        self.spectrum_title = spectrum_title;
+        self
+    }
+
+    /// Sets the location of the spectra file
+    ///
+    /// # Arguments
+    ///
+    /// * `spectra_data_location` - Location of the spectra file
+    ///
+    pub fn with_spectra_data_location(mut self, spectra_data_location: Option<String>) -> Self {
+        self.spectra_data_location = spectra_data_location;
+        self
+    }
+
+    /// Sets the retention time
+    ///
+    /// # Arguments
+    ///
+    /// * `retention_time` - Retention time in seconds
+    ///
+    pub fn with_retention_time(mut self, retention_time: Option<f64>) -> Self {
+        self.retention_time = retention_time;
+        self
+    }
+
+    /// Sets the calculated m/z
+    ///
+    /// # Arguments
+    ///
+    /// * `calculated_mz` - Calculated m/z
+    ///
+    pub fn with_calculated_mz(mut self, calculated_mz: Option<f64>) -> Self {
+        self.calculated_mz = calculated_mz;
+        self
+    }
+
+    /// Sets if the PSM passes the threshold of the search engine
+    ///
+    /// # Arguments
+    ///
+    /// * `pass_threshold` - True if the PSM passes the threshold
+    ///
+    pub fn with_pass_threshold(mut self, pass_threshold: bool) -> Self {
+        self.pass_threshold = pass_threshold;
+        self
+    }
+
+    /// Sets the modifications
+    ///
+    /// # Arguments
+    ///
+    /// * `modifications` - Modifications
+    ///
+    pub fn with_modifications(mut self, modifications: Vec<Modification>) -> Self {
+        self.modifications = modifications;
+        self
+    }
+
+    /// Sets the proteins
+    ///
+    /// # Arguments
+    ///
+    /// * `proteins` - Proteins the peptide maps to
+    ///
+    pub fn with_proteins(mut self, proteins: Vec<ProteinEvidence>) -> Self {
+        self.proteins = proteins;
+        self
+    }
+
+    /// Sets the scores
+    ///
+    /// # Arguments
+    ///
+    /// * `scores` - Scores
+    ///
+    pub fn with_scores(mut self, scores: Vec<Score>) -> Self {
+        self.scores = scores;
+        self
+    }
+
+    /// Sets the parameters, which are no scores
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters
+    ///
+    pub fn with_params(mut self, params: Vec<Param>) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Returns the ID of the spectrum within the spectra file, e.g. `index=5`
+    ///
+    pub fn get_spectrum_id(&self) -> &str {
+        &self.spectrum_id
+    }
+
+    /// Returns the 0-based index of the spectrum if the spectrum ID has the form `index=<index>`,
+    /// as used for MGF files.
+    ///
+    pub fn get_spectrum_index(&self) -> Option<usize> {
+        self.spectrum_id.strip_prefix("index=").and_then(|index| index.parse().ok())
+    }
+
+    /// Returns the spectrum title (`MS:1000796`), i.e. the `TITLE` of MGF spectra
+    ///
+    pub fn get_spectrum_title(&self) -> Option<&str> {
+        self.spectrum_title.as_deref()
+    }
+
+    /// Returns the location of the spectra file
+    ///
+    pub fn get_spectra_data_location(&self) -> Option<&str> {
+        self.spectra_data_location.as_deref()
+    }
+
+    /// Returns the retention time in seconds
+    ///
+    pub fn get_retention_time(&self) -> Option<f64> {
+        self.retention_time
+    }
+
+    /// Returns the rank among the PSMs of the spectrum
+    ///
+    pub fn get_rank(&self) -> u32 {
+        self.rank
+    }
+
+    /// Returns the charge
+    ///
+    pub fn get_charge(&self) -> i32 {
+        self.charge
+    }
+
+    /// Returns the experimental m/z
+    ///
+    pub fn get_experimental_mz(&self) -> f64 {
+        self.experimental_mz
+    }
+
+    /// Returns the calculated m/z
+    ///
+    pub fn get_calculated_mz(&self) -> Option<f64> {
+        self.calculated_mz
+    }
+
+    /// Returns true if the PSM passes the threshold of the search engine
+    ///
+    pub fn is_passing_threshold(&self) -> bool {
+        self.pass_threshold
+    }
+
+    /// Returns the peptide sequence
+    ///
+    pub fn get_sequence(&self) -> &str {
+        &self.sequence
+    }
+
+    /// Returns the modifications
+    ///
+    pub fn get_modifications(&self) -> &Vec<Modification> {
+        &self.modifications
+    }
+
+    /// Returns the proteins the peptide maps to
+    ///
+    pub fn get_proteins(&self) -> &Vec<ProteinEvidence> {
+        &self.proteins
+    }
+
+    /// Returns true if the peptide maps to decoy proteins only
+    ///
+    pub fn is_decoy(&self) -> bool {
+        !self.proteins.is_empty() && self.proteins.iter().all(|protein| protein.is_decoy())
+    }
+
+    /// Returns the scores
+    ///
+    pub fn get_scores(&self) -> &Vec<Score> {
+        &self.scores
+    }
+
+    /// Returns the value of the score with the given accession or name
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Accession or name, e.g. `MS:1002049` or `MS-GF:RawScore`
+    ///
+    pub fn get_score(&self, key: &str) -> Option<f64> {
+        self.scores.iter()
+            .find(|score| score.get_accession() == Some(key) || score.get_name() == key)
+            .map(|score| score.get_value())
+    }
+
+    /// Returns the parameters, which are no scores
+    ///
+    pub fn get_params(&self) -> &Vec<Param> {
+        &self.params
+    }
+
+    /// Returns the value of the parameter with the given accession or name.
+    /// None if the parameter is missing or has no value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Accession or name, e.g. `IsotopeError`
+    ///
+    pub fn get_param(&self, key: &str) -> Option<&str> {
+        self.params.iter()
+            .find(|param| param.get_accession() == Some(key) || param.get_name() == key)
+            .and_then(|param| param.get_value())
+    }
+}
@@ -0,0 +1,495 @@
+// std imports
+use std::collections::{HashMap, VecDeque};
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+use quick_xml::events::{BytesStart, Event};
+
+// internal imports
+use crate::compression::open_reader;
+use crate::error::{Error, Result, parse_float};
+use crate::index::parse_int;
+use crate::mzidentml::psm::{Modification, Param, ProteinEvidence, Psm, Score};
+use crate::xml::XmlReader;
+
+/// protein description
+const PROTEIN_DESCRIPTION: &str = "MS:1001088";
+/// spectrum title
+const SPECTRUM_TITLE: &str = "MS:1000796";
+/// retention time
+const RETENTION_TIME: &str = "MS:1000894";
+/// scan start time
+const SCAN_START_TIME: &str = "MS:1000016";
+/// minute
+const UNIT_MINUTE: &str = "UO:0000031";
+
+/// PSM score terms of the PSI-MS ontology, i.e. search engine scores, e-values, q-values and PEPs
+const PSM_SCORES: [&str; 29] = [
+    // SEQUEST:xcorr, SEQUEST:deltacn
+    "MS:1001155", "MS:1001156",
+    // Mascot:score, Mascot:expectation value
+    "MS:1001171", "MS:1001172",
+    // OMSSA:evalue, OMSSA:pvalue
+    "MS:1001328", "MS:1001329",
+    // X!Tandem:expect, X!Tandem:hyperscore
+    "MS:1001330", "MS:1001331",
+    // percolator:Q value, percolator:score, percolator:PEP
+    "MS:1001491", "MS:1001492", "MS:1001493",
+    // MS-GF:RawScore, MS-GF:DeNovoScore, MS-GF:Energy, MS-GF:SpecEValue, MS-GF:EValue, MS-GF:QValue,
+    // MS-GF:PepQValue, MS-GF:PEP
+    "MS:1002049", "MS:1002050", "MS:1002051", "MS:1002052", "MS:1002053", "MS:1002054", "MS:1002055", "MS:1002056",
+    // Comet:xcorr, Comet:deltacn, Comet:expectation value
+    "MS:1002252", "MS:1002253", "MS:1002257",
+    // PSM-level local FDR, p-value, e-value, q-value, FDRScore, combined FDRScore, probability
+    "MS:1002351", "MS:1002352", "MS:1002353", "MS:1002354", "MS:1002355", "MS:1002356", "MS:1002357"
+];
+
+/// Parts of user parameter names marking scores, compared case-insensitively
+const SCORE_NAME_PARTS: [&str; 7] = ["score", "evalue", "e-value", "qvalue", "q-value", "pvalue", "p-value"];
+
+/// Peptide of the sequence collection (`<Peptide>`)
+struct Peptide {
+    sequence: String,
+    modifications: Vec<Modification>
+}
+
+/// Peptide evidence of the sequence collection (`<PeptideEvidence>`) with the resolved protein
+struct PeptideEvidence {
+    peptide_ref: String,
+    protein: ProteinEvidence
+}
+
+/// Parameter (`<cvParam>` or `<userParam>`) with the attributes needed to build PSMs
+struct RawParam {
+    accession: Option<String>,
+    name: String,
+    value: Option<String>,
+    unit_accession: Option<String>
+}
+
+/// Streaming reader for mzIdentML.
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+/// The sequence collection and the spectra data of the inputs are kept in memory to resolve the references
+/// of the `<SpectrumIdentificationItem>`s, which are returned as flat PSMs in order of the file.
+/// Spectra written by `crate::mgf::writer::Writer` can be linked via `Psm::get_spectrum_title()`.
+pub struct Reader<R: BufRead> {
+    internal_reader: XmlReader<R>,
+    // Accessions and descriptions of the proteins by ID
+    db_sequences: HashMap<String, (String, Option<String>)>,
+    peptides: HashMap<String, Peptide>,
+    peptide_evidences: HashMap<String, PeptideEvidence>,
+    // Locations of the spectra files by ID
+    spectra_data: HashMap<String, String>,
+    // PSMs of the last read `<SpectrumIdentificationResult>`, which are not returned yet
+    pending_psms: VecDeque<Psm>
+}
+
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given mzIdentML file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
+    ///
+    /// # Arguments
+    ///
+    /// * `mzidentml_file_path` - Path to mzIdentML file
+    /// * `buffer_size` - Size of the internal read buffer
+    ///
+    pub fn new(mzidentml_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Ok(Self::from_xml_reader(XmlReader::new(
+            open_reader(mzidentml_file_path, buffer_size)?,
+            Some(mzidentml_file_path.to_path_buf())
+        )))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the mzIdentML content
+    ///
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self::from_xml_reader(XmlReader::new(internal_reader, None))
+    }
+
+    /// Creates a new Reader from a XML reader
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - XML reader providing the mzIdentML content
+    ///
+    fn from_xml_reader(internal_reader: XmlReader<R>) -> Self {
+        Self {
+            internal_reader,
+            db_sequences: HashMap::new(),
+            peptides: HashMap::new(),
+            peptide_evidences: HashMap::new(),
+            spectra_data: HashMap::new(),
+            pending_psms: VecDeque::new()
+        }
+    }
+
+    /// Reads a `<cvParam>` or `<userParam>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Parameter
+    ///
+    fn read_param(&self, element: &BytesStart) -> Result<RawParam> {
+        Ok(RawParam {
+            accession: self.internal_reader.get_attribute(element, "accession")?,
+            name: self.internal_reader.get_required_attribute(element, "name")?,
+            value: self.internal_reader.get_attribute(element, "value")?,
+            unit_accession: self.internal_reader.get_attribute(element, "unitAccession")?
+        })
+    }
+
+    /// Reads the events up to the end of the given element and returns its parameters.
+    /// Parameters of nested elements are included.
+    ///
+    /// # Arguments
+    ///
+    /// * `element_name` - Name of the element
+    ///
+    fn read_params(&mut self, element_name: &[u8]) -> Result<Vec<RawParam>> {
+        let mut params: Vec<RawParam> = Vec::new();
+        loop {
+            match self.internal_reader.read_event()? {
+                Event::Start(element) | Event::Empty(element) if is_param(&element) => {
+                    params.push(self.read_param(&element)?);
+                },
+                Event::End(element) if element.local_name().as_ref() == element_name => return Ok(params),
+                Event::Eof => return Err(self.unexpected_eof(element_name)),
+                _ => ()
+            }
+        }
+    }
+
+    /// Reads a `<DBSequence>`, i.e. a protein.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the DBSequence
+    /// * `is_empty` - True if the element has no content
+    ///
+    fn read_db_sequence(&mut self, element: &BytesStart, is_empty: bool) -> Result<()> {
+        let id = self.internal_reader.get_required_attribute(element, "id")?;
+        let accession = self.internal_reader.get_required_attribute(element, "accession")?;
+        let params = match is_empty {
+            true => Vec::new(),
+            false => self.read_params(b"DBSequence")?
+        };
+        let description = params.into_iter()
+            .find(|param| param.accession.as_deref() == Some(PROTEIN_DESCRIPTION))
+            .and_then(|param| param.value);
+        self.db_sequences.insert(id, (accession, description));
+        Ok(())
+    }
+
+    /// Reads a `<Modification>` of a peptide. The first parameter names the modification.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the modification
+    /// * `is_empty` - True if the element has no content
+    ///
+    fn read_modification(&mut self, element: &BytesStart, is_empty: bool) -> Result<Modification> {
+        let position = self.internal_reader.get_position();
+        let location = match self.internal_reader.get_attribute(element, "location")? {
+            Some(location) => Some(parse_int(&location, &position)?),
+            None => None
+        };
+        let residues: Vec<char> = self.internal_reader.get_attribute(element, "residues")?
+            .unwrap_or_default()
+            .chars()
+            .filter(|residue| !residue.is_whitespace() && *residue != '.')
+            .collect();
+        let monoisotopic_mass_delta = match self.internal_reader.get_attribute(element, "monoisotopicMassDelta")? {
+            Some(mass_delta) => Some(parse_float(&mass_delta, || position.clone())?),
+            None => None
+        };
+        let param = match is_empty {
+            true => None,
+            false => self.read_params(b"Modification")?.into_iter().next()
+        };
+        let (accession, name) = match param {
+            Some(param) => (param.accession, Some(param.name)),
+            None => (None, None)
+        };
+        Ok(Modification::new(location, residues, monoisotopic_mass_delta, accession, name))
+    }
+
+    /// Reads a `<Peptide>` with its sequence and modifications.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the peptide
+    ///
+    fn read_peptide(&mut self, element: &BytesStart) -> Result<()> {
+        let id = self.internal_reader.get_required_attribute(element, "id")?;
+        let mut sequence = String::new();
+        let mut modifications: Vec<Modification> = Vec::new();
+        loop {
+            match self.internal_reader.read_event()? {
+                Event::Start(element) if element.local_name().as_ref() == b"PeptideSequence" => {
+                    sequence = self.internal_reader.read_text()?.trim().to_string();
+                },
+                Event::Start(element) if element.local_name().as_ref() == b"Modification" => {
+                    modifications.push(self.read_modification(&element, false)?);
+                },
+                Event::Empty(element) if element.local_name().as_ref() == b"Modification" => {
+                    modifications.push(self.read_modification(&element, true)?);
+                },
+                Event::End(element) if element.local_name().as_ref() == b"Peptide" => break,
+                Event::Eof => return Err(self.unexpected_eof(b"Peptide")),
+                _ => ()
+            }
+        }
+        self.peptides.insert(id, Peptide {
+            sequence,
+            modifications
+        });
+        Ok(())
+    }
+
+    /// Reads a `<PeptideEvidence>` and resolves the referenced protein.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the peptide evidence
+    /// * `is_empty` - True if the element has no content
+    ///
+    fn read_peptide_evidence(&mut self, element: &BytesStart, is_empty: bool) -> Result<()> {
+        let position = self.internal_reader.get_position();
+        let id = self.internal_reader.get_required_attribute(element, "id")?;
+        let peptide_ref = self.internal_reader.get_required_attribute(element, "peptide_ref")?;
+        let db_sequence_ref = self.internal_reader.get_required_attribute(element, "dBSequence_ref")?;
+        let (accession, description) = self.db_sequences.get(&db_sequence_ref)
+            .cloned()
+            .ok_or_else(|| self.unknown_reference(&db_sequence_ref))?;
+        let mut location: [Option<usize>; 2] = [None, None];
+        for (idx, attribute) in ["start", "end"].iter().enumerate() {
+            if let Some(value) = self.internal_reader.get_attribute(element, attribute)? {
+                location[idx] = Some(parse_int(&value, &position)?);
+            }
+        }
+        let pre = self.internal_reader.get_attribute(element, "pre")?.and_then(|pre| pre.chars().next());
+        let post = self.internal_reader.get_attribute(element, "post")?.and_then(|post| post.chars().next());
+        let is_decoy = matches!(self.internal_reader.get_attribute(element, "isDecoy")?.as_deref(), Some("true" | "1"));
+        if !is_empty {
+            self.read_params(b"PeptideEvidence")?;
+        }
+        self.peptide_evidences.insert(id, PeptideEvidence {
+            peptide_ref,
+            protein: ProteinEvidence::new(accession, description)
+                .with_location(location[0], location[1])
+                .with_flanking_residues(pre, post)
+                .with_decoy(is_decoy)
+        });
+        Ok(())
+    }
+
+    /// Reads a `<SpectrumIdentificationItem>` and resolves the referenced peptide and peptide evidences.
+    /// Numeric PSM score terms and score-like user parameters are kept as scores, all other parameters as params.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the spectrum identification item
+    /// * `spectrum_id` - ID of the spectrum of the enclosing result
+    /// * `is_empty` - True if the element has no content
+    ///
+    fn read_spectrum_identification_item(&mut self, element: &BytesStart, spectrum_id: &str,
+        is_empty: bool) -> Result<Psm> {
+        let position = self.internal_reader.get_position();
+        let rank: u32 = parse_int(&self.internal_reader.get_required_attribute(element, "rank")?, &position)?;
+        let charge: i32 = parse_int(&self.internal_reader.get_required_attribute(element, "chargeState")?, &position)?;
+        let experimental_mz = self.internal_reader.get_required_attribute(element, "experimentalMassToCharge")?;
+        let experimental_mz: f64 = parse_float(&experimental_mz, || position.clone())?;
+        let calculated_mz = match self.internal_reader.get_attribute(element, "calculatedMassToCharge")? {
+            Some(calculated_mz) => Some(parse_float(&calculated_mz, || position.clone())?),
+            None => None
+        };
+        let pass_threshold = matches!(
+            self.internal_reader.get_attribute(element, "passThreshold")?.as_deref(), Some("true" | "1")
+        );
+        let peptide_ref = self.internal_reader.get_required_attribute(element, "peptide_ref")?;
+        let peptide = self.peptides.get(&peptide_ref).ok_or_else(|| self.unknown_reference(&peptide_ref))?;
+        let psm = Psm::new(spectrum_id.to_string(), rank, charge, experimental_mz, peptide.sequence.clone())
+            .with_modifications(peptide.modifications.clone())
+            .with_calculated_mz(calculated_mz)
+            .with_pass_threshold(pass_threshold);
+
+        let mut proteins: Vec<ProteinEvidence> = Vec::new();
+        let mut scores: Vec<Score> = Vec::new();
+        let mut params: Vec<Param> = Vec::new();
+        if !is_empty {
+            loop {
+                match self.internal_reader.read_event()? {
+                    Event::Start(element) | Event::Empty(element)
+                        if element.local_name().as_ref() == b"PeptideEvidenceRef" => {
+                        let evidence_ref = self.internal_reader.get_required_attribute(&element, "peptideEvidence_ref")?;
+                        let evidence = self.peptide_evidences.get(&evidence_ref)
+                            .ok_or_else(|| self.unknown_reference(&evidence_ref))?;
+                        if evidence.peptide_ref != peptide_ref {
+                            return Err(self.internal_reader.create_error(format!(
+                                "peptide evidence `{}` belongs to peptide `{}`, not `{}`",
+                                evidence_ref, evidence.peptide_ref, peptide_ref
+                            )));
+                        }
+                        proteins.push(evidence.protein.clone());
+                    },
+                    Event::Start(element) | Event::Empty(element) if is_param(&element) => {
+                        let param = self.read_param(&element)?;
+                        let score = match is_score(&param) {
+                            true => param.value.as_deref().and_then(|value| fast_float::parse(value).ok()),
+                            false => None
+                        };
+                        match score {
+                            Some(value) => scores.push(Score::new(param.accession, param.name, value)),
+                            None => params.push(Param::new(param.accession, param.name, param.value))
+                        }
+                    },
+                    Event::End(element) if element.local_name().as_ref() == b"SpectrumIdentificationItem" => break,
+                    Event::Eof => return Err(self.unexpected_eof(b"SpectrumIdentificationItem")),
+                    _ => ()
+                }
+            }
+        }
+        Ok(psm.with_proteins(proteins).with_scores(scores).with_params(params))
+    }
+
+    /// Reads a `<SpectrumIdentificationResult>` and queues a PSM for each of its items.
+    /// The spectrum title and retention time of the result are added to the PSMs.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the spectrum identification result
+    ///
+    fn read_spectrum_identification_result(&mut self, element: &BytesStart) -> Result<()> {
+        let position = self.internal_reader.get_position();
+        let spectrum_id = self.internal_reader.get_required_attribute(element, "spectrumID")?;
+        let spectra_data_location = self.internal_reader.get_attribute(element, "spectraData_ref")?
+            .and_then(|spectra_data_ref| self.spectra_data.get(&spectra_data_ref).cloned());
+        let mut psms: Vec<Psm> = Vec::new();
+        let mut spectrum_title: Option<String> = None;
+        let mut retention_time: Option<f64> = None;
+        loop {
+            match self.internal_reader.read_event()? {
+                Event::Start(element) if element.local_name().as_ref() == b"SpectrumIdentificationItem" => {
+                    psms.push(self.read_spectrum_identification_item(&element, &spectrum_id, false)?);
+                },
+                Event::Empty(element) if element.local_name().as_ref() == b"SpectrumIdentificationItem" => {
+                    psms.push(self.read_spectrum_identification_item(&element, &spectrum_id, true)?);
+                },
+                Event::Start(element) | Event::Empty(element) if is_param(&element) => {
+                    let param = self.read_param(&element)?;
+                    match param.accession.as_deref() {
+                        Some(SPECTRUM_TITLE) => spectrum_title = param.value,
+                        Some(RETENTION_TIME | SCAN_START_TIME) => {
+                            let value: f64 = parse_float(param.value.as_deref().unwrap_or_default(), || position.clone())?;
+                            retention_time = match param.unit_accession.as_deref() {
+                                Some(UNIT_MINUTE) => Some(value * 60.0),
+                                _ => Some(value)
+                            };
+                        },
+                        _ => ()
+                    }
+                },
+                Event::End(element) if element.local_name().as_ref() == b"SpectrumIdentificationResult" => break,
+                Event::Eof => return Err(self.unexpected_eof(b"SpectrumIdentificationResult")),
+                _ => ()
+            }
+        }
+        self.pending_psms.extend(psms.into_iter().map(|psm| psm
+            .with_spectrum_title(spectrum_title.clone())
+            .with_spectra_data_location(spectra_data_location.clone())
+            .with_retention_time(retention_time)
+        ));
+        Ok(())
+    }
+
+    /// Creates an error for a reference to an element, which was not read before
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - ID of the referenced element
+    ///
+    fn unknown_reference(&self, reference: &str) -> Error {
+        self.internal_reader.create_error(format!("unknown reference `{}`", reference))
+    }
+
+    /// Creates an error for the end of the file within an element
+    ///
+    /// # Arguments
+    ///
+    /// * `element_name` - Name of the element
+    ///
+    fn unexpected_eof(&self, element_name: &[u8]) -> Error {
+        self.internal_reader.create_error(format!(
+            "unexpected end of file within `<{}>`",
+            String::from_utf8_lossy(element_name)
+        ))
+    }
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
+    type Item = Psm;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        loop {
+            if let Some(psm) = self.pending_psms.pop_front() {
+                return Ok(Some(psm));
+            }
+            let (element, is_empty) = match self.internal_reader.read_event()? {
+                Event::Start(element) => (element, false),
+                Event::Empty(element) => (element, true),
+                Event::Eof => return Ok(None),
+                _ => continue
+            };
+            match element.local_name().as_ref() {
+                b"DBSequence" => self.read_db_sequence(&element, is_empty)?,
+                b"Peptide" if !is_empty => self.read_peptide(&element)?,
+                b"PeptideEvidence" => self.read_peptide_evidence(&element, is_empty)?,
+                b"SpectraData" => {
+                    let id = self.internal_reader.get_required_attribute(&element, "id")?;
+                    let location = self.internal_reader.get_required_attribute(&element, "location")?;
+                    self.spectra_data.insert(id, location);
+                },
+                b"SpectrumIdentificationResult" if !is_empty => self.read_spectrum_identification_result(&element)?,
+                // Protein inference follows the PSMs
+                b"ProteinDetectionList" => return Ok(None),
+                _ => ()
+            }
+        }
+    }
+}
+
+/// Checks if the element is a `<cvParam>` or `<userParam>`
+///
+/// # Arguments
+///
+/// * `element` - Element
+///
+fn is_param(element: &BytesStart) -> bool {
+    matches!(element.local_name().as_ref(), b"cvParam" | b"userParam")
+}
+
+/// Checks if the parameter is a PSM score, i.e. a PSM score term or a user parameter named like a score,
+/// e.g. `Andromeda:score`
+///
+/// # Arguments
+///
+/// * `param` - Parameter
+///
+fn is_score(param: &RawParam) -> bool {
+    match param.accession.as_deref() {
+        Some(accession) => PSM_SCORES.contains(&accession),
+        None => {
+            let name = param.name.to_lowercase();
+            SCORE_NAME_PARTS.iter().any(|part| name.contains(part))
+        }
+    }
+}
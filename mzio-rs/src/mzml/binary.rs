@@ -0,0 +1,214 @@
+// internal imports
+use crate::mzml::cv_param::{accession, CvParam};
+use crate::mzml::numpress;
+use crate::xml::{decode_base64, encode_base64, zlib_compress, zlib_decompress};
+
+/// Numeric type of a binary data array
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BinaryDataType {
+    Float32,
+    #[default]
+    Float64,
+    Int32,
+    Int64
+}
+
+impl BinaryDataType {
+    /// Returns the data type given by the CV parameters, None if it is missing
+    ///
+    /// # Arguments
+    ///
+    /// * `cv_params` - CV parameters of the binary data array
+    ///
+    pub fn from_cv_params(cv_params: &[CvParam]) -> Option<Self> {
+        cv_params.iter().find_map(|cv_param| match cv_param.get_accession() {
+            accession::FLOAT_32 => Some(Self::Float32),
+            accession::FLOAT_64 => Some(Self::Float64),
+            accession::INT_32 => Some(Self::Int32),
+            accession::INT_64 => Some(Self::Int64),
+            _ => None
+        })
+    }
+
+    /// Returns accession and name of the CV term
+    ///
+    pub fn get_cv_term(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Float32 => (accession::FLOAT_32, "32-bit float"),
+            Self::Float64 => (accession::FLOAT_64, "64-bit float"),
+            Self::Int32 => (accession::INT_32, "32-bit integer"),
+            Self::Int64 => (accession::INT_64, "64-bit integer")
+        }
+    }
+
+    /// Returns the number of bytes per value
+    ///
+    pub fn get_size(&self) -> usize {
+        match self {
+            Self::Float32 | Self::Int32 => 4,
+            Self::Float64 | Self::Int64 => 8
+        }
+    }
+}
+
+/// MS-Numpress compression scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Numpress {
+    /// Linear prediction, for m/z and retention times
+    Linear,
+    /// Positive integer, for ion counts
+    Pic,
+    /// Short logged float, for intensities
+    Slof
+}
+
+/// Compression of a binary data array.
+/// MS-Numpress may be followed by zlib, which is given either by a combined term
+/// or by two separate terms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BinaryCompression {
+    numpress: Option<Numpress>,
+    is_zlib: bool
+}
+
+impl BinaryCompression {
+    /// Creates a new compression
+    ///
+    /// # Arguments
+    ///
+    /// * `numpress` - MS-Numpress scheme, None for plain binary data
+    /// * `is_zlib` - True if zlib is applied (after MS-Numpress)
+    ///
+    pub fn new(numpress: Option<Numpress>, is_zlib: bool) -> Self {
+        Self {
+            numpress,
+            is_zlib
+        }
+    }
+
+    /// Returns the compression given by the CV parameters, no compression if none is given
+    ///
+    /// # Arguments
+    ///
+    /// * `cv_params` - CV parameters of the binary data array
+    ///
+    pub fn from_cv_params(cv_params: &[CvParam]) -> Self {
+        let mut compression = Self::default();
+        for cv_param in cv_params {
+            let (numpress, is_zlib) = match cv_param.get_accession() {
+                accession::ZLIB_COMPRESSION => (None, true),
+                accession::NUMPRESS_LINEAR => (Some(Numpress::Linear), false),
+                accession::NUMPRESS_PIC => (Some(Numpress::Pic), false),
+                accession::NUMPRESS_SLOF => (Some(Numpress::Slof), false),
+                accession::NUMPRESS_LINEAR_ZLIB => (Some(Numpress::Linear), true),
+                accession::NUMPRESS_PIC_ZLIB => (Some(Numpress::Pic), true),
+                accession::NUMPRESS_SLOF_ZLIB => (Some(Numpress::Slof), true),
+                _ => continue
+            };
+            compression.numpress = compression.numpress.or(numpress);
+            compression.is_zlib |= is_zlib;
+        }
+        compression
+    }
+
+    /// Returns the MS-Numpress scheme, None for plain binary data
+    ///
+    pub fn get_numpress(&self) -> Option<Numpress> {
+        self.numpress
+    }
+
+    /// Returns true if zlib is applied
+    ///
+    pub fn is_zlib(&self) -> bool {
+        self.is_zlib
+    }
+
+    /// Returns accession and name of the CV term
+    ///
+    pub fn get_cv_term(&self) -> (&'static str, &'static str) {
+        match (self.numpress, self.is_zlib) {
+            (None, false) => (accession::NO_COMPRESSION, "no compression"),
+            (None, true) => (accession::ZLIB_COMPRESSION, "zlib compression"),
+            (Some(Numpress::Linear), false) => (accession::NUMPRESS_LINEAR, "MS-Numpress linear prediction compression"),
+            (Some(Numpress::Pic), false) => (accession::NUMPRESS_PIC, "MS-Numpress positive integer compression"),
+            (Some(Numpress::Slof), false) => (accession::NUMPRESS_SLOF, "MS-Numpress short logged float compression"),
+            (Some(Numpress::Linear), true) => (
+                accession::NUMPRESS_LINEAR_ZLIB,
+                "MS-Numpress linear prediction compression followed by zlib compression"
+            ),
+            (Some(Numpress::Pic), true) => (
+                accession::NUMPRESS_PIC_ZLIB,
+                "MS-Numpress positive integer compression followed by zlib compression"
+            ),
+            (Some(Numpress::Slof), true) => (
+                accession::NUMPRESS_SLOF_ZLIB,
+                "MS-Numpress short logged float compression followed by zlib compression"
+            )
+        }
+    }
+}
+
+/// Decodes the base64 encoded content of a `<binary>` element,
+/// returning a message describing the problem on failure.
+///
+/// # Arguments
+///
+/// * `encoded` - Base64 encoded content
+/// * `cv_params` - CV parameters of the binary data array, giving data type and compression
+///
+pub fn decode_binary(encoded: &str, cv_params: &[CvParam]) -> std::result::Result<Vec<f64>, String> {
+    let mut data = decode_base64(encoded)?;
+    let compression = BinaryCompression::from_cv_params(cv_params);
+    if compression.is_zlib() {
+        data = zlib_decompress(&data)?;
+    }
+    if let Some(numpress) = compression.get_numpress() {
+        let values = match numpress {
+            Numpress::Linear => numpress::decode_linear(&data),
+            Numpress::Pic => numpress::decode_pic(&data),
+            Numpress::Slof => numpress::decode_slof(&data)
+        };
+        return values.ok_or_else(|| format!("corrupt MS-Numpress {:?} data", numpress));
+    }
+    let data_type = BinaryDataType::from_cv_params(cv_params)
+        .ok_or_else(|| "data type is missing".to_string())?;
+    if data.len() % data_type.get_size() != 0 {
+        return Err(format!("{} bytes cannot be {:?} values", data.len(), data_type));
+    }
+    let chunks = data.chunks_exact(data_type.get_size());
+    // The size of the chunks is checked above, so `try_into()` cannot fail
+    let values = match data_type {
+        BinaryDataType::Float32 => chunks.map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()) as f64).collect(),
+        BinaryDataType::Float64 => chunks.map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap())).collect(),
+        BinaryDataType::Int32 => chunks.map(|chunk| i32::from_le_bytes(chunk.try_into().unwrap()) as f64).collect(),
+        BinaryDataType::Int64 => chunks.map(|chunk| i64::from_le_bytes(chunk.try_into().unwrap()) as f64).collect()
+    };
+    Ok(values)
+}
+
+/// Encodes the values as content of a `<binary>` element.
+///
+/// # Arguments
+///
+/// * `values` - Values
+/// * `data_type` - Numeric type the values are stored as, integer types are rounded
+/// * `is_zlib` - True if the binary data is zlib compressed
+///
+pub fn encode_binary<T>(values: &[T], data_type: BinaryDataType, is_zlib: bool) -> String
+where
+    T: Copy + Into<f64>
+{
+    let mut data: Vec<u8> = Vec::with_capacity(values.len() * data_type.get_size());
+    for value in values.iter().map(|value| (*value).into()) {
+        match data_type {
+            BinaryDataType::Float32 => data.extend((value as f32).to_le_bytes()),
+            BinaryDataType::Float64 => data.extend(value.to_le_bytes()),
+            BinaryDataType::Int32 => data.extend((value.round() as i32).to_le_bytes()),
+            BinaryDataType::Int64 => data.extend((value.round() as i64).to_le_bytes())
+        }
+    }
+    if is_zlib {
+        data = zlib_compress(&data);
+    }
+    encode_base64(&data)
+}
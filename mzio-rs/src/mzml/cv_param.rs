@@ -0,0 +1,195 @@
+/// Accessions of the PSI-MS controlled vocabulary terms used by the mzML reader and writer
+pub mod accession {
+    /// ms level
+    pub const MS_LEVEL: &str = "MS:1000511";
+    /// MS1 spectrum
+    pub const MS1_SPECTRUM: &str = "MS:1000579";
+    /// MSn spectrum
+    pub const MSN_SPECTRUM: &str = "MS:1000580";
+    /// centroid spectrum
+    pub const CENTROID_SPECTRUM: &str = "MS:1000127";
+    /// profile spectrum
+    pub const PROFILE_SPECTRUM: &str = "MS:1000128";
+    /// no combination
+    pub const NO_COMBINATION: &str = "MS:1000795";
+    /// scan start time
+    pub const SCAN_START_TIME: &str = "MS:1000016";
+    /// ion injection time
+    pub const ION_INJECTION_TIME: &str = "MS:1000927";
+    /// selected ion m/z
+    pub const SELECTED_ION_MZ: &str = "MS:1000744";
+    /// peak intensity
+    pub const PEAK_INTENSITY: &str = "MS:1000042";
+    /// charge state
+    pub const CHARGE_STATE: &str = "MS:1000041";
+    /// possible charge state
+    pub const POSSIBLE_CHARGE_STATE: &str = "MS:1000633";
+    /// isolation window target m/z
+    pub const ISOLATION_WINDOW_TARGET_MZ: &str = "MS:1000827";
+    /// m/z array
+    pub const MZ_ARRAY: &str = "MS:1000514";
+    /// intensity array
+    pub const INTENSITY_ARRAY: &str = "MS:1000515";
+    /// 32-bit integer
+    pub const INT_32: &str = "MS:1000519";
+    /// 32-bit float
+    pub const FLOAT_32: &str = "MS:1000521";
+    /// 64-bit integer
+    pub const INT_64: &str = "MS:1000522";
+    /// 64-bit float
+    pub const FLOAT_64: &str = "MS:1000523";
+    /// no compression
+    pub const NO_COMPRESSION: &str = "MS:1000576";
+    /// zlib compression
+    pub const ZLIB_COMPRESSION: &str = "MS:1000574";
+    /// MS-Numpress linear prediction compression
+    pub const NUMPRESS_LINEAR: &str = "MS:1002312";
+    /// MS-Numpress positive integer compression
+    pub const NUMPRESS_PIC: &str = "MS:1002313";
+    /// MS-Numpress short logged float compression
+    pub const NUMPRESS_SLOF: &str = "MS:1002314";
+    /// MS-Numpress linear prediction compression followed by zlib compression
+    pub const NUMPRESS_LINEAR_ZLIB: &str = "MS:1002746";
+    /// MS-Numpress positive integer compression followed by zlib compression
+    pub const NUMPRESS_PIC_ZLIB: &str = "MS:1002747";
+    /// MS-Numpress short logged float compression followed by zlib compression
+    pub const NUMPRESS_SLOF_ZLIB: &str = "MS:1002748";
+    /// instrument model
+    pub const INSTRUMENT_MODEL: &str = "MS:1000031";
+    /// custom unreleased software tool
+    pub const CUSTOM_SOFTWARE: &str = "MS:1000799";
+    /// Conversion to mzML
+    pub const CONVERSION_TO_MZML: &str = "MS:1000544";
+    /// second
+    pub const UNIT_SECOND: &str = "UO:0000010";
+    /// minute
+    pub const UNIT_MINUTE: &str = "UO:0000031";
+    /// millisecond
+    pub const UNIT_MILLISECOND: &str = "UO:0000028";
+    /// m/z
+    pub const UNIT_MZ: &str = "MS:1000040";
+    /// number of detector counts
+    pub const UNIT_DETECTOR_COUNTS: &str = "MS:1000131";
+}
+
+/// Controlled vocabulary parameter (`<cvParam>`), e.g.
+/// `<cvParam cvRef="MS" accession="MS:1000511" name="ms level" value="2"/>`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CvParam {
+    cv_ref: String,
+    accession: String,
+    name: String,
+    value: String,
+    unit_accession: Option<String>,
+    unit_name: Option<String>
+}
+
+impl CvParam {
+    /// Creates a new CV parameter
+    ///
+    /// # Arguments
+    ///
+    /// * `cv_ref` - Reference to the controlled vocabulary, e.g. `MS`
+    /// * `accession` - Accession, e.g. `MS:1000511`
+    /// * `name` - Name, e.g. `ms level`
+    /// * `value` - Value, empty for flags
+    ///
+    pub fn new(cv_ref: String, accession: String, name: String, value: String) -> Self {
+        Self {
+            cv_ref,
+            accession,
+            name,
+            value,
+            unit_accession: None,
+            unit_name: None
+        }
+    }
+
+    /// Sets the value
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value
+    ///
+    pub fn with_value(mut self, value: String) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the unit
+    ///
+    /// # Arguments
+    ///
+    /// * `unit_accession` - Accession of the unit, e.g. `UO:0000010`
+    /// * `unit_name` - Name of the unit, e.g. `second`
+    ///
+    pub fn with_unit(mut self, unit_accession: String, unit_name: String) -> Self {
+        self.unit_accession = Some(unit_accession);
+        self.unit_name = Some(unit_name);
+        self
+    }
+
+    /// Returns the reference to the controlled vocabulary
+    ///
+    pub fn get_cv_ref(&self) -> &str {
+        &self.cv_ref
+    }
+
+    /// Returns the accession
+    ///
+    pub fn get_accession(&self) -> &str {
+        &self.accession
+    }
+
+    /// Returns the name
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value, empty for flags
+    ///
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the value as float, None if it is not a number
+    ///
+    pub fn get_value_as_f64(&self) -> Option<f64> {
+        fast_float::parse(&self.value).ok()
+    }
+
+    /// Returns the accession of the unit
+    ///
+    pub fn get_unit_accession(&self) -> Option<&str> {
+        self.unit_accession.as_deref()
+    }
+
+    /// Returns the name of the unit
+    ///
+    pub fn get_unit_name(&self) -> Option<&str> {
+        self.unit_name.as_deref()
+    }
+}
+
+/// Returns the first parameter with the given accession
+///
+/// # Arguments
+///
+/// * `cv_params` - CV parameters
+/// * `accession` - Accession
+///
+pub fn find_cv_param<'a>(cv_params: &'a [CvParam], accession: &str) -> Option<&'a CvParam> {
+    cv_params.iter().find(|cv_param| cv_param.get_accession() == accession)
+}
+
+/// Creates a CV parameter of the PSI-MS controlled vocabulary without value
+///
+/// # Arguments
+///
+/// * `accession` - Accession
+/// * `name` - Name
+///
+pub(crate) fn ms_cv_param(accession: &str, name: &str) -> CvParam {
+    CvParam::new("MS".to_string(), accession.to_string(), name.to_string(), String::new())
+}
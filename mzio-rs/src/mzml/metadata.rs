@@ -0,0 +1,145 @@
+// internal imports
+use crate::mzml::cv_param::{accession, ms_cv_param, CvParam};
+
+/// Run metadata written by the mzML writer, i.e. the minimal required
+/// file description, software, instrument configuration and data processing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    run_id: String,
+    file_content: Vec<CvParam>,
+    software_id: String,
+    software_version: String,
+    software: Vec<CvParam>,
+    instrument: Vec<CvParam>,
+    data_processing: Vec<CvParam>
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Self {
+            run_id: "run".to_string(),
+            file_content: vec![ms_cv_param(accession::MSN_SPECTRUM, "MSn spectrum")],
+            software_id: "mzio".to_string(),
+            software_version: env!("CARGO_PKG_VERSION").to_string(),
+            software: vec![
+                CvParam::new(
+                    "MS".to_string(),
+                    accession::CUSTOM_SOFTWARE.to_string(),
+                    "custom unreleased software tool".to_string(),
+                    "mzio".to_string()
+                )
+            ],
+            instrument: vec![ms_cv_param(accession::INSTRUMENT_MODEL, "instrument model")],
+            data_processing: vec![ms_cv_param(accession::CONVERSION_TO_MZML, "Conversion to mzML")]
+        }
+    }
+}
+
+impl Metadata {
+    /// Creates the default metadata, describing an unknown instrument and the conversion by mzio
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of the run
+    ///
+    /// # Arguments
+    ///
+    /// * `run_id` - Run ID, e.g. the raw file name
+    ///
+    pub fn with_run_id(mut self, run_id: String) -> Self {
+        self.run_id = run_id;
+        self
+    }
+
+    /// Sets the CV parameters describing the file content, e.g. `MS:1000579` (MS1 spectrum)
+    ///
+    /// # Arguments
+    ///
+    /// * `file_content` - CV parameters
+    ///
+    pub fn with_file_content(mut self, file_content: Vec<CvParam>) -> Self {
+        self.file_content = file_content;
+        self
+    }
+
+    /// Sets the software which processed the data
+    ///
+    /// # Arguments
+    ///
+    /// * `software_id` - ID of the software
+    /// * `software_version` - Version of the software
+    /// * `software` - CV parameters describing the software
+    ///
+    pub fn with_software(mut self, software_id: String, software_version: String, software: Vec<CvParam>) -> Self {
+        self.software_id = software_id;
+        self.software_version = software_version;
+        self.software = software;
+        self
+    }
+
+    /// Sets the CV parameters describing the instrument, e.g. `MS:1001742` (LTQ Orbitrap Velos)
+    ///
+    /// # Arguments
+    ///
+    /// * `instrument` - CV parameters
+    ///
+    pub fn with_instrument(mut self, instrument: Vec<CvParam>) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    /// Sets the CV parameters describing the data processing, e.g. `MS:1000035` (peak picking)
+    ///
+    /// # Arguments
+    ///
+    /// * `data_processing` - CV parameters
+    ///
+    pub fn with_data_processing(mut self, data_processing: Vec<CvParam>) -> Self {
+        self.data_processing = data_processing;
+        self
+    }
+
+    /// Returns the ID of the run
+    ///
+    pub fn get_run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Returns the CV parameters describing the file content
+    ///
+    pub fn get_file_content(&self) -> &Vec<CvParam> {
+        &self.file_content
+    }
+
+    /// Returns the ID of the software
+    ///
+    pub fn get_software_id(&self) -> &str {
+        &self.software_id
+    }
+
+    /// Returns the version of the software
+    ///
+    pub fn get_software_version(&self) -> &str {
+        &self.software_version
+    }
+
+    /// Returns the CV parameters describing the software
+    ///
+    pub fn get_software(&self) -> &Vec<CvParam> {
+        &self.software
+    }
+
+    /// Returns the CV parameters describing the instrument
+    ///
+    pub fn get_instrument(&self) -> &Vec<CvParam> {
+        &self.instrument
+    }
+
+    /// Returns the CV parameters describing the data processing
+    ///
+    pub fn get_data_processing(&self) -> &Vec<CvParam> {
+        &self.data_processing
+    }
+}
@@ -0,0 +1,260 @@
+//! Reading and writing of mzML files (HUPO-PSI, version 1.1).
+//! The reader supports indexed and non-indexed files with zlib and/or MS-Numpress compressed
+//! binary data arrays, the writer creates indexed files with SHA-1 checksum.
+//! MGF conversion maps the native ID to the title and each selected ion to a precursor.
+
+pub mod binary;
+pub mod cv_param;
+pub mod metadata;
+mod numpress;
+pub mod reader;
+pub mod spectrum;
+pub mod writer;
+
+#[cfg(test)]
+mod test {
+    // std imports
+    use std::path::Path;
+
+    // 3rd party imports
+    use fallible_iterator::FallibleIterator;
+    use sha1::Digest;
+
+    // internal imports
+    use super::*;
+    use crate::error::Error;
+    use crate::mgf::reader::Reader as MgfReader;
+    use crate::mgf::spectrum::Spectrum as MgfSpectrum;
+    use crate::mgf::writer::Writer as MgfWriter;
+
+    const MZML_FILE_PATH_STR: &str = "../test_files/mzml/small.mzML";
+    const MGF_FILE_PATH_STR: &str = "../test_files/mgf/Velos005137.mgf";
+
+    /// Returns true if the values are equal within the given relative tolerance
+    fn is_close(values: &[f64], expected: &[f64], tolerance: f64) -> bool {
+        values.len() == expected.len()
+            && values.iter().zip(expected).all(|(value, expected)| (value - expected).abs() <= expected.abs() * tolerance + 1e-9)
+    }
+
+    #[test]
+    /// Reads an indexed mzML file with zlib and MS-Numpress compressed binary data arrays.
+    fn test_reading() {
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::new(Path::new(MZML_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(spectra.len(), 3);
+
+        // MS1, 64-bit zlib compressed m/z, uncompressed 32-bit intensities
+        let ms1 = &spectra[0];
+        assert_eq!(ms1.get_id(), "controllerType=0 controllerNumber=1 scan=1");
+        assert_eq!(ms1.get_index(), 0);
+        assert_eq!(ms1.get_ms_level(), Some(1));
+        assert!(ms1.is_centroided());
+        assert_eq!(ms1.get_retention_time(), Some(30.0));
+        assert_eq!(ms1.get_ion_injection_time(), Some(12.5));
+        assert_eq!(ms1.get_scan_number(), Some(1));
+        assert!(ms1.get_precursors().is_empty());
+        assert_eq!(ms1.get_mz_list(), &vec![400.123456789, 500.5, 600.25, 700.0]);
+        assert_eq!(ms1.get_intensity_list(), &vec![1000.5, 2000.0, 150.25, 30.0]);
+
+        // MS2, numpress linear m/z, numpress positive integer + zlib intensities
+        let ms2 = &spectra[1];
+        assert_eq!(ms2.get_ms_level(), Some(2));
+        assert_eq!(ms2.get_index(), 1);
+        assert_eq!(ms2.get_ion_injection_time(), Some(35.0));
+        let precursor = &ms2.get_precursors()[0];
+        assert_eq!(precursor.get_spectrum_ref(), Some("controllerType=0 controllerNumber=1 scan=1"));
+        assert_eq!(precursor.get_isolation_window_target_mz(), Some(445.12));
+        assert_eq!(precursor.get_activation().len(), 2);
+        let selected_ion = &precursor.get_selected_ions()[0];
+        assert_eq!(selected_ion.get_mz(), Some(445.12));
+        assert_eq!(selected_ion.get_charges(), vec![2]);
+        assert_eq!(selected_ion.get_intensity(), Some(120053.0));
+        assert!(is_close(ms2.get_mz_list(), &[110.0712, 147.1128, 175.119, 262.151, 333.1881], 1e-6));
+        assert_eq!(ms2.get_intensity_list(), &vec![500.0, 1200.0, 8000.0, 350.0, 0.0]);
+
+        // MS2, numpress short logged float intensities, possible charge states
+        let ms2 = &spectra[2];
+        assert_eq!(ms2.get_precursors()[0].get_selected_ions()[0].get_charges(), vec![2, 3]);
+        assert_eq!(ms2.get_precursors()[0].get_selected_ions()[0].get_intensity(), None);
+        let intensities: Vec<f64> = ms2.get_intensity_list().iter().map(|intensity| *intensity as f64).collect();
+        assert!(is_close(&intensities, &[10.0, 100.0, 1000.0], 1e-3));
+    }
+
+    #[test]
+    /// Reads a non-indexed mzML with a referenceable param group and converts it into a MGF spectrum.
+    fn test_conversion_to_mgf() {
+        let mzml_content = r#"<?xml version="1.0" encoding="utf-8"?>
+<mzML xmlns="http://psi.hupo.org/ms/mzml" version="1.1.0">
+  <referenceableParamGroupList count="1">
+    <referenceableParamGroup id="ms2">
+      <cvParam cvRef="MS" accession="MS:1000511" name="ms level" value="2"/>
+    </referenceableParamGroup>
+  </referenceableParamGroupList>
+  <run id="run">
+    <spectrumList count="1">
+      <spectrum index="0" id="index=7" defaultArrayLength="2">
+        <referenceableParamGroupRef ref="ms2"/>
+        <scanList count="1">
+          <scan>
+            <cvParam cvRef="MS" accession="MS:1000016" name="scan start time" value="12.5" unitCvRef="UO" unitAccession="UO:0000010" unitName="second"/>
+          </scan>
+        </scanList>
+        <precursorList count="1">
+          <precursor>
+            <selectedIonList count="1">
+              <selectedIon>
+                <cvParam cvRef="MS" accession="MS:1000744" name="selected ion m/z" value="500.25"/>
+                <cvParam cvRef="MS" accession="MS:1000041" name="charge state" value="3"/>
+              </selectedIon>
+            </selectedIonList>
+          </precursor>
+        </precursorList>
+        <binaryDataArrayList count="2">
+          <binaryDataArray encodedLength="24">
+            <cvParam cvRef="MS" accession="MS:1000523" name="64-bit float" value=""/>
+            <cvParam cvRef="MS" accession="MS:1000576" name="no compression" value=""/>
+            <cvParam cvRef="MS" accession="MS:1000514" name="m/z array" value=""/>
+            <binary>AAAAAAAAWUAAAAAAAABpQA==</binary>
+          </binaryDataArray>
+          <binaryDataArray encodedLength="12">
+            <cvParam cvRef="MS" accession="MS:1000521" name="32-bit float" value=""/>
+            <cvParam cvRef="MS" accession="MS:1000515" name="intensity array" value=""/>
+            <binary>AAAgQQAAyEI=</binary>
+          </binaryDataArray>
+        </binaryDataArrayList>
+      </spectrum>
+    </spectrumList>
+  </run>
+</mzML>
+"#;
+        let spectrum = reader::Reader::from_reader(mzml_content.as_bytes()).next().unwrap().unwrap();
+        assert_eq!(spectrum.get_ms_level(), Some(2));
+        assert_eq!(spectrum.get_retention_time(), Some(12.5));
+        assert_eq!(spectrum.get_scan_number(), None);
+
+        let mgf_spectrum = MgfSpectrum::from(spectrum);
+        let mut writer = MgfWriter::from_writer(Vec::new());
+        writer.write_spectrum(&mgf_spectrum).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "BEGIN IONS\nTITLE=index=7\nPEPMASS=500.25\nRTINSECONDS=12.5\nCHARGE=3+\n100 10\n200 100\nEND IONS\n"
+        );
+    }
+
+    #[test]
+    /// Writes an indexed mzML and checks the offsets and the checksum.
+    fn test_writing() {
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::new(Path::new(MZML_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let mut writer = writer::Writer::from_writer(Vec::new())
+            .with_metadata(metadata::Metadata::new().with_run_id("small".to_string()))
+            .with_mz_data_type(binary::BinaryDataType::Float32)
+            .with_zlib(true);
+        writer.write_all(spectra.iter()).unwrap();
+        let mzml_content = writer.into_inner();
+        let mzml_str = String::from_utf8(mzml_content.clone()).unwrap();
+
+        // Offsets of the spectra and the index list
+        let offsets: Vec<usize> = mzml_str.match_indices("<offset idRef=")
+            .map(|(idx, _)| {
+                let offset = &mzml_str[idx..].split_once('>').unwrap().1;
+                offset[..offset.find('<').unwrap()].parse().unwrap()
+            })
+            .collect();
+        assert_eq!(offsets.len(), 3);
+        for (idx, offset) in offsets.into_iter().enumerate() {
+            assert!(mzml_str[offset..].starts_with(&format!("<spectrum index=\"{}\" id=\"{}\"", idx, spectra[idx].get_id())));
+        }
+        let index_list_offset = mzml_str.split_once("<indexListOffset>").unwrap().1.split_once('<').unwrap().0;
+        assert!(mzml_str[index_list_offset.parse::<usize>().unwrap()..].starts_with("<indexList count=\"1\">"));
+
+        // SHA-1 of everything up to and including `<fileChecksum>`
+        let checksum_end = mzml_str.find("<fileChecksum>").unwrap() + "<fileChecksum>".len();
+        let checksum: String = sha1::Sha1::digest(&mzml_content[..checksum_end]).iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        assert!(mzml_str[checksum_end..].starts_with(&format!("{}</fileChecksum>", checksum)));
+
+        let written_spectra: Vec<spectrum::Spectrum> = reader::Reader::from_reader(mzml_content.as_slice())
+            .collect()
+            .unwrap();
+        assert_eq!(written_spectra.len(), spectra.len());
+        for (written_spectrum, spectrum) in written_spectra.iter().zip(spectra.iter()) {
+            assert_eq!(written_spectrum.get_id(), spectrum.get_id());
+            assert_eq!(written_spectrum.get_cv_params(), spectrum.get_cv_params());
+            assert_eq!(written_spectrum.get_scans(), spectrum.get_scans());
+            assert_eq!(written_spectrum.get_precursors(), spectrum.get_precursors());
+            assert!(is_close(written_spectrum.get_mz_list(), spectrum.get_mz_list(), 1e-6));
+            assert_eq!(written_spectrum.get_intensity_list(), spectrum.get_intensity_list());
+        }
+    }
+
+    #[test]
+    /// Converts MGF spectra to mzML and back.
+    fn test_mgf_roundtrip() {
+        let mgf_spectra: Vec<MgfSpectrum> = MgfReader::new(Path::new(MGF_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let spectra: Vec<spectrum::Spectrum> = mgf_spectra.iter().cloned().map(spectrum::Spectrum::from).collect();
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+
+        let mzml_content = writer.into_inner();
+        let roundtrip_spectra: Vec<MgfSpectrum> = reader::Reader::from_reader(mzml_content.as_slice())
+            .map(|spectrum| Ok(MgfSpectrum::from(spectrum)))
+            .collect()
+            .unwrap();
+        assert_eq!(roundtrip_spectra.len(), mgf_spectra.len());
+        for (roundtrip_spectrum, mgf_spectrum) in roundtrip_spectra.iter().zip(mgf_spectra.iter()) {
+            assert_eq!(roundtrip_spectrum.get_title(), mgf_spectrum.get_title());
+            assert_eq!(roundtrip_spectrum.get_precursors(), mgf_spectrum.get_precursors());
+            assert_eq!(roundtrip_spectrum.get_precursor_charges(), mgf_spectrum.get_precursor_charges());
+            assert_eq!(roundtrip_spectrum.get_retention_time(), mgf_spectrum.get_retention_time());
+            assert_eq!(roundtrip_spectrum.get_mz_list(), mgf_spectrum.get_mz_list());
+            assert_eq!(roundtrip_spectrum.get_intensity_list(), mgf_spectrum.get_intensity_list());
+        }
+
+        // The spectrum count in the header must match
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        assert!(writer.write_spectrum(&spectra[0]).is_err());
+        writer.write_header(2).unwrap();
+        writer.write_spectrum(&spectra[0]).unwrap();
+        assert!(writer.write_footer().is_err());
+    }
+
+    #[test]
+    /// Rejects spectra whose m/z and intensity lists have different lengths.
+    fn test_writer_rejects_mismatched_lengths() {
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_header(1).unwrap();
+        let spectrum = spectrum::Spectrum::new("scan=1".to_string(), 0, vec![100.0, 200.0], vec![10.0]);
+        let error = writer.write_spectrum(&spectrum).unwrap_err();
+        assert!(matches!(error, crate::Error::Io { ref source, .. } if source.kind() == std::io::ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    /// Checks that broken binary data is reported.
+    fn test_reading_malformed_input() {
+        let mzml_content = r#"<mzML><run><spectrumList>
+            <spectrum index="0" id="scan=1" defaultArrayLength="3">
+              <binaryDataArrayList count="1">
+                <binaryDataArray>
+                  <cvParam cvRef="MS" accession="MS:1000523" name="64-bit float" value=""/>
+                  <cvParam cvRef="MS" accession="MS:1000514" name="m/z array" value=""/>
+                  <binary>AAAAAAAAWUAAAAAAAABpQA==</binary>
+                </binaryDataArray>
+              </binaryDataArrayList>
+            </spectrum>
+        </spectrumList></run></mzML>"#;
+        let error = reader::Reader::from_reader(mzml_content.as_bytes()).next().unwrap_err();
+        assert!(matches!(&error, Error::BinaryData { message, .. } if message == "expected 3 values, got 2"));
+
+        let error = reader::Reader::from_reader(&mzml_content.as_bytes()[..200]).next().unwrap_err();
+        assert!(matches!(error, Error::Xml { .. }));
+    }
+}
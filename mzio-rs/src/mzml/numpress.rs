@@ -0,0 +1,138 @@
+//! Decoding of MS-Numpress compressed arrays, see
+//! Teleman et al., Numerical compression schemes for proteomics mass spectrometry data,
+//! Mol Cell Proteomics 2014 (https://github.com/ms-numpress/ms-numpress).
+
+/// Decodes the fixed point stored as big endian double in the first 8 bytes.
+///
+/// # Arguments
+///
+/// * `data` - Encoded data
+///
+fn decode_fixed_point(data: &[u8]) -> Option<f64> {
+    Some(f64::from_be_bytes(data.get(..8)?.try_into().ok()?))
+}
+
+/// Reader for the half bytes (nibbles) of the encoded data, high nibble first.
+struct HalfByteReader<'a> {
+    data: &'a [u8],
+    position: usize
+}
+
+impl<'a> HalfByteReader<'a> {
+    /// Creates a new reader starting at the given byte
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Encoded data
+    /// * `start` - Byte to start at
+    ///
+    fn new(data: &'a [u8], start: usize) -> Self {
+        Self {
+            data,
+            position: start * 2
+        }
+    }
+
+    /// Returns true if all half bytes are read. A single zero half byte at the end is padding.
+    ///
+    fn is_finished(&self) -> bool {
+        let remaining = (self.data.len() * 2).saturating_sub(self.position);
+        remaining == 0 || (remaining == 1 && self.data[self.data.len() - 1] & 0xf == 0)
+    }
+
+    /// Returns the next half byte
+    ///
+    fn next(&mut self) -> Option<u32> {
+        let byte = *self.data.get(self.position / 2)?;
+        let half_byte = if self.position.is_multiple_of(2) { byte >> 4 } else { byte & 0xf };
+        self.position += 1;
+        Some(half_byte as u32)
+    }
+
+    /// Decodes the next integer. The first half byte is the number of leading zero half bytes (0-8)
+    /// or 8 + the number of leading `0xf` half bytes (9-15), followed by the remaining half bytes,
+    /// least significant first.
+    ///
+    fn next_int(&mut self) -> Option<u32> {
+        let head = self.next()?;
+        let (num_leading, mut value) = if head <= 8 {
+            (head, 0)
+        } else {
+            let num_leading = head - 8;
+            (num_leading, (0..num_leading).fold(0_u32, |value, idx| value | (0xf000_0000 >> (4 * idx))))
+        };
+        for idx in 0..(8 - num_leading) {
+            value |= self.next()? << (4 * idx);
+        }
+        Some(value)
+    }
+}
+
+/// Decodes linear prediction compressed data (`MS:1002312`), usually m/z or retention times.
+/// Returns None if the data is corrupt.
+///
+/// # Arguments
+///
+/// * `data` - Encoded data
+///
+pub(crate) fn decode_linear(data: &[u8]) -> Option<Vec<f64>> {
+    let fixed_point = decode_fixed_point(data)?;
+    let read_int = |start: usize| -> Option<i64> {
+        Some(u32::from_le_bytes(data.get(start..start + 4)?.try_into().ok()?) as i64)
+    };
+    // The first two values are stored as plain integers, an array with a single value has 12 bytes
+    match data.len() {
+        8 => return Some(Vec::new()),
+        12 => return Some(vec![read_int(8)? as f64 / fixed_point]),
+        _ => ()
+    }
+    let mut previous = read_int(8)?;
+    let mut current = read_int(12)?;
+    let mut values: Vec<f64> = Vec::with_capacity(data.len());
+    values.push(previous as f64 / fixed_point);
+    values.push(current as f64 / fixed_point);
+    let mut reader = HalfByteReader::new(data, 16);
+    while !reader.is_finished() {
+        let diff = reader.next_int()? as i32 as i64;
+        let value = current + (current - previous) + diff;
+        values.push(value as f64 / fixed_point);
+        previous = current;
+        current = value;
+    }
+    Some(values)
+}
+
+/// Decodes positive integer compressed data (`MS:1002313`), usually ion counts.
+/// Returns None if the data is corrupt.
+///
+/// # Arguments
+///
+/// * `data` - Encoded data
+///
+pub(crate) fn decode_pic(data: &[u8]) -> Option<Vec<f64>> {
+    let mut values: Vec<f64> = Vec::with_capacity(data.len());
+    let mut reader = HalfByteReader::new(data, 0);
+    while !reader.is_finished() {
+        values.push(reader.next_int()? as f64);
+    }
+    Some(values)
+}
+
+/// Decodes short logged float compressed data (`MS:1002314`), usually intensities.
+/// Returns None if the data is corrupt.
+///
+/// # Arguments
+///
+/// * `data` - Encoded data
+///
+pub(crate) fn decode_slof(data: &[u8]) -> Option<Vec<f64>> {
+    let fixed_point = decode_fixed_point(data)?;
+    if !(data.len() - 8).is_multiple_of(2) {
+        return None;
+    }
+    Some(
+        data[8..].chunks(2)
+            .map(|chunk| (u16::from_le_bytes([chunk[0], chunk[1]]) as f64 / fixed_point).exp() - 1.0)
+            .collect()
+    )
+}
@@ -0,0 +1,307 @@
+// std imports
+use std::collections::HashMap;
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+use quick_xml::events::{BytesStart, Event};
+
+// internal imports
+use crate::compression::open_reader;
+use crate::error::{Error, Result};
+use crate::index::parse_int;
+use crate::mzml::binary::decode_binary;
+use crate::mzml::cv_param::{accession, find_cv_param, CvParam};
+use crate::mzml::spectrum::{Precursor, Scan, SelectedIon, Spectrum};
+use crate::xml::XmlReader;
+
+/// Content of the spectrum which is currently read
+#[derive(Default)]
+struct SpectrumContent {
+    cv_params: Vec<CvParam>,
+    scans: Vec<Scan>,
+    precursors: Vec<Precursor>,
+    mz_list: Vec<f64>,
+    intensity_list: Vec<f32>,
+    // CV parameters of the current binary data array
+    array_params: Vec<CvParam>
+}
+
+impl SpectrumContent {
+    /// Adds the CV parameters to the element they belong to,
+    /// parameters of unsupported elements are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `parents` - Names of the enclosing elements, innermost last
+    /// * `cv_params` - CV parameters
+    ///
+    fn add_cv_params(&mut self, parents: &[Vec<u8>], cv_params: Vec<CvParam>) {
+        let grandparent = parents.len().checked_sub(2).map(|idx| parents[idx].as_slice());
+        let target = match parents.last().map(|parent| parent.as_slice()) {
+            Some(b"spectrum") => Some(&mut self.cv_params),
+            Some(b"scan") => self.scans.last_mut().map(Scan::get_cv_params_mut),
+            Some(b"isolationWindow") if grandparent == Some(b"precursor") => {
+                self.precursors.last_mut().map(Precursor::get_isolation_window_mut)
+            },
+            Some(b"selectedIon") => self.precursors.last_mut()
+                .and_then(|precursor| precursor.get_selected_ions_mut().last_mut())
+                .map(SelectedIon::get_cv_params_mut),
+            Some(b"activation") => self.precursors.last_mut().map(Precursor::get_activation_mut),
+            Some(b"binaryDataArray") => Some(&mut self.array_params),
+            _ => None
+        };
+        if let Some(target) = target {
+            target.extend(cv_params);
+        }
+    }
+}
+
+/// Streaming reader for indexed and non-indexed mzML.
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+/// Chromatograms and the index are skipped.
+pub struct Reader<R: BufRead> {
+    internal_reader: XmlReader<R>,
+    param_groups: HashMap<String, Vec<CvParam>>
+}
+
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given mzML file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
+    ///
+    /// # Arguments
+    ///
+    /// * `mzml_file_path` - Path to mzML file
+    /// * `buffer_size` - Size of the internal read buffer
+    ///
+    pub fn new(mzml_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Ok(Self::from_xml_reader(XmlReader::new(
+            open_reader(mzml_file_path, buffer_size)?,
+            Some(mzml_file_path.to_path_buf())
+        )))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the mzML content
+    ///
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self::from_xml_reader(XmlReader::new(internal_reader, None))
+    }
+
+    /// Creates a new Reader from a XML reader
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - XML reader providing the mzML content
+    ///
+    fn from_xml_reader(internal_reader: XmlReader<R>) -> Self {
+        Self {
+            internal_reader,
+            param_groups: HashMap::new()
+        }
+    }
+
+    /// Reads a `<cvParam>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - cvParam element
+    ///
+    fn read_cv_param(&self, element: &BytesStart) -> Result<CvParam> {
+        let cv_param = CvParam::new(
+            self.internal_reader.get_attribute(element, "cvRef")?.unwrap_or_default(),
+            self.internal_reader.get_required_attribute(element, "accession")?,
+            self.internal_reader.get_attribute(element, "name")?.unwrap_or_default(),
+            self.internal_reader.get_attribute(element, "value")?.unwrap_or_default()
+        );
+        match self.internal_reader.get_attribute(element, "unitAccession")? {
+            Some(unit_accession) => Ok(cv_param.with_unit(
+                unit_accession,
+                self.internal_reader.get_attribute(element, "unitName")?.unwrap_or_default()
+            )),
+            None => Ok(cv_param)
+        }
+    }
+
+    /// Returns the CV parameters of the group referenced by a `<referenceableParamGroupRef>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - referenceableParamGroupRef element
+    ///
+    fn get_param_group(&self, element: &BytesStart) -> Result<Vec<CvParam>> {
+        let group_id = self.internal_reader.get_required_attribute(element, "ref")?;
+        match self.param_groups.get(&group_id) {
+            Some(cv_params) => Ok(cv_params.clone()),
+            None => Err(self.internal_reader.create_error(
+                format!("referenceable param group `{}` is not defined", group_id)
+            ))
+        }
+    }
+
+    /// Reads a `<referenceableParamGroup>`, so it can be resolved when referenced in spectra.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the referenceableParamGroup
+    ///
+    fn read_param_group(&mut self, element: &BytesStart) -> Result<()> {
+        let group_id = self.internal_reader.get_required_attribute(element, "id")?;
+        let mut cv_params: Vec<CvParam> = Vec::new();
+        loop {
+            match self.internal_reader.read_event()? {
+                Event::Start(element) | Event::Empty(element) if element.local_name().as_ref() == b"cvParam" => {
+                    cv_params.push(self.read_cv_param(&element)?);
+                },
+                Event::End(element) if element.local_name().as_ref() == b"referenceableParamGroup" => break,
+                Event::Eof => return Err(self.internal_reader.create_error(
+                    "unexpected end of file within `<referenceableParamGroup>`".to_string()
+                )),
+                _ => ()
+            }
+        }
+        self.param_groups.insert(group_id, cv_params);
+        Ok(())
+    }
+
+    /// Decodes the current binary data array and adds the values to the spectrum
+    /// if it is the m/z or intensity array.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Content of the current spectrum
+    /// * `encoded` - Base64 encoded content of the `<binary>` element
+    /// * `array_length` - Expected number of values
+    ///
+    fn add_binary_data_array(&self, content: &mut SpectrumContent, encoded: &str, array_length: Option<usize>) -> Result<()> {
+        let is_mz_array = find_cv_param(&content.array_params, accession::MZ_ARRAY).is_some();
+        let is_intensity_array = find_cv_param(&content.array_params, accession::INTENSITY_ARRAY).is_some();
+        if !is_mz_array && !is_intensity_array {
+            return Ok(());
+        }
+        let values = decode_binary(encoded, &content.array_params).map_err(|message| Error::BinaryData {
+            message,
+            position: self.internal_reader.get_position()
+        })?;
+        if let Some(array_length) = array_length.filter(|array_length| *array_length != values.len()) {
+            return Err(Error::BinaryData {
+                message: format!("expected {} values, got {}", array_length, values.len()),
+                position: self.internal_reader.get_position()
+            });
+        }
+        if is_mz_array {
+            content.mz_list = values;
+        } else {
+            content.intensity_list = values.into_iter().map(|value| value as f32).collect();
+        }
+        Ok(())
+    }
+
+    /// Reads a `<spectrum>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the spectrum
+    ///
+    fn read_spectrum(&mut self, element: &BytesStart) -> Result<Spectrum> {
+        let id = self.internal_reader.get_required_attribute(element, "id")?;
+        let index: usize = match self.internal_reader.get_attribute(element, "index")? {
+            Some(index) => parse_int(&index, &self.internal_reader.get_position())?,
+            None => 0
+        };
+        let default_array_length: Option<usize> = match self.internal_reader.get_attribute(element, "defaultArrayLength")? {
+            Some(length) => Some(parse_int(&length, &self.internal_reader.get_position())?),
+            None => None
+        };
+        let mut content = SpectrumContent::default();
+        let mut parents: Vec<Vec<u8>> = vec![b"spectrum".to_vec()];
+        let mut encoded = String::new();
+        let mut array_length = default_array_length;
+        loop {
+            let (element, is_empty) = match self.internal_reader.read_event()? {
+                Event::Start(element) => (element, false),
+                Event::Empty(element) => (element, true),
+                Event::End(_) => {
+                    match parents.pop().as_deref() {
+                        Some(b"spectrum") => break,
+                        Some(b"binaryDataArray") => self.add_binary_data_array(&mut content, &encoded, array_length)?,
+                        _ => ()
+                    }
+                    continue;
+                },
+                Event::Eof => return Err(self.internal_reader.create_error(
+                    "unexpected end of file within `<spectrum>`".to_string()
+                )),
+                _ => continue
+            };
+            let name = element.local_name().as_ref().to_vec();
+            match name.as_slice() {
+                b"cvParam" => {
+                    let cv_param = self.read_cv_param(&element)?;
+                    content.add_cv_params(&parents, vec![cv_param]);
+                },
+                b"referenceableParamGroupRef" => {
+                    let cv_params = self.get_param_group(&element)?;
+                    content.add_cv_params(&parents, cv_params);
+                },
+                b"scan" => content.scans.push(Scan::default()),
+                b"precursor" => content.precursors.push(
+                    Precursor::default().with_spectrum_ref(self.internal_reader.get_attribute(&element, "spectrumRef")?)
+                ),
+                b"selectedIon" => {
+                    if let Some(precursor) = content.precursors.last_mut() {
+                        precursor.get_selected_ions_mut().push(SelectedIon::default());
+                    }
+                },
+                b"binaryDataArray" => {
+                    content.array_params.clear();
+                    encoded.clear();
+                    array_length = match self.internal_reader.get_attribute(&element, "arrayLength")? {
+                        Some(length) => Some(parse_int(&length, &self.internal_reader.get_position())?),
+                        None => default_array_length
+                    };
+                },
+                b"binary" if !is_empty => {
+                    // Reads up to and including `</binary>`
+                    encoded = self.internal_reader.read_text()?;
+                    continue;
+                },
+                _ => ()
+            }
+            if !is_empty {
+                parents.push(name);
+            }
+        }
+        Ok(
+            Spectrum::new(id, index, content.mz_list, content.intensity_list)
+                .with_cv_params(content.cv_params)
+                .with_scans(content.scans)
+                .with_precursors(content.precursors)
+        )
+    }
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
+    type Item = Spectrum;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        loop {
+            match self.internal_reader.read_event()? {
+                Event::Start(element) => match element.local_name().as_ref() {
+                    b"spectrum" => return self.read_spectrum(&element).map(Some),
+                    b"referenceableParamGroup" => self.read_param_group(&element)?,
+                    _ => ()
+                },
+                Event::Eof => return Ok(None),
+                _ => ()
+            }
+        }
+    }
+}
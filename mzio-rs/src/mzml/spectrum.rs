@@ -0,0 +1,459 @@
+// internal imports
+use crate::mgf::spectrum::{Precursor as MgfPrecursor, Spectrum as MgfSpectrum};
+use crate::mzml::cv_param::{accession, find_cv_param, ms_cv_param, CvParam};
+
+/// Scan of a spectrum (`<scan>`), containing e.g. the scan start time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scan {
+    cv_params: Vec<CvParam>
+}
+
+impl Scan {
+    /// Creates a new scan
+    ///
+    /// # Arguments
+    ///
+    /// * `cv_params` - CV parameters
+    ///
+    pub fn new(cv_params: Vec<CvParam>) -> Self {
+        Self {
+            cv_params
+        }
+    }
+
+    /// Returns the CV parameters
+    ///
+    pub fn get_cv_params(&self) -> &Vec<CvParam> {
+        &self.cv_params
+    }
+
+    /// Returns the CV parameters for modification
+    ///
+    pub fn get_cv_params_mut(&mut self) -> &mut Vec<CvParam> {
+        &mut self.cv_params
+    }
+
+    /// Returns the scan start time in seconds
+    ///
+    pub fn get_start_time(&self) -> Option<f64> {
+        let cv_param = find_cv_param(&self.cv_params, accession::SCAN_START_TIME)?;
+        let start_time = cv_param.get_value_as_f64()?;
+        match cv_param.get_unit_accession() {
+            Some(accession::UNIT_MINUTE) => Some(start_time * 60.0),
+            _ => Some(start_time)
+        }
+    }
+
+    /// Returns the ion injection time in milliseconds
+    ///
+    pub fn get_ion_injection_time(&self) -> Option<f64> {
+        find_cv_param(&self.cv_params, accession::ION_INJECTION_TIME)?.get_value_as_f64()
+    }
+}
+
+/// Selected ion of a precursor (`<selectedIon>`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectedIon {
+    cv_params: Vec<CvParam>
+}
+
+impl SelectedIon {
+    /// Creates a new selected ion
+    ///
+    /// # Arguments
+    ///
+    /// * `cv_params` - CV parameters
+    ///
+    pub fn new(cv_params: Vec<CvParam>) -> Self {
+        Self {
+            cv_params
+        }
+    }
+
+    /// Returns the CV parameters
+    ///
+    pub fn get_cv_params(&self) -> &Vec<CvParam> {
+        &self.cv_params
+    }
+
+    /// Returns the CV parameters for modification
+    ///
+    pub fn get_cv_params_mut(&mut self) -> &mut Vec<CvParam> {
+        &mut self.cv_params
+    }
+
+    /// Returns the m/z
+    ///
+    pub fn get_mz(&self) -> Option<f64> {
+        find_cv_param(&self.cv_params, accession::SELECTED_ION_MZ)?.get_value_as_f64()
+    }
+
+    /// Returns the intensity
+    ///
+    pub fn get_intensity(&self) -> Option<f64> {
+        find_cv_param(&self.cv_params, accession::PEAK_INTENSITY)?.get_value_as_f64()
+    }
+
+    /// Returns the charge state, or the possible charge states if the charge is ambiguous
+    ///
+    pub fn get_charges(&self) -> Vec<i8> {
+        let charges = |charge_accession: &str| -> Vec<i8> {
+            self.cv_params.iter()
+                .filter(|cv_param| cv_param.get_accession() == charge_accession)
+                .filter_map(|cv_param| cv_param.get_value().parse().ok())
+                .collect()
+        };
+        let charge_states = charges(accession::CHARGE_STATE);
+        if charge_states.is_empty() {
+            charges(accession::POSSIBLE_CHARGE_STATE)
+        } else {
+            charge_states
+        }
+    }
+}
+
+/// Precursor of a spectrum (`<precursor>`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Precursor {
+    spectrum_ref: Option<String>,
+    isolation_window: Vec<CvParam>,
+    selected_ions: Vec<SelectedIon>,
+    activation: Vec<CvParam>
+}
+
+impl Precursor {
+    /// Creates a new precursor
+    ///
+    /// # Arguments
+    ///
+    /// * `selected_ions` - Selected ions
+    ///
+    pub fn new(selected_ions: Vec<SelectedIon>) -> Self {
+        Self {
+            selected_ions,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the ID of the spectrum the precursor was selected from
+    ///
+    /// # Arguments
+    ///
+    /// * `spectrum_ref` - Native ID of the precursor spectrum
+    ///
+    pub fn with_spectrum_ref(mut self, spectrum_ref: Option<String>) -> Self {
+        self.spectrum_ref = spectrum_ref;
+        self
+    }
+
+    /// Sets the CV parameters of the isolation window
+    ///
+    /// # Arguments
+    ///
+    /// * `isolation_window` - CV parameters
+    ///
+    pub fn with_isolation_window(mut self, isolation_window: Vec<CvParam>) -> Self {
+        self.isolation_window = isolation_window;
+        self
+    }
+
+    /// Sets the CV parameters of the activation, e.g. the dissociation method
+    ///
+    /// # Arguments
+    ///
+    /// * `activation` - CV parameters
+    ///
+    pub fn with_activation(mut self, activation: Vec<CvParam>) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Returns the ID of the spectrum the precursor was selected from
+    ///
+    pub fn get_spectrum_ref(&self) -> Option<&str> {
+        self.spectrum_ref.as_deref()
+    }
+
+    /// Returns the CV parameters of the isolation window
+    ///
+    pub fn get_isolation_window(&self) -> &Vec<CvParam> {
+        &self.isolation_window
+    }
+
+    /// Returns the selected ions
+    ///
+    pub fn get_selected_ions(&self) -> &Vec<SelectedIon> {
+        &self.selected_ions
+    }
+
+    /// Returns the CV parameters of the activation
+    ///
+    pub fn get_activation(&self) -> &Vec<CvParam> {
+        &self.activation
+    }
+
+    /// Returns the isolation window target m/z
+    ///
+    pub fn get_isolation_window_target_mz(&self) -> Option<f64> {
+        find_cv_param(&self.isolation_window, accession::ISOLATION_WINDOW_TARGET_MZ)?.get_value_as_f64()
+    }
+
+    /// Returns the CV parameters of the isolation window for modification
+    ///
+    pub fn get_isolation_window_mut(&mut self) -> &mut Vec<CvParam> {
+        &mut self.isolation_window
+    }
+
+    /// Returns the selected ions for modification
+    ///
+    pub fn get_selected_ions_mut(&mut self) -> &mut Vec<SelectedIon> {
+        &mut self.selected_ions
+    }
+
+    /// Returns the CV parameters of the activation for modification
+    ///
+    pub fn get_activation_mut(&mut self) -> &mut Vec<CvParam> {
+        &mut self.activation
+    }
+}
+
+/// Spectrum representation for mzML files.
+/// Converts from and into a MGF spectrum, the native ID being the title.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    id: String,
+    index: usize,
+    cv_params: Vec<CvParam>,
+    scans: Vec<Scan>,
+    precursors: Vec<Precursor>,
+    mz_list: Vec<f64>,
+    intensity_list: Vec<f32>
+}
+
+impl Spectrum {
+    /// Creates a new spectrum
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Native ID, e.g. `controllerType=0 controllerNumber=1 scan=42`
+    /// * `index` - Position of the spectrum in the run (0-based)
+    /// * `mz_list` - M/Z list
+    /// * `intensity_list` - Intensity list
+    ///
+    pub fn new(id: String, index: usize, mz_list: Vec<f64>, intensity_list: Vec<f32>) -> Self {
+        Self {
+            id,
+            index,
+            cv_params: Vec::new(),
+            scans: Vec::new(),
+            precursors: Vec::new(),
+            mz_list,
+            intensity_list
+        }
+    }
+
+    /// Sets the CV parameters, e.g. MS level and spectrum representation
+    ///
+    /// # Arguments
+    ///
+    /// * `cv_params` - CV parameters
+    ///
+    pub fn with_cv_params(mut self, cv_params: Vec<CvParam>) -> Self {
+        self.cv_params = cv_params;
+        self
+    }
+
+    /// Sets the scans
+    ///
+    /// # Arguments
+    ///
+    /// * `scans` - Scans
+    ///
+    pub fn with_scans(mut self, scans: Vec<Scan>) -> Self {
+        self.scans = scans;
+        self
+    }
+
+    /// Sets the precursors
+    ///
+    /// # Arguments
+    ///
+    /// * `precursors` - Precursors
+    ///
+    pub fn with_precursors(mut self, precursors: Vec<Precursor>) -> Self {
+        self.precursors = precursors;
+        self
+    }
+
+    /// Returns the native ID
+    ///
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the position of the spectrum in the run (0-based)
+    ///
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the CV parameters
+    ///
+    pub fn get_cv_params(&self) -> &Vec<CvParam> {
+        &self.cv_params
+    }
+
+    /// Returns the scans
+    ///
+    pub fn get_scans(&self) -> &Vec<Scan> {
+        &self.scans
+    }
+
+    /// Returns the precursors, empty for MS1 spectra
+    ///
+    pub fn get_precursors(&self) -> &Vec<Precursor> {
+        &self.precursors
+    }
+
+    /// Returns the M/Z list
+    ///
+    pub fn get_mz_list(&self) -> &Vec<f64> {
+        &self.mz_list
+    }
+
+    /// Returns the intensity list
+    ///
+    pub fn get_intensity_list(&self) -> &Vec<f32> {
+        &self.intensity_list
+    }
+
+    /// Returns the MS level
+    ///
+    pub fn get_ms_level(&self) -> Option<u8> {
+        find_cv_param(&self.cv_params, accession::MS_LEVEL)?.get_value().parse().ok()
+    }
+
+    /// Returns true if the spectrum is centroided
+    ///
+    pub fn is_centroided(&self) -> bool {
+        find_cv_param(&self.cv_params, accession::CENTROID_SPECTRUM).is_some()
+    }
+
+    /// Returns the retention time in seconds, i.e. the start time of the first scan
+    ///
+    pub fn get_retention_time(&self) -> Option<f64> {
+        self.scans.first()?.get_start_time()
+    }
+
+    /// Returns the ion injection time of the first scan in milliseconds
+    ///
+    pub fn get_ion_injection_time(&self) -> Option<f64> {
+        self.scans.first()?.get_ion_injection_time()
+    }
+
+    /// Returns the scan number, if the native ID contains `scan=`
+    ///
+    pub fn get_scan_number(&self) -> Option<u32> {
+        let (_, scan) = self.id.split_once("scan=")?;
+        scan.split_ascii_whitespace().next()?.parse().ok()
+    }
+
+    /// Returns the CV parameters for modification
+    ///
+    pub fn get_cv_params_mut(&mut self) -> &mut Vec<CvParam> {
+        &mut self.cv_params
+    }
+
+    /// Returns the scans for modification
+    ///
+    pub fn get_scans_mut(&mut self) -> &mut Vec<Scan> {
+        &mut self.scans
+    }
+
+    /// Returns the precursors for modification
+    ///
+    pub fn get_precursors_mut(&mut self) -> &mut Vec<Precursor> {
+        &mut self.precursors
+    }
+}
+
+impl From<Spectrum> for MgfSpectrum {
+    /// Converts the spectrum into a MGF spectrum. The native ID becomes the title,
+    /// each selected ion a precursor and the scan number `SCANS=`.
+    ///
+    fn from(spectrum: Spectrum) -> Self {
+        let precursors: Vec<MgfPrecursor> = spectrum.precursors.iter()
+            .flat_map(|precursor| precursor.selected_ions.iter())
+            .filter_map(|selected_ion| Some(MgfPrecursor::new(selected_ion.get_mz()?, selected_ion.get_intensity())))
+            .collect();
+        let mut precursor_charges: Vec<i8> = Vec::new();
+        let selected_ions = spectrum.precursors.iter()
+            .flat_map(|precursor| precursor.selected_ions.iter());
+        for charge in selected_ions.flat_map(|selected_ion| selected_ion.get_charges()) {
+            if !precursor_charges.contains(&charge) {
+                precursor_charges.push(charge);
+            }
+        }
+        let mut additional_params: Vec<(String, String)> = Vec::new();
+        if let Some(scan) = spectrum.get_scan_number() {
+            additional_params.push(("SCANS".to_string(), scan.to_string()));
+        }
+        let retention_time = spectrum.get_retention_time();
+        MgfSpectrum::new(spectrum.id, precursors, precursor_charges, retention_time, spectrum.mz_list, spectrum.intensity_list)
+            .with_additional_params(additional_params)
+    }
+}
+
+impl From<MgfSpectrum> for Spectrum {
+    /// Converts a MGF spectrum into a centroided MS2 spectrum. The title becomes the native ID,
+    /// each precursor a selected ion and the charges the (possible) charge states.
+    ///
+    fn from(mgf_spectrum: MgfSpectrum) -> Self {
+        let cv_params = vec![
+            ms_cv_param(accession::MS_LEVEL, "ms level").with_value("2".to_string()),
+            ms_cv_param(accession::MSN_SPECTRUM, "MSn spectrum"),
+            ms_cv_param(accession::CENTROID_SPECTRUM, "centroid spectrum")
+        ];
+        let scans = match mgf_spectrum.get_retention_time() {
+            Some(retention_time) => vec![Scan::new(vec![
+                ms_cv_param(accession::SCAN_START_TIME, "scan start time")
+                    .with_value(retention_time.to_string())
+                    .with_unit(accession::UNIT_SECOND.to_string(), "second".to_string())
+            ])],
+            None => Vec::new()
+        };
+        let charge_term = match mgf_spectrum.get_precursor_charges().len() {
+            1 => ms_cv_param(accession::CHARGE_STATE, "charge state"),
+            _ => ms_cv_param(accession::POSSIBLE_CHARGE_STATE, "possible charge state")
+        };
+        let precursors = mgf_spectrum.get_precursors().iter()
+            .map(|mgf_precursor| {
+                let mut cv_params = vec![
+                    ms_cv_param(accession::SELECTED_ION_MZ, "selected ion m/z")
+                        .with_value(mgf_precursor.get_mz().to_string())
+                        .with_unit(accession::UNIT_MZ.to_string(), "m/z".to_string())
+                ];
+                for charge in mgf_spectrum.get_precursor_charges() {
+                    cv_params.push(charge_term.clone().with_value(charge.to_string()));
+                }
+                if let Some(intensity) = mgf_precursor.get_intensity() {
+                    cv_params.push(
+                        ms_cv_param(accession::PEAK_INTENSITY, "peak intensity")
+                            .with_value(intensity.to_string())
+                            .with_unit(accession::UNIT_DETECTOR_COUNTS.to_string(), "number of detector counts".to_string())
+                    );
+                }
+                Precursor::new(vec![SelectedIon::new(cv_params)])
+            })
+            .collect();
+        Self::new(
+            mgf_spectrum.get_title().to_string(),
+            0,
+            mgf_spectrum.get_mz_list().clone(),
+            mgf_spectrum.get_intensity_list().clone()
+        )
+            .with_cv_params(cv_params)
+            .with_scans(scans)
+            .with_precursors(precursors)
+    }
+}
@@ -0,0 +1,457 @@
+// std imports
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use quick_xml::escape::escape;
+use sha1::{Digest, Sha1};
+
+// internal imports
+use crate::compression::{Compression, Encoder};
+use crate::error::{Error, Result};
+use crate::mzml::binary::{encode_binary, BinaryCompression, BinaryDataType};
+use crate::mzml::cv_param::{accession, ms_cv_param, CvParam};
+use crate::mzml::metadata::Metadata;
+use crate::mzml::spectrum::Spectrum;
+
+/// Controlled vocabularies referenced by the written CV parameters
+const CV_LIST: &str = r#"    <cvList count="2">
+      <cv id="MS" fullName="Proteomics Standards Initiative Mass Spectrometry Ontology" version="4.1.30" URI="https://raw.githubusercontent.com/HUPO-PSI/psi-ms-CV/master/psi-ms.obo"/>
+      <cv id="UO" fullName="Unit Ontology" version="09:04:2014" URI="https://raw.githubusercontent.com/bio-ontology-research-group/unit-ontology/master/unit.obo"/>
+    </cvList>
+"#;
+
+/// ID of the instrument configuration
+const INSTRUMENT_CONFIGURATION_ID: &str = "IC1";
+
+/// ID of the data processing
+const DATA_PROCESSING_ID: &str = "mzio_processing";
+
+/// Writer for indexed mzML files (`indexedmzML`), including the spectrum offsets and the SHA-1 checksum.
+/// Works on any target implementing `Write`, e.g. files, stdout or `Vec<u8>`.
+/// MGF spectra can be written after converting them with `Spectrum::from()`.
+///
+/// The spectrum count is part of the header, so write the spectra either with `write_all()`
+/// or with `write_header()`, `write_spectrum()` for each spectrum and `write_footer()`.
+pub struct Writer<W: Write> {
+    internal_writer: W,
+    metadata: Metadata,
+    mz_data_type: BinaryDataType,
+    intensity_data_type: BinaryDataType,
+    is_zlib: bool,
+    hasher: Sha1,
+    written_bytes: u64,
+    spectrum_count: Option<usize>,
+    spectrum_offsets: Vec<(String, u64)>
+}
+
+impl Writer<BufWriter<File>> {
+    /// Creates a new buffered Writer for the given mzML file
+    ///
+    /// # Arguments
+    ///
+    /// * `mzml_file_path` - Path to mzML file
+    ///
+    pub fn new(mzml_file_path: &Path) -> Result<Self> {
+        let mzml_file: File = File::create(mzml_file_path)
+            .map_err(|err| Error::io_with_path(err, mzml_file_path))?;
+        Ok(Self::from_writer(BufWriter::new(mzml_file)))
+    }
+}
+
+impl Writer<Encoder<BufWriter<File>>> {
+    /// Creates a new buffered Writer for the given mzML file, compressing the output.
+    /// The offsets in the index refer to the uncompressed content.
+    /// Use finish() to complete the compressed file.
+    ///
+    /// # Arguments
+    ///
+    /// * `mzml_file_path` - Path to mzML file
+    /// * `compression` - Compression of the written file, see `Compression::from_path()`
+    ///
+    pub fn with_compression(mzml_file_path: &Path, compression: Compression) -> Result<Self> {
+        Ok(Self::from_writer(Encoder::create(mzml_file_path, compression)?))
+    }
+}
+
+impl<W: Write> Writer<Encoder<W>> {
+    /// Flushes the buffer, completes the compressed stream and returns the underlying write target.
+    ///
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        self.internal_writer.finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new Writer for any write target.
+    /// The target is used as is, so wrap unbuffered targets in a `BufWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_writer` - Write target
+    ///
+    pub fn from_writer(internal_writer: W) -> Self {
+        Self {
+            internal_writer,
+            metadata: Metadata::default(),
+            mz_data_type: BinaryDataType::Float64,
+            intensity_data_type: BinaryDataType::Float32,
+            is_zlib: false,
+            hasher: Sha1::new(),
+            written_bytes: 0,
+            spectrum_count: None,
+            spectrum_offsets: Vec::new()
+        }
+    }
+
+    /// Sets the metadata, e.g. instrument and software
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - Run metadata
+    ///
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Sets the numeric type of the m/z arrays, `BinaryDataType::Float64` by default
+    ///
+    /// # Arguments
+    ///
+    /// * `mz_data_type` - Numeric type
+    ///
+    pub fn with_mz_data_type(mut self, mz_data_type: BinaryDataType) -> Self {
+        self.mz_data_type = mz_data_type;
+        self
+    }
+
+    /// Sets the numeric type of the intensity arrays, `BinaryDataType::Float32` by default
+    ///
+    /// # Arguments
+    ///
+    /// * `intensity_data_type` - Numeric type
+    ///
+    pub fn with_intensity_data_type(mut self, intensity_data_type: BinaryDataType) -> Self {
+        self.intensity_data_type = intensity_data_type;
+        self
+    }
+
+    /// Enables or disables zlib compression of the binary data arrays. Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_zlib` - Compress binary data arrays
+    ///
+    pub fn with_zlib(mut self, is_zlib: bool) -> Self {
+        self.is_zlib = is_zlib;
+        self
+    }
+
+    /// Returns the metadata
+    ///
+    pub fn get_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the underlying write target, e.g. to access an in-memory buffer.
+    /// Make sure to call flush() first.
+    ///
+    pub fn into_inner(self) -> W {
+        self.internal_writer
+    }
+
+    /// Writes the complete byte slice, updating the byte count and checksum.
+    /// Returns the number of written bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to write
+    ///
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.internal_writer.write_all(bytes)?;
+        self.hasher.update(bytes);
+        self.written_bytes += bytes.len() as u64;
+        Ok(bytes.len())
+    }
+
+    /// Writes the header, i.e. the metadata and the start of the spectrum list.
+    /// Returns the number of written bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectrum_count` - Number of spectra which will be written
+    ///
+    pub fn write_header(&mut self, spectrum_count: usize) -> Result<usize> {
+        if self.spectrum_count.is_some() {
+            return Err(invalid_input("header is already written"));
+        }
+        self.spectrum_count = Some(spectrum_count);
+        let metadata = &self.metadata;
+        let mut content = String::new();
+        content.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        content.push_str("<indexedmzML xmlns=\"http://psi.hupo.org/ms/mzml\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:schemaLocation=\"http://psi.hupo.org/ms/mzml http://psidev.info/files/ms/mzML/xsd/mzML1.1.2_idx.xsd\">\n");
+        content.push_str(&format!(
+            "  <mzML xmlns=\"http://psi.hupo.org/ms/mzml\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:schemaLocation=\"http://psi.hupo.org/ms/mzml http://psidev.info/files/ms/mzML/xsd/mzML1.1.0.xsd\" id=\"{}\" version=\"1.1.0\">\n",
+            escape(metadata.get_run_id())
+        ));
+        content.push_str(CV_LIST);
+        content.push_str("    <fileDescription>\n      <fileContent>\n");
+        push_cv_params(&mut content, metadata.get_file_content(), 8);
+        content.push_str("      </fileContent>\n    </fileDescription>\n");
+        content.push_str(&format!(
+            "    <softwareList count=\"1\">\n      <software id=\"{}\" version=\"{}\">\n",
+            escape(metadata.get_software_id()),
+            escape(metadata.get_software_version())
+        ));
+        push_cv_params(&mut content, metadata.get_software(), 8);
+        content.push_str("      </software>\n    </softwareList>\n");
+        content.push_str(&format!(
+            "    <instrumentConfigurationList count=\"1\">\n      <instrumentConfiguration id=\"{}\">\n",
+            INSTRUMENT_CONFIGURATION_ID
+        ));
+        push_cv_params(&mut content, metadata.get_instrument(), 8);
+        content.push_str("      </instrumentConfiguration>\n    </instrumentConfigurationList>\n");
+        content.push_str(&format!(
+            "    <dataProcessingList count=\"1\">\n      <dataProcessing id=\"{}\">\n        <processingMethod order=\"0\" softwareRef=\"{}\">\n",
+            DATA_PROCESSING_ID,
+            escape(metadata.get_software_id())
+        ));
+        push_cv_params(&mut content, metadata.get_data_processing(), 10);
+        content.push_str("        </processingMethod>\n      </dataProcessing>\n    </dataProcessingList>\n");
+        content.push_str(&format!(
+            "    <run id=\"{}\" defaultInstrumentConfigurationRef=\"{}\">\n      <spectrumList count=\"{}\" defaultDataProcessingRef=\"{}\">\n",
+            escape(metadata.get_run_id()),
+            INSTRUMENT_CONFIGURATION_ID,
+            spectrum_count,
+            DATA_PROCESSING_ID
+        ));
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Writes a spectrum, the index attribute is set to the position in the written file.
+    /// Returns the number of written bytes. The m/z and intensity lists must have the same length.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectrum` - Spectrum
+    ///
+    pub fn write_spectrum(&mut self, spectrum: &Spectrum) -> Result<usize> {
+        match self.spectrum_count {
+            None => return Err(invalid_input("header must be written before the spectra")),
+            Some(spectrum_count) if spectrum_count == self.spectrum_offsets.len() => {
+                return Err(invalid_input(&format!("header announced {} spectra", spectrum_count)));
+            },
+            _ => ()
+        }
+        if spectrum.get_mz_list().len() != spectrum.get_intensity_list().len() {
+            return Err(invalid_input(&format!(
+                "spectrum `{}` has {} m/z values but {} intensities",
+                spectrum.get_id(),
+                spectrum.get_mz_list().len(),
+                spectrum.get_intensity_list().len()
+            )));
+        }
+        let indent = "        ";
+        let mut content = format!(
+            "{}<spectrum index=\"{}\" id=\"{}\" defaultArrayLength=\"{}\">\n",
+            indent,
+            self.spectrum_offsets.len(),
+            escape(spectrum.get_id()),
+            spectrum.get_mz_list().len()
+        );
+        push_cv_params(&mut content, spectrum.get_cv_params(), 10);
+        if !spectrum.get_scans().is_empty() {
+            content.push_str(&format!("          <scanList count=\"{}\">\n", spectrum.get_scans().len()));
+            push_cv_params(&mut content, &[ms_cv_param(accession::NO_COMBINATION, "no combination")], 12);
+            for scan in spectrum.get_scans() {
+                content.push_str("            <scan>\n");
+                push_cv_params(&mut content, scan.get_cv_params(), 14);
+                content.push_str("            </scan>\n");
+            }
+            content.push_str("          </scanList>\n");
+        }
+        if !spectrum.get_precursors().is_empty() {
+            content.push_str(&format!("          <precursorList count=\"{}\">\n", spectrum.get_precursors().len()));
+            for precursor in spectrum.get_precursors() {
+                match precursor.get_spectrum_ref() {
+                    Some(spectrum_ref) => content.push_str(&format!("            <precursor spectrumRef=\"{}\">\n", escape(spectrum_ref))),
+                    None => content.push_str("            <precursor>\n")
+                }
+                if !precursor.get_isolation_window().is_empty() {
+                    content.push_str("              <isolationWindow>\n");
+                    push_cv_params(&mut content, precursor.get_isolation_window(), 16);
+                    content.push_str("              </isolationWindow>\n");
+                }
+                if !precursor.get_selected_ions().is_empty() {
+                    content.push_str(&format!(
+                        "              <selectedIonList count=\"{}\">\n",
+                        precursor.get_selected_ions().len()
+                    ));
+                    for selected_ion in precursor.get_selected_ions() {
+                        content.push_str("                <selectedIon>\n");
+                        push_cv_params(&mut content, selected_ion.get_cv_params(), 18);
+                        content.push_str("                </selectedIon>\n");
+                    }
+                    content.push_str("              </selectedIonList>\n");
+                }
+                // The activation is mandatory, even if it is unknown
+                content.push_str("              <activation>\n");
+                push_cv_params(&mut content, precursor.get_activation(), 16);
+                content.push_str("              </activation>\n");
+                content.push_str("            </precursor>\n");
+            }
+            content.push_str("          </precursorList>\n");
+        }
+        content.push_str("          <binaryDataArrayList count=\"2\">\n");
+        self.push_binary_data_array(
+            &mut content,
+            &encode_binary(spectrum.get_mz_list(), self.mz_data_type, self.is_zlib),
+            self.mz_data_type,
+            ms_cv_param(accession::MZ_ARRAY, "m/z array").with_unit(accession::UNIT_MZ.to_string(), "m/z".to_string())
+        );
+        self.push_binary_data_array(
+            &mut content,
+            &encode_binary(spectrum.get_intensity_list(), self.intensity_data_type, self.is_zlib),
+            self.intensity_data_type,
+            ms_cv_param(accession::INTENSITY_ARRAY, "intensity array")
+                .with_unit(accession::UNIT_DETECTOR_COUNTS.to_string(), "number of detector counts".to_string())
+        );
+        content.push_str("          </binaryDataArrayList>\n");
+        content.push_str("        </spectrum>\n");
+
+        self.spectrum_offsets.push((spectrum.get_id().to_string(), self.written_bytes + indent.len() as u64));
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Appends a binary data array to the content
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - Content to append to
+    /// * `encoded` - Base64 encoded binary data
+    /// * `data_type` - Numeric type of the binary data
+    /// * `array_type` - CV parameter of the array type
+    ///
+    fn push_binary_data_array(&self, content: &mut String, encoded: &str, data_type: BinaryDataType, array_type: CvParam) {
+        let (data_type_accession, data_type_name) = data_type.get_cv_term();
+        let (compression_accession, compression_name) = BinaryCompression::new(None, self.is_zlib).get_cv_term();
+        content.push_str(&format!("            <binaryDataArray encodedLength=\"{}\">\n", encoded.len()));
+        push_cv_params(
+            content,
+            &[
+                ms_cv_param(data_type_accession, data_type_name),
+                ms_cv_param(compression_accession, compression_name),
+                array_type
+            ],
+            14
+        );
+        content.push_str(&format!("              <binary>{}</binary>\n", encoded));
+        content.push_str("            </binaryDataArray>\n");
+    }
+
+    /// Writes the footer, i.e. the end of the spectrum list, the index and the SHA-1 checksum.
+    /// Returns the number of written bytes.
+    ///
+    pub fn write_footer(&mut self) -> Result<usize> {
+        match self.spectrum_count {
+            None => return Err(invalid_input("header must be written before the footer")),
+            Some(spectrum_count) if spectrum_count != self.spectrum_offsets.len() => {
+                return Err(invalid_input(&format!(
+                    "header announced {} spectra, but {} were written",
+                    spectrum_count,
+                    self.spectrum_offsets.len()
+                )));
+            },
+            _ => ()
+        }
+        let mut written_bytes = self.write_bytes(b"      </spectrumList>\n    </run>\n  </mzML>\n")?;
+        let index_list_offset = self.written_bytes + 2;
+        let mut content = String::from("  <indexList count=\"1\">\n    <index name=\"spectrum\">\n");
+        for (id, offset) in self.spectrum_offsets.iter() {
+            content.push_str(&format!("      <offset idRef=\"{}\">{}</offset>\n", escape(id), offset));
+        }
+        content.push_str("    </index>\n  </indexList>\n");
+        content.push_str(&format!("  <indexListOffset>{}</indexListOffset>\n  <fileChecksum>", index_list_offset));
+        written_bytes += self.write_bytes(content.as_bytes())?;
+
+        // The checksum covers everything up to and including `<fileChecksum>`
+        let checksum: String = self.hasher.clone().finalize().iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        written_bytes += self.write_bytes(format!("{}</fileChecksum>\n</indexedmzML>\n", checksum).as_bytes())?;
+        Ok(written_bytes)
+    }
+
+    /// Writes a complete mzML file containing the given spectra.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectra` - Iterator of spectra
+    ///
+    pub fn write_all<'b, I>(&mut self, spectra: I) -> Result<usize>
+    where
+        I: ExactSizeIterator<Item = &'b Spectrum>,
+    {
+        let mut written_bytes = self.write_header(spectra.len())?;
+        for spectrum in spectra {
+            written_bytes += self.write_spectrum(spectrum)?;
+        }
+        written_bytes += self.write_footer()?;
+        Ok(written_bytes)
+    }
+
+    /// Flushes the buffer
+    ///
+    pub fn flush(&mut self) -> Result<()> {
+        self.internal_writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Creates an error for a call in the wrong order
+///
+/// # Arguments
+///
+/// * `message` - Message describing the problem
+///
+fn invalid_input(message: &str) -> Error {
+    Error::from(io::Error::new(io::ErrorKind::InvalidInput, message))
+}
+
+/// Appends the CV parameters as `<cvParam>` elements to the content
+///
+/// # Arguments
+///
+/// * `content` - Content to append to
+/// * `cv_params` - CV parameters
+/// * `indent` - Number of spaces to indent the elements
+///
+fn push_cv_params(content: &mut String, cv_params: &[CvParam], indent: usize) {
+    for cv_param in cv_params {
+        content.push_str(&format!(
+            "{:indent$}<cvParam cvRef=\"{}\" accession=\"{}\" name=\"{}\" value=\"{}\"",
+            "",
+            escape(cv_param.get_cv_ref()),
+            escape(cv_param.get_accession()),
+            escape(cv_param.get_name()),
+            escape(cv_param.get_value()),
+            indent = indent
+        ));
+        if let Some(unit_accession) = cv_param.get_unit_accession() {
+            // The unit CV is the prefix of the accession, e.g. `UO` for `UO:0000010`
+            let unit_cv_ref = unit_accession.split(':').next().unwrap_or_default();
+            content.push_str(&format!(
+                " unitCvRef=\"{}\" unitAccession=\"{}\" unitName=\"{}\"",
+                escape(unit_cv_ref),
+                escape(unit_accession),
+                escape(cv_param.get_unit_name().unwrap_or_default())
+            ));
+        }
+        content.push_str("/>\n");
+    }
+}
@@ -0,0 +1,96 @@
+// std imports
+use std::fmt;
+
+/// Parameter as used in mzTab, e.g. `[MS, MS:1001207, Mascot, ]`.
+/// User parameters have an empty label and accession, e.g. `[, , my parameter, 42]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CvParam {
+    label: String,
+    accession: String,
+    name: String,
+    value: String
+}
+
+impl CvParam {
+    /// Creates a new parameter
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - Label of the controlled vocabulary, e.g. `MS`
+    /// * `accession` - Accession, e.g. `MS:1001207`
+    /// * `name` - Name, e.g. `Mascot`
+    /// * `value` - Value, empty if none
+    ///
+    pub fn new(label: String, accession: String, name: String, value: String) -> Self {
+        Self {
+            label,
+            accession,
+            name,
+            value
+        }
+    }
+
+    /// Parses a parameter, e.g. `[MS, MS:1001207, Mascot, ]`. None if malformed.
+    /// The name may contain commas.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - Parameter
+    ///
+    pub fn parse(param: &str) -> Option<Self> {
+        let param = param.trim().strip_prefix('[')?.strip_suffix(']')?;
+        let mut fields = param.splitn(3, ',');
+        let label = fields.next()?.trim();
+        let accession = fields.next()?.trim();
+        let (name, value) = fields.next()?.rsplit_once(',')?;
+        Some(Self::new(label.to_string(), accession.to_string(), name.trim().to_string(), value.trim().to_string()))
+    }
+
+    /// Parses a `|` separated list of parameters. None if a parameter is malformed.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters
+    ///
+    pub fn parse_list(params: &str) -> Option<Vec<Self>> {
+        // Names may contain `|`, so the list is split at `]|[`
+        let mut cv_params = Vec::new();
+        let mut remaining = params.trim();
+        while let Some(end) = remaining.find("]|[") {
+            cv_params.push(Self::parse(&remaining[..=end])?);
+            remaining = &remaining[end + 2..];
+        }
+        cv_params.push(Self::parse(remaining)?);
+        Some(cv_params)
+    }
+
+    /// Returns the label of the controlled vocabulary
+    ///
+    pub fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the accession
+    ///
+    pub fn get_accession(&self) -> &str {
+        &self.accession
+    }
+
+    /// Returns the name
+    ///
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value, empty if none
+    ///
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for CvParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}, {}, {}]", self.label, self.accession, self.name, self.value)
+    }
+}
@@ -0,0 +1,237 @@
+// 3rd party imports
+use indexmap::IndexMap;
+
+// internal imports
+use crate::mztab::cv_param::CvParam;
+use crate::mztab::section::Section;
+
+/// Keys of the metadata mandatory in mzTab 1.0 files
+const MANDATORY_KEYS_V1: [&str; 7] = [
+    "mzTab-version",
+    "mzTab-mode",
+    "mzTab-type",
+    "description",
+    "ms_run[1]-location",
+    "fixed_mod[1]",
+    "variable_mod[1]"
+];
+
+/// Keys of the metadata mandatory in mzTab 2.0-M files
+const MANDATORY_KEYS_V2_M: [&str; 18] = [
+    "mzTab-version",
+    "mzTab-ID",
+    "software[1]",
+    "quantification_method",
+    "small_molecule-quantification_unit",
+    "small_molecule_feature-quantification_unit",
+    "id_confidence_measure[1]",
+    "ms_run[1]-location",
+    "assay[1]",
+    "study_variable[1]",
+    "cv[1]-label",
+    "cv[1]-full_name",
+    "cv[1]-version",
+    "cv[1]-uri",
+    "database[1]",
+    "database[1]-prefix",
+    "database[1]-version",
+    "database[1]-uri"
+];
+
+/// mzTab version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    /// mzTab 1.0 for proteomics and metabolomics
+    V1,
+    /// mzTab-M 2.0 for metabolomics
+    V2M
+}
+
+impl Version {
+    /// Parses the version of `mzTab-version`, None if unsupported.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Version, e.g. `1.0.0` or `2.0.0-M`
+    ///
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value {
+            "1.0.0" | "1.0" => Some(Self::V1),
+            "2.0.0-M" | "2.0-M" => Some(Self::V2M),
+            _ => None
+        }
+    }
+
+    /// Returns the version as written to `mzTab-version`
+    ///
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V1 => "1.0.0",
+            Self::V2M => "2.0.0-M"
+        }
+    }
+}
+
+/// Metadata section of a mzTab file (`MTD` lines), kept in the original order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    entries: IndexMap<String, String>
+}
+
+impl Metadata {
+    /// Creates empty metadata
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an entry, keeping the position of an existing key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key, e.g. `mzTab-version` or `ms_run[1]-location`
+    /// * `value` - Value
+    ///
+    pub fn with_entry(mut self, key: &str, value: String) -> Self {
+        self.set_entry(key, value);
+        self
+    }
+
+    /// Sets an entry, keeping the position of an existing key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key, e.g. `mzTab-version` or `ms_run[1]-location`
+    /// * `value` - Value
+    ///
+    pub fn set_entry(&mut self, key: &str, value: String) {
+        self.entries.insert(key.to_string(), value);
+    }
+
+    /// Returns the entries in their original order
+    ///
+    pub fn get_entries(&self) -> &IndexMap<String, String> {
+        &self.entries
+    }
+
+    /// Returns the value of the given key, None if missing or `null`
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key
+    ///
+    pub fn get_entry(&self, key: &str) -> Option<&str> {
+        self.entries.get(key)
+            .map(|value| value.as_str())
+            .filter(|value| !value.is_empty() && *value != "null")
+    }
+
+    /// Returns the value of the given key as parameter, None if missing or malformed
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key, e.g. `psm_search_engine_score[1]`
+    ///
+    pub fn get_cv_param(&self, key: &str) -> Option<CvParam> {
+        self.get_entry(key).and_then(CvParam::parse)
+    }
+
+    /// Returns the version, None if missing or unsupported
+    ///
+    pub fn get_version(&self) -> Option<Version> {
+        self.get_entry("mzTab-version").and_then(Version::from_name)
+    }
+
+    /// Returns the mode of mzTab 1.0 files, e.g. `Summary` or `Complete`
+    ///
+    pub fn get_mode(&self) -> Option<&str> {
+        self.get_entry("mzTab-mode")
+    }
+
+    /// Returns the type of mzTab 1.0 files, e.g. `Identification` or `Quantification`
+    ///
+    pub fn get_type(&self) -> Option<&str> {
+        self.get_entry("mzTab-type")
+    }
+
+    /// Returns the ID of the file
+    ///
+    pub fn get_id(&self) -> Option<&str> {
+        self.get_entry("mzTab-ID")
+    }
+
+    /// Returns the description of the file
+    ///
+    pub fn get_description(&self) -> Option<&str> {
+        self.get_entry("description")
+    }
+
+    /// Returns the locations of the MS runs (`ms_run[n]-location`) in order of their index
+    ///
+    pub fn get_ms_run_locations(&self) -> Vec<&str> {
+        (1..)
+            .map_while(|index| self.get_entry(&format!("ms_run[{}]-location", index)))
+            .collect()
+    }
+
+    /// Checks the presence of the mandatory metadata of the file's version.
+    /// Returns a message naming the first problem.
+    ///
+    pub fn validate(&self) -> std::result::Result<Version, String> {
+        let version = match self.entries.get("mzTab-version") {
+            Some(version) => Version::from_name(version)
+                .ok_or_else(|| format!("unsupported mzTab version `{}`", version))?,
+            None => return Err("missing mandatory metadata `mzTab-version`".to_string())
+        };
+        let mandatory_keys: &[&str] = match version {
+            Version::V1 => &MANDATORY_KEYS_V1,
+            Version::V2M => &MANDATORY_KEYS_V2_M
+        };
+        for key in mandatory_keys {
+            self.validate_key(key)?;
+        }
+        if version == Version::V1 {
+            if self.get_mode() == Some("Complete") {
+                self.validate_key("software[1]")?;
+            }
+            if self.get_type() == Some("Quantification") {
+                self.validate_key("quantification_method")?;
+            }
+        }
+        Ok(version)
+    }
+
+    /// Checks the presence of the metadata required by the given section,
+    /// e.g. `psm_search_engine_score[1]` for PSMs in mzTab 1.0 files.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version of the file
+    /// * `section` - Section
+    ///
+    pub fn validate_section(&self, version: Version, section: Section) -> std::result::Result<(), String> {
+        match (version, section) {
+            (Version::V1, Section::Protein) => self.validate_key("protein_search_engine_score[1]"),
+            (Version::V1, Section::Peptide) => self.validate_key("peptide_search_engine_score[1]"),
+            (Version::V1, Section::Psm) => self.validate_key("psm_search_engine_score[1]"),
+            (Version::V1, Section::SmallMolecule) => self.validate_key("smallmolecule_search_engine_score[1]"),
+            (Version::V2M, Section::SmallMolecule) => Ok(()),
+            (Version::V2M, _) => Err(format!(
+                "section {} is not part of mzTab {}", section.get_row_prefix(), version.as_str()
+            ))
+        }
+    }
+
+    /// Checks the presence of the given key
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key
+    ///
+    fn validate_key(&self, key: &str) -> std::result::Result<(), String> {
+        match self.entries.contains_key(key) {
+            true => Ok(()),
+            false => Err(format!("missing mandatory metadata `{}`", key))
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Reading and writing of mzTab 1.0 and mzTab-M 2.0 files.
+//! The metadata (`MTD`) is validated for the mandatory entries of the file's version.
+//! Rows of the protein, peptide, PSM and small molecule sections keep all columns as text
+//! and provide typed getters for the standard columns. Parameters like `[MS, MS:1001207, Mascot, ]`
+//! are parsed into a `CvParam`.
+
+pub mod cv_param;
+pub mod metadata;
+pub mod reader;
+pub mod row;
+pub mod section;
+pub mod writer;
+
+#[cfg(test)]
+mod test {
+    // std imports
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+
+    // 3rd party imports
+    use fallible_iterator::FallibleIterator;
+
+    // internal imports
+    use super::*;
+    use crate::error::Error;
+    use crate::mztab::row::MzTabRow;
+
+    const MZTAB_FILE_PATH_STR: &str = "../test_files/mztab/small.mztab";
+    const MZTAB_M_FILE_PATH_STR: &str = "../test_files/mztab/small_m.mztab";
+
+    #[test]
+    /// Reads the metadata and rows of all sections of a mzTab 1.0 file.
+    fn test_reading() {
+        let mut reader = reader::Reader::new(Path::new(MZTAB_FILE_PATH_STR), 1024).unwrap();
+        assert_eq!(reader.get_version().unwrap(), metadata::Version::V1);
+        let metadata = reader.get_metadata().unwrap();
+        assert_eq!(metadata.get_entries().len(), 11);
+        assert_eq!(metadata.get_mode(), Some("Summary"));
+        assert_eq!(metadata.get_ms_run_locations(), vec!["file:///data/run1.mzML"]);
+        assert_eq!(
+            metadata.get_cv_param("psm_search_engine_score[1]").unwrap().get_name(),
+            "Mascot:score"
+        );
+
+        let records: Vec<row::Record> = reader.collect().unwrap();
+        assert_eq!(records.len(), 6);
+        let sections: Vec<section::Section> = records.iter().map(|record| record.get_section()).collect();
+        assert_eq!(sections, vec![
+            section::Section::Protein,
+            section::Section::Protein,
+            section::Section::Peptide,
+            section::Section::Psm,
+            section::Section::Psm,
+            section::Section::SmallMolecule
+        ]);
+
+        let row::Record::Protein(protein) = &records[0] else { panic!("expected protein") };
+        assert_eq!(protein.get_accession(), Some("P02768"));
+        assert_eq!(protein.get_taxid(), Some(9606));
+        assert_eq!(protein.get_best_search_engine_score(1), Some(72.5));
+        assert_eq!(protein.get_ambiguity_members(), vec!["P02769", "P02770"]);
+        assert_eq!(protein.get_modifications(), None);
+        assert_eq!(protein.get_column("opt_global_note"), Some("first"));
+        let row::Record::Protein(protein) = &records[1] else { panic!("expected protein") };
+        assert_eq!(protein.get_search_engines().len(), 2);
+        assert_eq!(protein.get_search_engines()[1].get_name(), "X!Tandem");
+        assert!(protein.get_ambiguity_members().is_empty());
+
+        let row::Record::Peptide(peptide) = &records[2] else { panic!("expected peptide") };
+        assert_eq!(peptide.is_unique(), Some(true));
+        assert_eq!(peptide.get_mass_to_charge(), Some(575.3111));
+
+        let row::Record::Psm(psm) = &records[4] else { panic!("expected PSM") };
+        assert_eq!(psm.get_sequence(), Some("MCTVASLR"));
+        assert_eq!(psm.get_psm_id(), Some("2"));
+        assert_eq!(psm.get_search_engine_score(1), Some(30.7));
+        assert_eq!(psm.get_modifications(), Some("1-UNIMOD:35,2-UNIMOD:4"));
+        assert_eq!(psm.get_retention_times(), vec![980.5, 981.0]);
+        assert_eq!(psm.get_charge(), Some(2));
+        assert_eq!(psm.get_exp_mass_to_charge(), Some(483.2301));
+        assert_eq!(psm.get_spectra_refs(), vec!["ms_run[1]:index=7", "ms_run[1]:index=8"]);
+        assert_eq!((psm.get_start(), psm.get_end()), (Some(199), Some(206)));
+
+        let row::Record::SmallMolecule(small_molecule) = &records[5] else { panic!("expected small molecule") };
+        assert_eq!(small_molecule.get_identifiers(), vec!["CID:2519"]);
+        assert_eq!(small_molecule.get_name(), Some("Caffeine"));
+        assert_eq!(small_molecule.get_best_score(1), Some(0.9));
+    }
+
+    #[test]
+    /// Reads a mzTab-M 2.0 file, skipping comments, features and evidences.
+    fn test_reading_mztab_m() {
+        let mut reader = reader::Reader::new(Path::new(MZTAB_M_FILE_PATH_STR), 1024).unwrap();
+        assert_eq!(reader.get_version().unwrap(), metadata::Version::V2M);
+        assert_eq!(reader.get_metadata().unwrap().get_id(), Some("EXAMPLE-1"));
+        let records: Vec<row::Record> = reader.collect().unwrap();
+        assert_eq!(records.len(), 1);
+        let row::Record::SmallMolecule(small_molecule) = &records[0] else { panic!("expected small molecule") };
+        assert_eq!(small_molecule.get_sml_id(), Some(1));
+        assert_eq!(small_molecule.get_identifiers(), vec!["hmdb:HMDB0001847"]);
+        assert_eq!(small_molecule.get_name(), Some("Caffeine"));
+        assert_eq!(small_molecule.get_theoretical_neutral_masses(), vec![194.0804]);
+        assert_eq!(small_molecule.get_best_score(1), Some(0.95));
+        assert_eq!(small_molecule.get_column("abundance_assay[1]"), Some("40523.2"));
+    }
+
+    #[test]
+    /// Writes the read metadata and rows, which reproduces the file.
+    fn test_writing() {
+        let mut reader = reader::Reader::new(Path::new(MZTAB_FILE_PATH_STR), 1024).unwrap();
+        let metadata = reader.get_metadata().unwrap().clone();
+        let records: Vec<row::Record> = reader.collect().unwrap();
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        let mut written_bytes = writer.write_metadata(&metadata).unwrap();
+        written_bytes += writer.write_all(records.iter()).unwrap();
+        writer.flush().unwrap();
+        let buffer = writer.into_inner();
+        assert_eq!(written_bytes, buffer.len());
+        assert_eq!(String::from_utf8(buffer).unwrap(), fs::read_to_string(MZTAB_FILE_PATH_STR).unwrap());
+    }
+
+    #[test]
+    /// Checks that misuse of the writer is reported.
+    fn test_writing_misuse() {
+        let mut reader = reader::Reader::new(Path::new(MZTAB_FILE_PATH_STR), 1024).unwrap();
+        let metadata = reader.get_metadata().unwrap().clone();
+        let records: Vec<row::Record> = reader.collect().unwrap();
+        let is_invalid_input = |error: Error| matches!(
+            error, Error::Io { source, .. } if source.kind() == io::ErrorKind::InvalidInput
+        );
+
+        // Rows before metadata
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        assert!(is_invalid_input(writer.write_record(&records[0]).unwrap_err()));
+
+        // Sections out of order
+        writer.write_metadata(&metadata).unwrap();
+        writer.write_record(&records[3]).unwrap();
+        assert!(is_invalid_input(writer.write_record(&records[0]).unwrap_err()));
+
+        // Missing mandatory column
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_metadata(&metadata).unwrap();
+        let psm = row::Psm::default().with_column("sequence", "PEPTIDE".to_string());
+        assert!(is_invalid_input(writer.write_row(&psm).unwrap_err()));
+
+        // Invalid metadata
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        let metadata = metadata::Metadata::new().with_entry("mzTab-version", "1.0.0".to_string());
+        assert!(is_invalid_input(writer.write_metadata(&metadata).unwrap_err()));
+    }
+
+    #[test]
+    /// Parses single parameters and lists of parameters.
+    fn test_cv_param_parsing() {
+        let cv_param = cv_param::CvParam::parse("[MS, MS:1001207, Mascot, ]").unwrap();
+        assert_eq!(cv_param.get_label(), "MS");
+        assert_eq!(cv_param.get_accession(), "MS:1001207");
+        assert_eq!(cv_param.get_name(), "Mascot");
+        assert_eq!(cv_param.get_value(), "");
+        assert_eq!(cv_param.to_string(), "[MS, MS:1001207, Mascot, ]");
+
+        let cv_param = cv_param::CvParam::parse("[, , name, with comma, 42]").unwrap();
+        assert_eq!(cv_param.get_name(), "name, with comma");
+        assert_eq!(cv_param.get_value(), "42");
+
+        let cv_params = cv_param::CvParam::parse_list("[MS, MS:1001207, Mascot, ]|[, , a|b, 1]").unwrap();
+        assert_eq!(cv_params.len(), 2);
+        assert_eq!(cv_params[1].get_name(), "a|b");
+
+        assert_eq!(cv_param::CvParam::parse("MS, MS:1001207, Mascot"), None);
+        assert_eq!(cv_param::CvParam::parse("[MS, MS:1001207]"), None);
+    }
+
+    #[test]
+    /// Checks that missing metadata and malformed rows are reported.
+    fn test_reading_invalid_content() {
+        let metadata = fs::read_to_string(MZTAB_FILE_PATH_STR).unwrap()
+            .split("\n\n")
+            .next()
+            .unwrap()
+            .to_string();
+        let is_invalid_content = |content: &str, expected_line_number: usize| {
+            let error = reader::Reader::from_reader(content.as_bytes()).collect::<Vec<row::Record>>().unwrap_err();
+            matches!(error, Error::InvalidContent { .. })
+                && error.get_position().unwrap().get_line_number() == Some(expected_line_number)
+        };
+
+        // Missing mandatory metadata
+        let content = metadata.replace("MTD\tdescription\tSmall example of an identification\n", "");
+        assert!(is_invalid_content(&content, 10));
+        assert!(is_invalid_content("MTD\tmzTab-version\t3.0.0\n", 1));
+
+        // Missing mandatory column
+        let content = format!("{}\n\nPSH\tsequence\tPSM_ID\n", metadata);
+        assert!(is_invalid_content(&content, 13));
+
+        // Row without header, wrong number of values and malformed values
+        let header = "PEH\tsequence\taccession\tunique\tdatabase\tdatabase_version\tsearch_engine\t\
+            best_search_engine_score[1]\tmodifications\tretention_time\tretention_time_window\tcharge\tmass_to_charge";
+        let row = "PEP\tPEPTIDE\tP1\t1\tnull\tnull\t[MS, MS:1001207, Mascot, ]\t1.0\tnull\t10.5\tnull\t2\t400.2";
+        assert!(is_invalid_content(&format!("{}\n{}\n", metadata, row), 12));
+        assert!(is_invalid_content(&format!("{}\n{}\n{}\tnull\n", metadata, header, row), 13));
+        assert!(is_invalid_content(&format!("{}\n{}\n{}\n", metadata, header, row.replace("\t2\t", "\tx\t")), 13));
+        assert!(is_invalid_content(&format!("{}\n{}\n{}\n", metadata, header, row.replace("\t1\t", "\tyes\t")), 13));
+
+        // Metadata after rows
+        assert!(is_invalid_content(&format!("{}\n{}\n{}\nMTD\ta\tb\n", metadata, header, row), 14));
+
+        // Section not allowed in mzTab-M
+        let content = fs::read_to_string(MZTAB_M_FILE_PATH_STR).unwrap().replace("SMH\t", "PSH\t");
+        assert!(is_invalid_content(&content, 22));
+
+        let content = format!("{}\n{}\n{}\n", metadata, header, row);
+        let records: Vec<row::Record> = reader::Reader::from_reader(content.as_bytes()).collect().unwrap();
+        assert_eq!(records.len(), 1);
+    }
+}
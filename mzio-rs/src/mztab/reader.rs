@@ -0,0 +1,227 @@
+// std imports
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+use indexmap::IndexMap;
+
+// internal imports
+use crate::compression::open_reader;
+use crate::error::{Error, Position, Result};
+use crate::line_reader::LineReader;
+use crate::mztab::metadata::{Metadata, Version};
+use crate::mztab::row::Record;
+use crate::mztab::section::Section;
+
+/// Prefixes of the mzTab-M 2.0 feature and evidence sections, which are skipped
+const SKIPPED_PREFIXES: [&str; 4] = ["SFH", "SMF", "SEH", "SME"];
+
+/// Reader for mzTab 1.0 and mzTab-M 2.0 files.
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+/// The metadata is read and validated before the first row, the rows of the protein, peptide, PSM
+/// and small molecule sections are returned in order of the file.
+/// Comments (`COM`) and the small molecule feature and evidence sections of mzTab-M are skipped.
+pub struct Reader<R: BufRead> {
+    internal_reader: LineReader<R>,
+    metadata: Option<(Metadata, Version)>,
+    // First line after the metadata, which was read while reading the metadata
+    pending_line: Option<(String, Position)>,
+    header: Option<(Section, Vec<String>)>
+}
+
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given mzTab file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
+    ///
+    /// # Arguments
+    ///
+    /// * `mztab_file_path` - Path to mzTab file
+    /// * `buffer_size` - Size of the internal read buffer
+    ///
+    pub fn new(mztab_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Ok(Self::from_line_reader(LineReader::new(
+            open_reader(mztab_file_path, buffer_size)?,
+            Some(mztab_file_path.to_path_buf())
+        )))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the mzTab content
+    ///
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self::from_line_reader(LineReader::new(internal_reader, None))
+    }
+
+    /// Creates a new Reader from a line reader
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Line reader providing the mzTab content
+    ///
+    fn from_line_reader(internal_reader: LineReader<R>) -> Self {
+        Self {
+            internal_reader,
+            metadata: None,
+            pending_line: None,
+            header: None
+        }
+    }
+
+    /// Returns the metadata, reading and validating it if not done yet.
+    ///
+    pub fn get_metadata(&mut self) -> Result<&Metadata> {
+        Ok(&self.read_metadata()?.0)
+    }
+
+    /// Returns the version, reading and validating the metadata if not done yet.
+    ///
+    pub fn get_version(&mut self) -> Result<Version> {
+        Ok(self.read_metadata()?.1)
+    }
+
+    /// Reads the `MTD` lines up to the first line of another section and validates the mandatory metadata.
+    ///
+    fn read_metadata(&mut self) -> Result<&(Metadata, Version)> {
+        if self.metadata.is_none() {
+            let mut metadata = Metadata::new();
+            let mut line = String::new();
+            while self.internal_reader.read_line(&mut line)? > 0 {
+                let fields: Vec<&str> = line.trim_end_matches(['\r', '\n']).split('\t').collect();
+                match fields[0] {
+                    "MTD" if fields.len() >= 3 => metadata.set_entry(fields[1].trim(), fields[2].trim().to_string()),
+                    "MTD" => return Err(invalid_content(
+                        format!("metadata line needs a key and a value, got `{}`", line.trim()),
+                        self.internal_reader.get_position()
+                    )),
+                    "COM" | "" => (),
+                    _ => {
+                        self.pending_line = Some((line, self.internal_reader.get_position()));
+                        break;
+                    }
+                }
+            }
+            let version = metadata.validate()
+                .map_err(|message| invalid_content(message, self.internal_reader.get_position()))?;
+            self.metadata = Some((metadata, version));
+        }
+        // Set above if missing
+        Ok(self.metadata.as_ref().unwrap())
+    }
+
+    /// Reads the next line, which is not empty or a comment.
+    /// Returns the line and its position, None on EOF.
+    ///
+    fn read_content_line(&mut self) -> Result<Option<(String, Position)>> {
+        if let Some(pending_line) = self.pending_line.take() {
+            return Ok(Some(pending_line));
+        }
+        let mut line = String::new();
+        while self.internal_reader.read_line(&mut line)? > 0 {
+            let trimmed_line = line.trim_end_matches(['\r', '\n']);
+            if !trimmed_line.is_empty() && !trimmed_line.starts_with("COM") {
+                return Ok(Some((trimmed_line.to_string(), self.internal_reader.get_position())));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks a section header and makes it the current one.
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - Section of the header
+    /// * `columns` - Column names
+    /// * `position` - Position of the header line
+    ///
+    fn read_header(&mut self, section: Section, columns: &[&str], position: Position) -> Result<()> {
+        let (metadata, version) = self.read_metadata()?;
+        let version = *version;
+        if let Err(message) = metadata.validate_section(version, section) {
+            return Err(invalid_content(message, position));
+        }
+        if let Some(column) = section.get_mandatory_columns(version).iter().find(|column| !columns.contains(column)) {
+            return Err(invalid_content(
+                format!("header {} misses mandatory column `{}`", section.get_header_prefix(), column),
+                position
+            ));
+        }
+        self.header = Some((section, columns.iter().map(|column| column.to_string()).collect()));
+        Ok(())
+    }
+
+    /// Maps the values of a row to the columns of the current header and checks their types.
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - Section of the row
+    /// * `values` - Values
+    /// * `position` - Position of the row line
+    ///
+    fn read_row(&self, section: Section, values: &[&str], position: Position) -> Result<Record> {
+        let columns = match &self.header {
+            Some((header_section, columns)) if *header_section == section => columns,
+            _ => return Err(invalid_content(
+                format!("{} row without preceding {} header", section.get_row_prefix(), section.get_header_prefix()),
+                position
+            ))
+        };
+        if values.len() != columns.len() {
+            return Err(invalid_content(
+                format!("expected {} values as defined by the header, got {}", columns.len(), values.len()),
+                position
+            ));
+        }
+        let mut row: IndexMap<String, String> = IndexMap::with_capacity(columns.len());
+        for (column, value) in columns.iter().zip(values) {
+            if *value != "null" && !value.is_empty() && !section.get_column_type(column).is_valid(value) {
+                return Err(invalid_content(format!("invalid value `{}` of column `{}`", value, column), position));
+            }
+            row.insert(column.clone(), value.to_string());
+        }
+        Ok(Record::new(section, row))
+    }
+
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
+    type Item = Record;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        self.read_metadata()?;
+        while let Some((line, position)) = self.read_content_line()? {
+            let fields: Vec<&str> = line.split('\t').map(|field| field.trim()).collect();
+            let prefix = fields[0];
+            if let Some(section) = Section::from_header_prefix(prefix) {
+                self.read_header(section, &fields[1..], position)?;
+            } else if let Some(section) = Section::from_row_prefix(prefix) {
+                return self.read_row(section, &fields[1..], position).map(Some);
+            } else if prefix == "MTD" {
+                return Err(invalid_content("metadata after the table sections".to_string(), position));
+            } else if !SKIPPED_PREFIXES.contains(&prefix) {
+                return Err(invalid_content(format!("unknown line prefix `{}`", prefix), position));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Creates an error for invalid content
+///
+/// # Arguments
+///
+/// * `message` - Message describing the problem
+/// * `position` - Position of the problem
+///
+fn invalid_content(message: String, position: Position) -> Error {
+    Error::InvalidContent {
+        message,
+        position
+    }
+}
@@ -0,0 +1,558 @@
+// 3rd party imports
+use indexmap::IndexMap;
+
+// internal imports
+use crate::mztab::cv_param::CvParam;
+use crate::mztab::section::Section;
+
+/// Row of one of the table sections. The values are kept as text in order of the section header,
+/// so optional and custom columns (`opt_...`) are preserved. The typed getters return None
+/// for missing, `null` or malformed values.
+pub trait MzTabRow: Sized {
+    /// Section of the row
+    const SECTION: Section;
+
+    /// Creates a row from the values, keyed by column name
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - Values keyed by column name in order of the header
+    ///
+    fn from_columns(columns: IndexMap<String, String>) -> Self;
+
+    /// Returns the values keyed by column name in order of the header
+    ///
+    fn get_columns(&self) -> &IndexMap<String, String>;
+
+    /// Returns the values keyed by column name for modification
+    ///
+    fn get_columns_mut(&mut self) -> &mut IndexMap<String, String>;
+
+    /// Returns the value of the given column, None if missing or `null`
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - Column name, e.g. `accession`
+    ///
+    fn get_column(&self, column: &str) -> Option<&str> {
+        self.get_columns()
+            .get(column)
+            .map(|value| value.as_str())
+            .filter(|value| !value.is_empty() && *value != "null")
+    }
+
+    /// Sets the value of the given column, keeping the position of an existing column
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - Column name, e.g. `accession`
+    /// * `value` - Value
+    ///
+    fn with_column(mut self, column: &str, value: String) -> Self {
+        self.get_columns_mut().insert(column.to_string(), value);
+        self
+    }
+}
+
+/// Protein (`PRT`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Protein {
+    columns: IndexMap<String, String>
+}
+
+impl Protein {
+    /// Returns the accession
+    ///
+    pub fn get_accession(&self) -> Option<&str> {
+        self.get_column("accession")
+    }
+
+    /// Returns the description
+    ///
+    pub fn get_description(&self) -> Option<&str> {
+        self.get_column("description")
+    }
+
+    /// Returns the NCBI taxonomy ID
+    ///
+    pub fn get_taxid(&self) -> Option<i64> {
+        parse_column(self, "taxid")
+    }
+
+    /// Returns the species
+    ///
+    pub fn get_species(&self) -> Option<&str> {
+        self.get_column("species")
+    }
+
+    /// Returns the search engines
+    ///
+    pub fn get_search_engines(&self) -> Vec<CvParam> {
+        parse_cv_param_list_column(self, "search_engine")
+    }
+
+    /// Returns the best search engine score of the given index over all MS runs
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the score, as defined by `protein_search_engine_score[index]`
+    ///
+    pub fn get_best_search_engine_score(&self, index: usize) -> Option<f64> {
+        parse_float_column(self, &format!("best_search_engine_score[{}]", index))
+    }
+
+    /// Returns the accessions of proteins, which cannot be distinguished from this one
+    ///
+    pub fn get_ambiguity_members(&self) -> Vec<&str> {
+        split_list_column(self, "ambiguity_members", ',')
+    }
+
+    /// Returns the modifications
+    ///
+    pub fn get_modifications(&self) -> Option<&str> {
+        self.get_column("modifications")
+    }
+}
+
+impl MzTabRow for Protein {
+    const SECTION: Section = Section::Protein;
+
+    fn from_columns(columns: IndexMap<String, String>) -> Self {
+        Self {
+            columns
+        }
+    }
+
+    fn get_columns(&self) -> &IndexMap<String, String> {
+        &self.columns
+    }
+
+    fn get_columns_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.columns
+    }
+}
+
+/// Peptide (`PEP`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Peptide {
+    columns: IndexMap<String, String>
+}
+
+impl Peptide {
+    /// Returns the sequence
+    ///
+    pub fn get_sequence(&self) -> Option<&str> {
+        self.get_column("sequence")
+    }
+
+    /// Returns the accession of the protein
+    ///
+    pub fn get_accession(&self) -> Option<&str> {
+        self.get_column("accession")
+    }
+
+    /// Returns true if the peptide is unique for the protein
+    ///
+    pub fn is_unique(&self) -> Option<bool> {
+        parse_bool_column(self, "unique")
+    }
+
+    /// Returns the search engines
+    ///
+    pub fn get_search_engines(&self) -> Vec<CvParam> {
+        parse_cv_param_list_column(self, "search_engine")
+    }
+
+    /// Returns the best search engine score of the given index over all MS runs
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the score, as defined by `peptide_search_engine_score[index]`
+    ///
+    pub fn get_best_search_engine_score(&self, index: usize) -> Option<f64> {
+        parse_float_column(self, &format!("best_search_engine_score[{}]", index))
+    }
+
+    /// Returns the modifications
+    ///
+    pub fn get_modifications(&self) -> Option<&str> {
+        self.get_column("modifications")
+    }
+
+    /// Returns the retention times in seconds
+    ///
+    pub fn get_retention_times(&self) -> Vec<f64> {
+        parse_float_list_column(self, "retention_time")
+    }
+
+    /// Returns the charge
+    ///
+    pub fn get_charge(&self) -> Option<i32> {
+        parse_column(self, "charge")
+    }
+
+    /// Returns the m/z
+    ///
+    pub fn get_mass_to_charge(&self) -> Option<f64> {
+        parse_float_column(self, "mass_to_charge")
+    }
+}
+
+impl MzTabRow for Peptide {
+    const SECTION: Section = Section::Peptide;
+
+    fn from_columns(columns: IndexMap<String, String>) -> Self {
+        Self {
+            columns
+        }
+    }
+
+    fn get_columns(&self) -> &IndexMap<String, String> {
+        &self.columns
+    }
+
+    fn get_columns_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.columns
+    }
+}
+
+/// Peptide spectrum match (`PSM`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Psm {
+    columns: IndexMap<String, String>
+}
+
+impl Psm {
+    /// Returns the sequence
+    ///
+    pub fn get_sequence(&self) -> Option<&str> {
+        self.get_column("sequence")
+    }
+
+    /// Returns the ID of the PSM, which is shared by the rows of the same PSM mapping to different proteins
+    ///
+    pub fn get_psm_id(&self) -> Option<&str> {
+        self.get_column("PSM_ID")
+    }
+
+    /// Returns the accession of the protein
+    ///
+    pub fn get_accession(&self) -> Option<&str> {
+        self.get_column("accession")
+    }
+
+    /// Returns true if the peptide is unique for the protein
+    ///
+    pub fn is_unique(&self) -> Option<bool> {
+        parse_bool_column(self, "unique")
+    }
+
+    /// Returns the search engines
+    ///
+    pub fn get_search_engines(&self) -> Vec<CvParam> {
+        parse_cv_param_list_column(self, "search_engine")
+    }
+
+    /// Returns the search engine score of the given index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the score, as defined by `psm_search_engine_score[index]`
+    ///
+    pub fn get_search_engine_score(&self, index: usize) -> Option<f64> {
+        parse_float_column(self, &format!("search_engine_score[{}]", index))
+    }
+
+    /// Returns the modifications
+    ///
+    pub fn get_modifications(&self) -> Option<&str> {
+        self.get_column("modifications")
+    }
+
+    /// Returns the retention times in seconds
+    ///
+    pub fn get_retention_times(&self) -> Vec<f64> {
+        parse_float_list_column(self, "retention_time")
+    }
+
+    /// Returns the charge
+    ///
+    pub fn get_charge(&self) -> Option<i32> {
+        parse_column(self, "charge")
+    }
+
+    /// Returns the experimental m/z
+    ///
+    pub fn get_exp_mass_to_charge(&self) -> Option<f64> {
+        parse_float_column(self, "exp_mass_to_charge")
+    }
+
+    /// Returns the calculated m/z
+    ///
+    pub fn get_calc_mass_to_charge(&self) -> Option<f64> {
+        parse_float_column(self, "calc_mass_to_charge")
+    }
+
+    /// Returns the references to the spectra, e.g. `ms_run[1]:index=5`
+    ///
+    pub fn get_spectra_refs(&self) -> Vec<&str> {
+        split_list_column(self, "spectra_ref", '|')
+    }
+
+    /// Returns the amino acid preceding the peptide
+    ///
+    pub fn get_pre(&self) -> Option<&str> {
+        self.get_column("pre")
+    }
+
+    /// Returns the amino acid following the peptide
+    ///
+    pub fn get_post(&self) -> Option<&str> {
+        self.get_column("post")
+    }
+
+    /// Returns the start of the peptide in the protein (1-based)
+    ///
+    pub fn get_start(&self) -> Option<usize> {
+        parse_column(self, "start")
+    }
+
+    /// Returns the end of the peptide in the protein (1-based, inclusive)
+    ///
+    pub fn get_end(&self) -> Option<usize> {
+        parse_column(self, "end")
+    }
+}
+
+impl MzTabRow for Psm {
+    const SECTION: Section = Section::Psm;
+
+    fn from_columns(columns: IndexMap<String, String>) -> Self {
+        Self {
+            columns
+        }
+    }
+
+    fn get_columns(&self) -> &IndexMap<String, String> {
+        &self.columns
+    }
+
+    fn get_columns_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.columns
+    }
+}
+
+/// Small molecule (`SML`). The getters handle the columns of mzTab 1.0 and 2.0-M.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SmallMolecule {
+    columns: IndexMap<String, String>
+}
+
+impl SmallMolecule {
+    /// Returns the identifiers, `identifier` in mzTab 1.0 and `database_identifier` in 2.0-M
+    ///
+    pub fn get_identifiers(&self) -> Vec<&str> {
+        match self.columns.contains_key("database_identifier") {
+            true => split_list_column(self, "database_identifier", '|'),
+            false => split_list_column(self, "identifier", '|')
+        }
+    }
+
+    /// Returns the ID of the row in mzTab 2.0-M
+    ///
+    pub fn get_sml_id(&self) -> Option<i64> {
+        parse_column(self, "SML_ID")
+    }
+
+    /// Returns the chemical formula
+    ///
+    pub fn get_chemical_formula(&self) -> Option<&str> {
+        self.get_column("chemical_formula")
+    }
+
+    /// Returns the SMILES
+    ///
+    pub fn get_smiles(&self) -> Option<&str> {
+        self.get_column("smiles")
+    }
+
+    /// Returns the name, `description` in mzTab 1.0 and `chemical_name` in 2.0-M
+    ///
+    pub fn get_name(&self) -> Option<&str> {
+        self.get_column("chemical_name").or_else(|| self.get_column("description"))
+    }
+
+    /// Returns the experimental m/z of mzTab 1.0
+    ///
+    pub fn get_exp_mass_to_charge(&self) -> Option<f64> {
+        parse_float_column(self, "exp_mass_to_charge")
+    }
+
+    /// Returns the charge of mzTab 1.0
+    ///
+    pub fn get_charge(&self) -> Option<i32> {
+        parse_column(self, "charge")
+    }
+
+    /// Returns the theoretical neutral masses of mzTab 2.0-M
+    ///
+    pub fn get_theoretical_neutral_masses(&self) -> Vec<f64> {
+        parse_float_list_column(self, "theoretical_neutral_mass")
+    }
+
+    /// Returns the best identification confidence value of mzTab 2.0-M,
+    /// or the best search engine score of the given index of mzTab 1.0.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the 1.0 score, as defined by `smallmolecule_search_engine_score[index]`
+    ///
+    pub fn get_best_score(&self, index: usize) -> Option<f64> {
+        parse_float_column(self, "best_id_confidence_value")
+            .or_else(|| parse_float_column(self, &format!("best_search_engine_score[{}]", index)))
+    }
+}
+
+impl MzTabRow for SmallMolecule {
+    const SECTION: Section = Section::SmallMolecule;
+
+    fn from_columns(columns: IndexMap<String, String>) -> Self {
+        Self {
+            columns
+        }
+    }
+
+    fn get_columns(&self) -> &IndexMap<String, String> {
+        &self.columns
+    }
+
+    fn get_columns_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.columns
+    }
+}
+
+/// Row of any section, as returned by `crate::mztab::reader::Reader`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Record {
+    Protein(Protein),
+    Peptide(Peptide),
+    Psm(Psm),
+    SmallMolecule(SmallMolecule)
+}
+
+impl Record {
+    /// Creates a row of the given section
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - Section of the row
+    /// * `columns` - Values keyed by column name in order of the header
+    ///
+    pub fn new(section: Section, columns: IndexMap<String, String>) -> Self {
+        match section {
+            Section::Protein => Self::Protein(Protein::from_columns(columns)),
+            Section::Peptide => Self::Peptide(Peptide::from_columns(columns)),
+            Section::Psm => Self::Psm(Psm::from_columns(columns)),
+            Section::SmallMolecule => Self::SmallMolecule(SmallMolecule::from_columns(columns))
+        }
+    }
+
+    /// Returns the section of the row
+    ///
+    pub fn get_section(&self) -> Section {
+        match self {
+            Self::Protein(_) => Section::Protein,
+            Self::Peptide(_) => Section::Peptide,
+            Self::Psm(_) => Section::Psm,
+            Self::SmallMolecule(_) => Section::SmallMolecule
+        }
+    }
+
+    /// Returns the values keyed by column name in order of the header
+    ///
+    pub fn get_columns(&self) -> &IndexMap<String, String> {
+        match self {
+            Self::Protein(row) => row.get_columns(),
+            Self::Peptide(row) => row.get_columns(),
+            Self::Psm(row) => row.get_columns(),
+            Self::SmallMolecule(row) => row.get_columns()
+        }
+    }
+}
+
+/// Parses the value of the given column, None if missing, `null` or malformed
+///
+/// # Arguments
+///
+/// * `row` - Row
+/// * `column` - Column name
+///
+fn parse_column<T: std::str::FromStr, R: MzTabRow>(row: &R, column: &str) -> Option<T> {
+    row.get_column(column).and_then(|value| value.trim().parse().ok())
+}
+
+/// Parses the float value of the given column, None if missing, `null` or malformed
+///
+/// # Arguments
+///
+/// * `row` - Row
+/// * `column` - Column name
+///
+fn parse_float_column<R: MzTabRow>(row: &R, column: &str) -> Option<f64> {
+    row.get_column(column).and_then(|value| fast_float::parse(value.trim()).ok())
+}
+
+/// Parses the boolean value (`0` or `1`) of the given column, None if missing, `null` or malformed
+///
+/// # Arguments
+///
+/// * `row` - Row
+/// * `column` - Column name
+///
+fn parse_bool_column<R: MzTabRow>(row: &R, column: &str) -> Option<bool> {
+    match row.get_column(column)?.trim() {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None
+    }
+}
+
+/// Parses the `|` separated floats of the given column, skipping malformed values
+///
+/// # Arguments
+///
+/// * `row` - Row
+/// * `column` - Column name
+///
+fn parse_float_list_column<R: MzTabRow>(row: &R, column: &str) -> Vec<f64> {
+    split_list_column(row, column, '|')
+        .into_iter()
+        .filter_map(|value| fast_float::parse(value).ok())
+        .collect()
+}
+
+/// Parses the `|` separated parameters of the given column, empty if missing or malformed
+///
+/// # Arguments
+///
+/// * `row` - Row
+/// * `column` - Column name
+///
+fn parse_cv_param_list_column<R: MzTabRow>(row: &R, column: &str) -> Vec<CvParam> {
+    row.get_column(column).and_then(CvParam::parse_list).unwrap_or_default()
+}
+
+/// Splits the value of the given column at the separator, empty if missing or `null`
+///
+/// # Arguments
+///
+/// * `row` - Row
+/// * `column` - Column name
+/// * `separator` - Separator
+///
+fn split_list_column<'a, R: MzTabRow>(row: &'a R, column: &str, separator: char) -> Vec<&'a str> {
+    match row.get_column(column) {
+        Some(value) => value.split(separator).map(|value| value.trim()).collect(),
+        None => Vec::new()
+    }
+}
@@ -0,0 +1,174 @@
+// internal imports
+use crate::mztab::cv_param::CvParam;
+use crate::mztab::metadata::Version;
+
+/// Table sections of a mzTab file, in the order they have to appear
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    /// Proteins (`PRH`/`PRT`)
+    Protein,
+    /// Peptides (`PEH`/`PEP`)
+    Peptide,
+    /// Peptide spectrum matches (`PSH`/`PSM`)
+    Psm,
+    /// Small molecules (`SMH`/`SML`)
+    SmallMolecule
+}
+
+/// Type of the values of a column, used for validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColumnType {
+    Text,
+    Int,
+    Float,
+    Bool,
+    /// `|` separated floats
+    FloatList,
+    CvParam,
+    /// `|` separated parameters
+    CvParamList
+}
+
+impl ColumnType {
+    /// Checks if the given value, which is not `null`, matches the type
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Value
+    ///
+    pub(crate) fn is_valid(&self, value: &str) -> bool {
+        match self {
+            Self::Text => true,
+            Self::Int => value.parse::<i64>().is_ok(),
+            Self::Float => is_float(value),
+            Self::Bool => value == "0" || value == "1",
+            Self::FloatList => value.split('|').all(is_float),
+            Self::CvParam => CvParam::parse(value).is_some(),
+            Self::CvParamList => CvParam::parse_list(value).is_some()
+        }
+    }
+}
+
+/// Checks if the value is a float, including `NaN` and `INF` as allowed by mzTab
+///
+/// # Arguments
+///
+/// * `value` - Value
+///
+fn is_float(value: &str) -> bool {
+    fast_float::parse::<f64, _>(value.trim()).is_ok()
+}
+
+impl Section {
+    /// Returns the section of the given header prefix, e.g. `PSH`
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Line prefix
+    ///
+    pub fn from_header_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "PRH" => Some(Self::Protein),
+            "PEH" => Some(Self::Peptide),
+            "PSH" => Some(Self::Psm),
+            "SMH" => Some(Self::SmallMolecule),
+            _ => None
+        }
+    }
+
+    /// Returns the section of the given row prefix, e.g. `PSM`
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Line prefix
+    ///
+    pub fn from_row_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "PRT" => Some(Self::Protein),
+            "PEP" => Some(Self::Peptide),
+            "PSM" => Some(Self::Psm),
+            "SML" => Some(Self::SmallMolecule),
+            _ => None
+        }
+    }
+
+    /// Returns the prefix of the header line
+    ///
+    pub fn get_header_prefix(&self) -> &'static str {
+        match self {
+            Self::Protein => "PRH",
+            Self::Peptide => "PEH",
+            Self::Psm => "PSH",
+            Self::SmallMolecule => "SMH"
+        }
+    }
+
+    /// Returns the prefix of the row lines
+    ///
+    pub fn get_row_prefix(&self) -> &'static str {
+        match self {
+            Self::Protein => "PRT",
+            Self::Peptide => "PEP",
+            Self::Psm => "PSM",
+            Self::SmallMolecule => "SML"
+        }
+    }
+
+    /// Returns the columns, which are mandatory in the given version
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - mzTab version
+    ///
+    pub fn get_mandatory_columns(&self, version: Version) -> &'static [&'static str] {
+        match (self, version) {
+            (Self::Protein, _) => &[
+                "accession", "description", "taxid", "species", "database", "database_version", "search_engine",
+                "best_search_engine_score[1]", "ambiguity_members", "modifications"
+            ],
+            (Self::Peptide, _) => &[
+                "sequence", "accession", "unique", "database", "database_version", "search_engine",
+                "best_search_engine_score[1]", "modifications", "retention_time", "retention_time_window", "charge",
+                "mass_to_charge"
+            ],
+            (Self::Psm, _) => &[
+                "sequence", "PSM_ID", "accession", "unique", "database", "database_version", "search_engine",
+                "search_engine_score[1]", "modifications", "retention_time", "charge", "exp_mass_to_charge",
+                "calc_mass_to_charge", "spectra_ref", "pre", "post", "start", "end"
+            ],
+            (Self::SmallMolecule, Version::V1) => &[
+                "identifier", "chemical_formula", "smiles", "inchi_key", "description", "exp_mass_to_charge",
+                "calc_mass_to_charge", "charge", "retention_time", "taxid", "species", "database", "database_version",
+                "spectra_ref", "search_engine", "best_search_engine_score[1]", "modifications"
+            ],
+            (Self::SmallMolecule, Version::V2M) => &[
+                "SML_ID", "SMF_ID_REFS", "database_identifier", "chemical_formula", "smiles", "inchi",
+                "chemical_name", "uri", "theoretical_neutral_mass", "adduct_ions", "reliability",
+                "best_id_confidence_measure", "best_id_confidence_value"
+            ]
+        }
+    }
+
+    /// Returns the type of the values of the given column
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - Column name
+    ///
+    pub(crate) fn get_column_type(&self, column: &str) -> ColumnType {
+        match column {
+            "unique" => ColumnType::Bool,
+            "taxid" | "charge" | "start" | "end" | "SML_ID" => ColumnType::Int,
+            "mass_to_charge" | "exp_mass_to_charge" | "calc_mass_to_charge" | "protein_coverage"
+                | "best_id_confidence_value" => ColumnType::Float,
+            "retention_time" | "retention_time_window" | "theoretical_neutral_mass" => ColumnType::FloatList,
+            "best_id_confidence_measure" => ColumnType::CvParam,
+            "search_engine" => ColumnType::CvParamList,
+            _ if column.starts_with("num_") => ColumnType::Int,
+            _ if column.starts_with("best_search_engine_score[")
+                || column.starts_with("search_engine_score[")
+                || column.contains("abundance_") => ColumnType::Float,
+            _ => ColumnType::Text
+        }
+    }
+}
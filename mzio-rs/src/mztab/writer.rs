@@ -0,0 +1,236 @@
+// std imports
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use indexmap::IndexMap;
+
+// internal imports
+use crate::compression::{Compression, Encoder};
+use crate::error::{Error, Result};
+use crate::mztab::metadata::{Metadata, Version};
+use crate::mztab::row::{MzTabRow, Record};
+use crate::mztab::section::Section;
+
+/// Writer for mzTab 1.0 and mzTab-M 2.0 files.
+/// Works on any target implementing `Write`, e.g. files, stdout or `Vec<u8>`.
+/// The metadata has to be written first, followed by the rows in order of their sections
+/// (proteins, peptides, PSMs, small molecules). The header of a section is written before its first row,
+/// its columns are the ones of the first row. Columns missing in later rows are written as `null`.
+/// Use flush() to make sure the buffer is written completely.
+pub struct Writer<W: Write> {
+    internal_writer: W,
+    version: Option<Version>,
+    metadata: Metadata,
+    header: Option<(Section, Vec<String>)>
+}
+
+impl Writer<BufWriter<File>> {
+    /// Creates a new buffered Writer for the given mzTab file
+    ///
+    /// # Arguments
+    ///
+    /// * `mztab_file_path` - Path to mzTab file
+    ///
+    pub fn new(mztab_file_path: &Path) -> Result<Self> {
+        let mztab_file: File = File::create(mztab_file_path)
+            .map_err(|err| Error::io_with_path(err, mztab_file_path))?;
+        Ok(Self::from_writer(BufWriter::new(mztab_file)))
+    }
+}
+
+impl Writer<Encoder<BufWriter<File>>> {
+    /// Creates a new buffered Writer for the given mzTab file, compressing the output.
+    /// Use finish() to complete the compressed file.
+    ///
+    /// # Arguments
+    ///
+    /// * `mztab_file_path` - Path to mzTab file
+    /// * `compression` - Compression of the written file, see `Compression::from_path()`
+    ///
+    pub fn with_compression(mztab_file_path: &Path, compression: Compression) -> Result<Self> {
+        Ok(Self::from_writer(Encoder::create(mztab_file_path, compression)?))
+    }
+}
+
+impl<W: Write> Writer<Encoder<W>> {
+    /// Flushes the buffer, completes the compressed stream and returns the underlying write target.
+    ///
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        self.internal_writer.finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new Writer for any write target.
+    /// The target is used as is, so wrap unbuffered targets in a `BufWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_writer` - Write target
+    ///
+    pub fn from_writer(internal_writer: W) -> Self {
+        Self {
+            internal_writer,
+            version: None,
+            metadata: Metadata::new(),
+            header: None
+        }
+    }
+
+    /// Returns the underlying write target, e.g. to access an in-memory buffer.
+    /// Make sure to call flush() first.
+    ///
+    pub fn into_inner(self) -> W {
+        self.internal_writer
+    }
+
+    /// Writes the complete byte slice.
+    /// Returns the number of written bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to write
+    ///
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.internal_writer.write_all(bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Validates and writes the metadata as `MTD` lines. Has to be called once before writing rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - Metadata
+    ///
+    pub fn write_metadata(&mut self, metadata: &Metadata) -> Result<usize> {
+        if self.version.is_some() {
+            return Err(invalid_input("metadata was already written"));
+        }
+        let version = metadata.validate().map_err(|message| invalid_input(&message))?;
+        let mut content = String::new();
+        for (key, value) in metadata.get_entries() {
+            content.push_str(&format!("MTD\t{}\t{}\n", key, value));
+        }
+        self.version = Some(version);
+        self.metadata = metadata.clone();
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Writes a row of any section, preceded by the section header if the section changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row, e.g. a `Psm`
+    ///
+    pub fn write_row<T: MzTabRow>(&mut self, row: &T) -> Result<usize> {
+        self.write_columns(T::SECTION, row.get_columns())
+    }
+
+    /// Writes a record, preceded by the section header if the section changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - Record
+    ///
+    pub fn write_record(&mut self, record: &Record) -> Result<usize> {
+        self.write_columns(record.get_section(), record.get_columns())
+    }
+
+    /// Writes multiple records to file.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - Iterator of records
+    ///
+    pub fn write_all<'b, I>(&mut self, records: I) -> Result<usize>
+    where
+        I: Iterator<Item = &'b Record>,
+    {
+        let mut written_bytes: usize = 0;
+        for record in records {
+            written_bytes += self.write_record(record)?;
+        }
+        Ok(written_bytes)
+    }
+
+    /// Writes the values of a row in order of the section header, starting the section if necessary.
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - Section of the row
+    /// * `columns` - Values keyed by column name
+    ///
+    fn write_columns(&mut self, section: Section, columns: &IndexMap<String, String>) -> Result<usize> {
+        let version = self.version.ok_or_else(|| invalid_input("metadata has to be written before the rows"))?;
+        let mut written_bytes: usize = 0;
+        match &self.header {
+            Some((current_section, _)) if *current_section == section => (),
+            Some((current_section, _)) if *current_section > section => return Err(invalid_input(&format!(
+                "{} rows cannot follow {} rows", section.get_row_prefix(), current_section.get_row_prefix()
+            ))),
+            _ => written_bytes += self.write_header(version, section, columns)?
+        }
+        // Set by `write_header()` if necessary
+        let header = &self.header.as_ref().unwrap().1;
+        if let Some(column) = columns.keys().find(|column| !header.contains(column)) {
+            return Err(invalid_input(&format!(
+                "column `{}` is not part of the {} header", column, section.get_header_prefix()
+            )));
+        }
+        let mut line = section.get_row_prefix().to_string();
+        for column in header {
+            line.push('\t');
+            match columns.get(column).filter(|value| !value.is_empty()) {
+                Some(value) => line.push_str(value),
+                None => line.push_str("null")
+            }
+        }
+        line.push('\n');
+        written_bytes += self.write_bytes(line.as_bytes())?;
+        Ok(written_bytes)
+    }
+
+    /// Checks the metadata and mandatory columns of the section and writes its header,
+    /// preceded by an empty line.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version of the file
+    /// * `section` - Section
+    /// * `columns` - Values of the first row keyed by column name
+    ///
+    fn write_header(&mut self, version: Version, section: Section, columns: &IndexMap<String, String>) -> Result<usize> {
+        self.metadata.validate_section(version, section).map_err(|message| invalid_input(&message))?;
+        if let Some(column) = section.get_mandatory_columns(version).iter().find(|column| !columns.contains_key(**column)) {
+            return Err(invalid_input(&format!(
+                "{} row misses mandatory column `{}`", section.get_row_prefix(), column
+            )));
+        }
+        let header: Vec<String> = columns.keys().cloned().collect();
+        let content = format!("\n{}\t{}\n", section.get_header_prefix(), header.join("\t"));
+        self.header = Some((section, header));
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Flushes the buffer
+    ///
+    pub fn flush(&mut self) -> Result<()> {
+        self.internal_writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Creates an error for misuse of the writer
+///
+/// # Arguments
+///
+/// * `message` - Message describing the problem
+///
+fn invalid_input(message: &str) -> Error {
+    Error::from(io::Error::new(io::ErrorKind::InvalidInput, message))
+}
@@ -0,0 +1,127 @@
+// std imports
+use std::collections::HashMap;
+use std::io::prelude::*;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+
+// 3rd party imports
+use quick_xml::events::Event;
+
+// internal imports
+use crate::error::{Error, Position, Result};
+use crate::index::parse_int;
+use crate::xml::XmlReader;
+
+/// Number of bytes at the end of the file searched for `<indexOffset>`
+const INDEX_OFFSET_SEARCH_SIZE: u64 = 1024;
+
+/// Scan index embedded in a mzXML file (`<index name="scan">`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MzXmlIndex {
+    offsets: Vec<(u32, u64)>,
+    scans: HashMap<u32, usize>
+}
+
+impl MzXmlIndex {
+    /// Creates a new index from the given scan numbers and byte offsets
+    ///
+    /// # Arguments
+    ///
+    /// * `offsets` - Scan numbers and byte offsets of the `<scan>` elements
+    ///
+    pub fn new(offsets: Vec<(u32, u64)>) -> Self {
+        let scans = offsets.iter()
+            .enumerate()
+            .map(|(position, (scan, _))| (*scan, position))
+            .collect();
+        Self {
+            offsets,
+            scans
+        }
+    }
+
+    /// Reads the index of the given mzXML content.
+    /// The index is located via `<indexOffset>` at the end of the content.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Buffered and seekable reader providing the uncompressed mzXML content
+    /// * `file_path` - Path to the file, used for error positions
+    ///
+    pub fn read<R: BufRead + Seek>(reader: &mut R, file_path: Option<PathBuf>) -> Result<Self> {
+        let io_error = |source, offset| Error::Io {
+            source,
+            position: Position::new(file_path.clone(), None, offset)
+        };
+        let length = reader.seek(SeekFrom::End(0)).map_err(|err| io_error(err, None))?;
+        let tail_offset = length.saturating_sub(INDEX_OFFSET_SEARCH_SIZE);
+        reader.seek(SeekFrom::Start(tail_offset)).map_err(|err| io_error(err, Some(tail_offset)))?;
+        let mut tail = Vec::new();
+        reader.read_to_end(&mut tail).map_err(|err| io_error(err, Some(tail_offset)))?;
+        let tail = String::from_utf8_lossy(&tail);
+
+        let index_offset = tail.rfind("<indexOffset>")
+            .and_then(|start| {
+                let value = &tail[start + "<indexOffset>".len()..];
+                value.split_once("</indexOffset>").map(|(value, _)| value.trim())
+            })
+            .ok_or_else(|| Error::InvalidIndex {
+                message: "file contains no `<indexOffset>`, it is not an indexed mzXML".to_string(),
+                position: Position::new(file_path.clone(), None, None)
+            })?;
+        let index_offset: u64 = parse_int(index_offset, &Position::new(file_path.clone(), None, Some(tail_offset)))?;
+        reader.seek(SeekFrom::Start(index_offset)).map_err(|err| io_error(err, Some(index_offset)))?;
+
+        let mut xml_reader = XmlReader::new(reader, file_path.clone()).with_offset(index_offset);
+        let mut offsets: Vec<(u32, u64)> = Vec::new();
+        let mut is_in_scan_index = false;
+        loop {
+            match xml_reader.read_event()? {
+                Event::Start(element) if element.local_name().as_ref() == b"index" => {
+                    is_in_scan_index = xml_reader.get_attribute(&element, "name")?.as_deref() == Some("scan");
+                },
+                Event::Start(element) if is_in_scan_index && element.local_name().as_ref() == b"offset" => {
+                    let position = xml_reader.get_position();
+                    let scan = parse_int(&xml_reader.get_required_attribute(&element, "id")?, &position)?;
+                    let offset = parse_int(xml_reader.read_text()?.trim(), &position)?;
+                    offsets.push((scan, offset));
+                },
+                Event::End(element) if is_in_scan_index && element.local_name().as_ref() == b"index" => break,
+                Event::Eof => return Err(Error::InvalidIndex {
+                    message: format!("no scan index at offset {}", index_offset),
+                    position: Position::new(file_path, None, Some(index_offset))
+                }),
+                _ => ()
+            }
+        }
+        Ok(Self::new(offsets))
+    }
+
+    /// Returns the scan numbers and byte offsets in order of the file
+    ///
+    pub fn get_offsets(&self) -> &Vec<(u32, u64)> {
+        &self.offsets
+    }
+
+    /// Returns the byte offset of the scan with the given number
+    ///
+    /// # Arguments
+    ///
+    /// * `scan` - Scan number
+    ///
+    pub fn get_offset_by_scan(&self, scan: u32) -> Option<u64> {
+        self.scans.get(&scan).map(|position| self.offsets[*position].1)
+    }
+
+    /// Returns the number of indexed scans
+    ///
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns true if no scan is indexed
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
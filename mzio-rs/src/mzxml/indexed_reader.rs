@@ -0,0 +1,137 @@
+// std imports
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, SeekFrom};
+use std::path::{Path, PathBuf};
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+
+// internal imports
+use crate::error::{Error, Position, Result};
+use crate::index::open_uncompressed;
+use crate::mzxml::index::MzXmlIndex;
+use crate::mzxml::reader::Reader;
+use crate::mzxml::spectrum::Spectrum;
+use crate::xml::XmlReader;
+
+/// Reader for random access to the scans of an uncompressed, indexed mzXML file
+/// using the embedded `MzXmlIndex`.
+pub struct IndexedReader<R: BufRead + Seek> {
+    internal_reader: R,
+    file_path: Option<PathBuf>,
+    index: MzXmlIndex
+}
+
+impl IndexedReader<BufReader<File>> {
+    /// Creates a new IndexedReader for the given mzXML file and reads its index.
+    ///
+    /// # Arguments
+    ///
+    /// * `mzxml_file_path` - Path to mzXML file
+    ///
+    pub fn new(mzxml_file_path: &Path) -> Result<Self> {
+        Self::from_reader(open_uncompressed(mzxml_file_path)?, Some(mzxml_file_path.to_path_buf()))
+    }
+}
+
+impl<R: BufRead + Seek> IndexedReader<R> {
+    /// Creates a new IndexedReader from any buffered and seekable source and reads its index.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the uncompressed mzXML content
+    /// * `file_path` - Path to the file, used for error positions
+    ///
+    pub fn from_reader(mut internal_reader: R, file_path: Option<PathBuf>) -> Result<Self> {
+        let index = MzXmlIndex::read(&mut internal_reader, file_path.clone())?;
+        let mut reader = Self::with_index(internal_reader, index);
+        reader.file_path = file_path;
+        Ok(reader)
+    }
+
+    /// Creates a new IndexedReader from any buffered and seekable source and a known index
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the uncompressed mzXML content
+    /// * `index` - Index of the content
+    ///
+    pub fn with_index(internal_reader: R, index: MzXmlIndex) -> Self {
+        Self {
+            internal_reader,
+            file_path: None,
+            index
+        }
+    }
+
+    /// Returns the index
+    ///
+    pub fn get_index(&self) -> &MzXmlIndex {
+        &self.index
+    }
+
+    /// Returns the spectrum with the given scan number, None if the scan is not indexed.
+    /// Returns an error if the indexed offset points to another scan.
+    ///
+    /// # Arguments
+    ///
+    /// * `scan` - Scan number
+    ///
+    pub fn get_spectrum_by_scan(&mut self, scan: u32) -> Result<Option<Spectrum>> {
+        let offset = match self.index.get_offset_by_scan(scan) {
+            Some(offset) => offset,
+            None => return Ok(None)
+        };
+        let spectrum = self.get_spectrum_by_offset(offset)?;
+        if spectrum.get_scan_number() != scan {
+            return Err(Error::InvalidIndex {
+                message: format!(
+                    "scan {} found instead of scan {} at offset {}, the index may be outdated",
+                    spectrum.get_scan_number(),
+                    scan,
+                    offset
+                ),
+                position: Position::new(self.file_path.clone(), None, Some(offset))
+            });
+        }
+        Ok(Some(spectrum))
+    }
+
+    /// Returns the spectrum at the given position in the index, None if the position is out of range.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Position of the spectrum (0-based)
+    ///
+    pub fn get_spectrum_by_position(&mut self, position: usize) -> Result<Option<Spectrum>> {
+        match self.index.get_offsets().get(position).map(|(_, offset)| *offset) {
+            Some(offset) => self.get_spectrum_by_offset(offset).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    /// Reads the spectrum starting at the given byte offset.
+    /// Nested scans are not part of the returned spectrum.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Byte offset of the `<scan>` element
+    ///
+    pub fn get_spectrum_by_offset(&mut self, offset: u64) -> Result<Spectrum> {
+        let position = Position::new(self.file_path.clone(), None, Some(offset));
+        self.internal_reader.seek(SeekFrom::Start(offset))
+            .map_err(|source| Error::Io {
+                source,
+                position: position.clone()
+            })?;
+        let xml_reader = XmlReader::new(&mut self.internal_reader, self.file_path.clone()).with_offset(offset);
+        match Reader::from_xml_reader(xml_reader).next()? {
+            Some(spectrum) => Ok(spectrum),
+            None => Err(Error::InvalidIndex {
+                message: format!("no scan at offset {}, the index may be outdated", offset),
+                position
+            })
+        }
+    }
+}
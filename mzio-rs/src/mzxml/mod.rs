@@ -0,0 +1,157 @@
+//! Reading of mzXML files (version 3.x).
+//! The streaming reader decodes the `<peaks>` of each scan (32/64-bit, zlib compressed or not),
+//! the indexed reader uses the embedded `<index>` for random access.
+//! Scans convert into MGF spectra titled `scan=<scan number>`.
+
+pub mod index;
+pub mod indexed_reader;
+pub mod reader;
+pub mod spectrum;
+
+#[cfg(test)]
+mod test {
+    // std imports
+    use std::path::Path;
+
+    // 3rd party imports
+    use fallible_iterator::FallibleIterator;
+
+    // internal imports
+    use super::*;
+    use crate::error::Error;
+    use crate::mgf::spectrum::Spectrum as MgfSpectrum;
+
+    const MZXML_FILE_PATH_STR: &str = "../test_files/mzxml/small.mzXML";
+
+    #[test]
+    /// Reads all scans, including a scan nested in its precursor scan.
+    fn test_reading() {
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::new(Path::new(MZXML_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(spectra.len(), 3);
+
+        // MS1, 32-bit zlib compressed
+        let ms1 = &spectra[0];
+        assert_eq!(ms1.get_scan_number(), 1);
+        assert_eq!(ms1.get_ms_level(), 1);
+        assert_eq!(ms1.get_retention_time(), Some(30.0));
+        assert!(ms1.get_precursors().is_empty());
+        assert_eq!(ms1.get_mz_list(), &vec![400.25, 500.5, 700.0]);
+        assert_eq!(ms1.get_intensity_list(), &vec![1000.5, 2000.0, 150.0]);
+
+        // nested MS2, 64-bit uncompressed
+        let ms2 = &spectra[1];
+        assert_eq!(ms2.get_scan_number(), 2);
+        assert_eq!(ms2.get_ms_level(), 2);
+        assert_eq!(ms2.get_retention_time(), Some(62.5));
+        assert_eq!(ms2.get_precursors().len(), 1);
+        assert_eq!(ms2.get_precursors()[0].get_mz(), 445.12);
+        assert_eq!(ms2.get_precursors()[0].get_intensity(), Some(120053.0));
+        assert_eq!(ms2.get_precursors()[0].get_charge(), Some(2));
+        assert_eq!(ms2.get_precursors()[0].get_activation_method(), Some("CID"));
+        assert_eq!(ms2.get_mz_list(), &vec![110.0712, 147.1128]);
+        assert_eq!(ms2.get_intensity_list(), &vec![500.0, 1200.0]);
+
+        let ms2 = &spectra[2];
+        assert_eq!(ms2.get_scan_number(), 3);
+        assert_eq!(ms2.get_retention_time(), Some(72.5));
+        assert_eq!(ms2.get_precursors()[0].get_charge(), None);
+        assert_eq!(ms2.get_mz_list(), &vec![200.125]);
+        assert_eq!(ms2.get_intensity_list(), &vec![10.5]);
+
+        assert_eq!(reader::parse_duration("PT1M12.5S"), Some(72.5));
+        assert_eq!(reader::parse_duration("P1DT1H"), Some(90000.0));
+        assert_eq!(reader::parse_duration("72.5"), None);
+    }
+
+    #[test]
+    /// Accesses scans by number and position using the embedded index.
+    fn test_indexed_reading() {
+        let spectra: Vec<spectrum::Spectrum> = reader::Reader::new(Path::new(MZXML_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let mut indexed_reader = indexed_reader::IndexedReader::new(Path::new(MZXML_FILE_PATH_STR)).unwrap();
+        assert_eq!(indexed_reader.get_index().get_offsets(), &vec![(1, 826), (2, 1195), (3, 1655)]);
+        for spectrum in spectra.iter().rev() {
+            assert_eq!(
+                indexed_reader.get_spectrum_by_scan(spectrum.get_scan_number()).unwrap().as_ref(),
+                Some(spectrum)
+            );
+        }
+        assert_eq!(indexed_reader.get_spectrum_by_position(1).unwrap().as_ref(), Some(&spectra[1]));
+        assert!(indexed_reader.get_spectrum_by_scan(4).unwrap().is_none());
+        assert!(indexed_reader.get_spectrum_by_position(3).unwrap().is_none());
+
+        let error = indexed_reader.get_spectrum_by_offset(2003).unwrap_err();
+        assert!(matches!(error, Error::InvalidIndex { .. }));
+
+        let error = indexed_reader::IndexedReader::from_reader(std::io::Cursor::new("<mzXML></mzXML>"), None)
+            .err()
+            .unwrap();
+        assert!(matches!(error, Error::InvalidIndex { .. }));
+    }
+
+    #[test]
+    /// Checks that an outdated index is reported and errors refer to the position in the whole file.
+    fn test_indexed_reading_with_outdated_index() {
+        let mzxml_content = std::fs::read_to_string(MZXML_FILE_PATH_STR).unwrap();
+        let swapped_index = index::MzXmlIndex::new(vec![(1, 1195), (2, 826)]);
+        let mut indexed_reader = indexed_reader::IndexedReader::with_index(
+            std::io::Cursor::new(mzxml_content.as_bytes()),
+            swapped_index
+        );
+        let error = indexed_reader.get_spectrum_by_scan(1).unwrap_err();
+        assert!(matches!(error, Error::InvalidIndex { .. }));
+
+        let broken_content = mzxml_content.replace("precursorIntensity=\"120053\"", "precursorIntensity=\"12005x\"");
+        let mut indexed_reader = indexed_reader::IndexedReader::from_reader(
+            std::io::Cursor::new(broken_content.as_bytes()),
+            None
+        ).unwrap();
+        let error = indexed_reader.get_spectrum_by_scan(2).unwrap_err();
+        assert!(matches!(error, Error::FloatParse { .. }));
+        assert!(error.get_position().and_then(|position| position.get_byte_offset()).unwrap() > 1195);
+    }
+
+    #[test]
+    /// Converts mzXML spectra into MGF spectra.
+    fn test_conversion_to_mgf() {
+        let mut spectra = reader::Reader::new(Path::new(MZXML_FILE_PATH_STR), 1024).unwrap();
+        spectra.next().unwrap();
+        let mgf_spectrum = MgfSpectrum::from(spectra.next().unwrap().unwrap());
+        assert_eq!(mgf_spectrum.get_title(), "scan=2");
+        assert_eq!(mgf_spectrum.get_precursor_mz(), 445.12);
+        assert_eq!(mgf_spectrum.get_precursor_intensity(), Some(120053.0));
+        assert_eq!(mgf_spectrum.get_precursor_charges(), &vec![2]);
+        assert_eq!(mgf_spectrum.get_retention_time(), &Some(62.5));
+        assert_eq!(mgf_spectrum.get_additional_param("SCANS"), Some("2"));
+        assert_eq!(mgf_spectrum.get_mz_list(), &vec![110.0712, 147.1128]);
+
+        let mgf_spectrum = MgfSpectrum::from(spectra.next().unwrap().unwrap());
+        assert_eq!(mgf_spectrum.get_precursor_mz(), 622.3);
+        assert!(mgf_spectrum.get_precursor_charges().is_empty());
+    }
+
+    #[test]
+    /// Checks that mismatching peak counts and broken documents are reported.
+    fn test_reading_malformed_input() {
+        let mzxml_content = r#"<mzXML><msRun>
+            <scan num="1" msLevel="1" peaksCount="2" retentionTime="PT30S">
+              <peaks precision="32" byteOrder="network" pairOrder="m/z-int">Q0ggAEEoAAA=</peaks>
+            </scan>
+        </msRun></mzXML>"#;
+        let error = reader::Reader::from_reader(mzxml_content.as_bytes()).next().unwrap_err();
+        assert!(matches!(&error, Error::BinaryData { message, .. } if message == "expected 2 peaks, got 1"));
+
+        let error = reader::Reader::from_reader(mzxml_content.replace("PT30S", "30").as_bytes())
+            .next()
+            .unwrap_err();
+        assert!(matches!(error, Error::FloatParse { .. }));
+
+        let error = reader::Reader::from_reader(&mzxml_content.as_bytes()[..120]).next().unwrap_err();
+        assert!(matches!(error, Error::Xml { .. }));
+    }
+}
@@ -0,0 +1,235 @@
+// std imports
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+use quick_xml::events::{BytesStart, Event};
+
+// internal imports
+use crate::compression::open_reader;
+use crate::error::{Error, Result, parse_float};
+use crate::index::parse_int;
+use crate::mzxml::spectrum::{Precursor, Spectrum};
+use crate::xml::{decode_base64, zlib_decompress, XmlReader};
+
+/// Streaming reader for mzXML.
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+/// Scans nested in their precursor scan are returned after it.
+pub struct Reader<R: BufRead> {
+    internal_reader: XmlReader<R>,
+    // Start of a nested scan, which was read while reading the enclosing scan
+    pending_scan: Option<BytesStart<'static>>
+}
+
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given mzXML file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
+    ///
+    /// # Arguments
+    ///
+    /// * `mzxml_file_path` - Path to mzXML file
+    /// * `buffer_size` - Size of the internal read buffer
+    ///
+    pub fn new(mzxml_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Ok(Self::from_xml_reader(XmlReader::new(
+            open_reader(mzxml_file_path, buffer_size)?,
+            Some(mzxml_file_path.to_path_buf())
+        )))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the mzXML content
+    ///
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self::from_xml_reader(XmlReader::new(internal_reader, None))
+    }
+
+    /// Creates a new Reader from a XML reader
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - XML reader providing the mzXML content
+    ///
+    pub(crate) fn from_xml_reader(internal_reader: XmlReader<R>) -> Self {
+        Self {
+            internal_reader,
+            pending_scan: None
+        }
+    }
+
+    /// Reads a `<precursorMz>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the precursorMz
+    ///
+    fn read_precursor(&mut self, element: &BytesStart) -> Result<Precursor> {
+        let intensity = match self.internal_reader.get_attribute(element, "precursorIntensity")? {
+            Some(intensity) => Some(parse_float(&intensity, || self.internal_reader.get_position())?),
+            None => None
+        };
+        let charge = match self.internal_reader.get_attribute(element, "precursorCharge")? {
+            Some(charge) => Some(parse_int(&charge, &self.internal_reader.get_position())?),
+            None => None
+        };
+        let activation_method = self.internal_reader.get_attribute(element, "activationMethod")?;
+        let mz = self.internal_reader.read_text()?;
+        let mz = parse_float(mz.trim(), || self.internal_reader.get_position())?;
+        Ok(Precursor::new(mz, intensity, charge, activation_method))
+    }
+
+    /// Reads and decodes `<peaks>`, i.e. interleaved m/z and intensity pairs.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the peaks
+    ///
+    fn read_peaks(&mut self, element: &BytesStart) -> Result<(Vec<f64>, Vec<f32>)> {
+        let precision = self.internal_reader.get_attribute(element, "precision")?;
+        let is_zlib = self.internal_reader.get_attribute(element, "compressionType")?.as_deref() == Some("zlib");
+        let is_little_endian = self.internal_reader.get_attribute(element, "byteOrder")?.as_deref() == Some("little");
+        let encoded = self.internal_reader.read_text()?;
+        let binary_data_error = |message: String| Error::BinaryData {
+            message,
+            position: self.internal_reader.get_position()
+        };
+
+        let mut data = decode_base64(&encoded).map_err(binary_data_error)?;
+        if is_zlib {
+            data = zlib_decompress(&data).map_err(binary_data_error)?;
+        }
+        let values: Vec<f64> = match precision.as_deref() {
+            Some("32") | None if data.len() % 8 == 0 => data.chunks_exact(4)
+                .map(|chunk| {
+                    // The size of the chunks is given, so `try_into()` cannot fail
+                    let bytes: [u8; 4] = chunk.try_into().unwrap();
+                    let value = if is_little_endian { f32::from_le_bytes(bytes) } else { f32::from_be_bytes(bytes) };
+                    value as f64
+                })
+                .collect(),
+            Some("64") if data.len() % 16 == 0 => data.chunks_exact(8)
+                .map(|chunk| {
+                    let bytes: [u8; 8] = chunk.try_into().unwrap();
+                    if is_little_endian { f64::from_le_bytes(bytes) } else { f64::from_be_bytes(bytes) }
+                })
+                .collect(),
+            _ => return Err(binary_data_error(format!(
+                "{} bytes cannot be m/z-intensity pairs with precision {}",
+                data.len(),
+                precision.unwrap_or_default()
+            )))
+        };
+        Ok((
+            values.iter().step_by(2).copied().collect(),
+            values.iter().skip(1).step_by(2).map(|intensity| *intensity as f32).collect()
+        ))
+    }
+
+    /// Reads a `<scan>` up to its end or the start of a nested scan.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Start of the scan
+    ///
+    fn read_scan(&mut self, element: &BytesStart) -> Result<Spectrum> {
+        let position = self.internal_reader.get_position();
+        let scan_number: u32 = parse_int(&self.internal_reader.get_required_attribute(element, "num")?, &position)?;
+        let ms_level: u8 = match self.internal_reader.get_attribute(element, "msLevel")? {
+            Some(ms_level) => parse_int(&ms_level, &position)?,
+            None => 1
+        };
+        let peaks_count: Option<usize> = match self.internal_reader.get_attribute(element, "peaksCount")? {
+            Some(peaks_count) => Some(parse_int(&peaks_count, &position)?),
+            None => None
+        };
+        let retention_time = match self.internal_reader.get_attribute(element, "retentionTime")? {
+            Some(retention_time) => Some(parse_duration(&retention_time).ok_or_else(|| Error::FloatParse {
+                value: retention_time,
+                position: position.clone()
+            })?),
+            None => None
+        };
+        let mut precursors: Vec<Precursor> = Vec::new();
+        let mut mz_list: Vec<f64> = Vec::new();
+        let mut intensity_list: Vec<f32> = Vec::new();
+        loop {
+            match self.internal_reader.read_event()? {
+                Event::Start(element) => match element.local_name().as_ref() {
+                    b"precursorMz" => precursors.push(self.read_precursor(&element)?),
+                    b"peaks" => (mz_list, intensity_list) = self.read_peaks(&element)?,
+                    b"scan" => {
+                        self.pending_scan = Some(element);
+                        break;
+                    },
+                    _ => ()
+                },
+                Event::End(element) if element.local_name().as_ref() == b"scan" => break,
+                Event::Eof => return Err(self.internal_reader.create_error(
+                    "unexpected end of file within `<scan>`".to_string()
+                )),
+                _ => ()
+            }
+        }
+        if let Some(peaks_count) = peaks_count.filter(|peaks_count| *peaks_count != mz_list.len()) {
+            return Err(Error::BinaryData {
+                message: format!("expected {} peaks, got {}", peaks_count, mz_list.len()),
+                position
+            });
+        }
+        Ok(Spectrum::new(scan_number, ms_level, retention_time, precursors, mz_list, intensity_list))
+    }
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
+    type Item = Spectrum;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        if let Some(element) = self.pending_scan.take() {
+            return self.read_scan(&element).map(Some);
+        }
+        loop {
+            match self.internal_reader.read_event()? {
+                Event::Start(element) if element.local_name().as_ref() == b"scan" => {
+                    return self.read_scan(&element).map(Some);
+                },
+                // The index follows the last scan
+                Event::Start(element) if element.local_name().as_ref() == b"index" => return Ok(None),
+                Event::Eof => return Ok(None),
+                _ => ()
+            }
+        }
+    }
+}
+
+/// Parses a XML schema duration as used for retention times, e.g. `PT72.5S` or `PT1M12.5S`,
+/// into seconds. Returns None if the duration is malformed.
+///
+/// # Arguments
+///
+/// * `duration` - Duration
+///
+pub(crate) fn parse_duration(duration: &str) -> Option<f64> {
+    let duration = duration.trim().strip_prefix('P')?;
+    let (days, time) = duration.split_once('T').unwrap_or((duration, ""));
+    let mut seconds = 0.0;
+    for (part, units) in [(days, &[('D', 86400.0)][..]), (time, &[('H', 3600.0), ('M', 60.0), ('S', 1.0)][..])] {
+        let mut remaining = part;
+        for (unit, factor) in units {
+            if let Some((value, rest)) = remaining.split_once(*unit) {
+                seconds += value.parse::<f64>().ok()? * factor;
+                remaining = rest;
+            }
+        }
+        if !remaining.is_empty() {
+            return None;
+        }
+    }
+    Some(seconds)
+}
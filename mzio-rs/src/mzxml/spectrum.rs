@@ -0,0 +1,155 @@
+// internal imports
+use crate::mgf::spectrum::{Precursor as MgfPrecursor, Spectrum as MgfSpectrum};
+
+/// Precursor of a spectrum (`<precursorMz>`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Precursor {
+    mz: f64,
+    intensity: Option<f64>,
+    charge: Option<i8>,
+    activation_method: Option<String>
+}
+
+impl Precursor {
+    /// Creates a new precursor
+    ///
+    /// # Arguments
+    ///
+    /// * `mz` - Precursor m/z
+    /// * `intensity` - Precursor intensity
+    /// * `charge` - Precursor charge
+    /// * `activation_method` - Activation method, e.g. `CID` or `HCD`
+    ///
+    pub fn new(mz: f64, intensity: Option<f64>, charge: Option<i8>, activation_method: Option<String>) -> Self {
+        Self {
+            mz,
+            intensity,
+            charge,
+            activation_method
+        }
+    }
+
+    /// Returns the m/z
+    ///
+    pub fn get_mz(&self) -> f64 {
+        self.mz
+    }
+
+    /// Returns the intensity
+    ///
+    pub fn get_intensity(&self) -> Option<f64> {
+        self.intensity
+    }
+
+    /// Returns the charge
+    ///
+    pub fn get_charge(&self) -> Option<i8> {
+        self.charge
+    }
+
+    /// Returns the activation method
+    ///
+    pub fn get_activation_method(&self) -> Option<&str> {
+        self.activation_method.as_deref()
+    }
+}
+
+/// Spectrum representation for mzXML files (`<scan>`).
+/// Converts into a MGF spectrum, keeping the scan number as `SCANS=`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    scan_number: u32,
+    ms_level: u8,
+    retention_time: Option<f64>,
+    precursors: Vec<Precursor>,
+    mz_list: Vec<f64>,
+    intensity_list: Vec<f32>
+}
+
+impl Spectrum {
+    /// Creates a new spectrum
+    ///
+    /// # Arguments
+    ///
+    /// * `scan_number` - Scan number
+    /// * `ms_level` - MS level
+    /// * `retention_time` - Retention time in seconds
+    /// * `precursors` - Precursors, empty for MS1 spectra
+    /// * `mz_list` - M/Z list
+    /// * `intensity_list` - Intensity list
+    ///
+    pub fn new(scan_number: u32, ms_level: u8, retention_time: Option<f64>, precursors: Vec<Precursor>,
+        mz_list: Vec<f64>, intensity_list: Vec<f32>) -> Self {
+        Self {
+            scan_number,
+            ms_level,
+            retention_time,
+            precursors,
+            mz_list,
+            intensity_list
+        }
+    }
+
+    /// Returns the scan number
+    ///
+    pub fn get_scan_number(&self) -> u32 {
+        self.scan_number
+    }
+
+    /// Returns the MS level
+    ///
+    pub fn get_ms_level(&self) -> u8 {
+        self.ms_level
+    }
+
+    /// Returns the retention time in seconds
+    ///
+    pub fn get_retention_time(&self) -> Option<f64> {
+        self.retention_time
+    }
+
+    /// Returns the precursors, empty for MS1 spectra
+    ///
+    pub fn get_precursors(&self) -> &Vec<Precursor> {
+        &self.precursors
+    }
+
+    /// Returns the M/Z list
+    ///
+    pub fn get_mz_list(&self) -> &Vec<f64> {
+        &self.mz_list
+    }
+
+    /// Returns the intensity list
+    ///
+    pub fn get_intensity_list(&self) -> &Vec<f32> {
+        &self.intensity_list
+    }
+}
+
+impl From<Spectrum> for MgfSpectrum {
+    /// Converts the spectrum into a MGF spectrum. The title is `scan=<scan number>`,
+    /// which is also written as `SCANS=`.
+    ///
+    fn from(spectrum: Spectrum) -> Self {
+        let precursors: Vec<MgfPrecursor> = spectrum.precursors.iter()
+            .map(|precursor| MgfPrecursor::new(precursor.mz, precursor.intensity))
+            .collect();
+        let mut precursor_charges: Vec<i8> = Vec::new();
+        for charge in spectrum.precursors.iter().filter_map(|precursor| precursor.charge) {
+            if !precursor_charges.contains(&charge) {
+                precursor_charges.push(charge);
+            }
+        }
+        let additional_params = vec![("SCANS".to_string(), spectrum.scan_number.to_string())];
+        MgfSpectrum::new(
+            format!("scan={}", spectrum.scan_number),
+            precursors,
+            precursor_charges,
+            spectrum.retention_time,
+            spectrum.mz_list,
+            spectrum.intensity_list
+        )
+            .with_additional_params(additional_params)
+    }
+}
@@ -0,0 +1,155 @@
+//! Reading and writing of SpectraST sptxt spectral libraries, using the library spectrum
+//! of the MSP module. The binary splib format is not supported.
+//! Entries are named `<peptide>/<charge>`, which is written from peptide and charge of the spectrum.
+
+pub mod reader;
+pub mod writer;
+
+#[cfg(test)]
+mod test {
+    // std imports
+    use std::fs;
+    use std::path::Path;
+
+    // 3rd party imports
+    use fallible_iterator::FallibleIterator;
+
+    // internal imports
+    use super::*;
+    use crate::mgf::reader::Reader as MgfReader;
+    use crate::mgf::spectrum::Spectrum as MgfSpectrum;
+    use crate::msp::spectrum::{LibrarySpectrum, Modification};
+
+    const SPTXT_FILE_PATH_STR: &str = "../test_files/sptxt/small.sptxt";
+    const MGF_FILE_PATH_STR: &str = "../test_files/mgf/Velos005137.mgf";
+
+    #[test]
+    /// Reads peptide, modifications, charge, precursor m/z, retention time and annotated peaks.
+    fn test_reading() {
+        let spectra: Vec<LibrarySpectrum> = reader::Reader::new(Path::new(SPTXT_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(spectra.len(), 2);
+
+        let spectrum = &spectra[0];
+        assert_eq!(spectrum.get_name(), "AAAAGPGAALS[167]PR/2");
+        assert_eq!(spectrum.get_peptide(), Some("AAAAGPGAALSPR"));
+        assert_eq!(spectrum.get_charge(), Some(2));
+        assert_eq!(spectrum.get_precursor_mz(), Some(542.7771));
+        assert_eq!(spectrum.get_retention_time(), Some(1234.5));
+        assert_eq!(spectrum.get_modifications(), &vec![Modification::new(10, 'S', "Phospho".to_string())]);
+        assert_eq!(spectrum.get_additional_field("LibID"), Some("0"));
+        assert_eq!(
+            spectrum.get_additional_field("FullName"),
+            Some("X.AAAAGPGAALS[167]PR.X/2 (CID)")
+        );
+        assert_eq!(spectrum.get_mz_list(), &vec![110.0712, 147.1128, 262.1395]);
+        assert_eq!(spectrum.get_peak_annotation_list()[0].as_deref(), Some("y1/0.01\t2/2 0.5"));
+
+        let spectrum = &spectra[1];
+        assert_eq!(spectrum.get_peptide(), Some("LMEPDAVSAMK"));
+        assert_eq!(spectrum.get_charge(), Some(3));
+        assert_eq!(spectrum.get_modifications(), &vec![Modification::new(-1, 'L', "Acetyl".to_string())]);
+        assert_eq!(spectrum.get_retention_time(), Some(845.2));
+    }
+
+    #[test]
+    /// Writes the read header and spectra, which reproduces the file.
+    fn test_writing() {
+        let spectra: Vec<LibrarySpectrum> = reader::Reader::new(Path::new(SPTXT_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        let written_bytes = writer.write_header(&["SpectraST spectral library (text format)", "==="]).unwrap()
+            + writer.write_all(spectra.iter()).unwrap();
+        writer.flush().unwrap();
+        let buffer = writer.into_inner();
+        assert_eq!(written_bytes, buffer.len());
+        assert_eq!(String::from_utf8(buffer).unwrap(), fs::read_to_string(SPTXT_FILE_PATH_STR).unwrap());
+
+        // The retention time range is kept
+        let spectrum = spectra[0].clone().with_retention_time(Some(1220.0));
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_spectrum(&spectrum).unwrap();
+        let content = String::from_utf8(writer.into_inner()).unwrap();
+        assert!(content.contains(" RetentionTime=1220,1200,1250 "));
+    }
+
+    #[test]
+    /// Exports MGF spectra as library and converts them back.
+    fn test_mgf_roundtrip() {
+        let mgf_spectra: Vec<MgfSpectrum> = MgfReader::new(Path::new(MGF_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let spectra: Vec<LibrarySpectrum> = mgf_spectra.iter()
+            .map(|mgf_spectrum| LibrarySpectrum::from(mgf_spectrum.clone()))
+            .collect();
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        let content = writer.into_inner();
+
+        let read_spectra: Vec<LibrarySpectrum> = reader::Reader::from_reader(content.as_slice()).collect().unwrap();
+        assert_eq!(read_spectra.len(), mgf_spectra.len());
+        assert!(read_spectra.iter().all(|spectrum| spectrum.get_peak_annotation_list().is_empty()));
+        for (read_spectrum, mgf_spectrum) in read_spectra.into_iter().zip(mgf_spectra) {
+            let converted_spectrum = MgfSpectrum::from(read_spectrum);
+            assert_eq!(converted_spectrum.get_title(), mgf_spectrum.get_title());
+            assert_eq!(converted_spectrum.get_precursor_mz(), mgf_spectrum.get_precursor_mz());
+            assert_eq!(converted_spectrum.get_retention_time(), mgf_spectrum.get_retention_time());
+            assert_eq!(converted_spectrum.get_mz_list(), mgf_spectrum.get_mz_list());
+            assert_eq!(converted_spectrum.get_intensity_list(), mgf_spectrum.get_intensity_list());
+        }
+
+        // Peptide spectra are named by peptide and charge
+        let mgf_content = "BEGIN IONS\nTITLE=test\nPEPMASS=500.5\nCHARGE=2+\nSEQ=PEPTIDE\n100.1 20.5 b1\nEND IONS\n";
+        let mgf_spectrum = MgfReader::from_reader(mgf_content.as_bytes()).next().unwrap().unwrap();
+        let spectrum = LibrarySpectrum::from(mgf_spectrum);
+        assert_eq!(spectrum.get_name(), "PEPTIDE/2");
+        assert_eq!(spectrum.get_peptide(), Some("PEPTIDE"));
+        assert_eq!(spectrum.get_peak_annotation_list(), &vec![Some("b1".to_string())]);
+    }
+
+    #[test]
+    /// Names written spectra by their peptide and charge and reads both back.
+    fn test_name_roundtrip() {
+        let spectrum = LibrarySpectrum::new("unnamed".to_string(), vec![100.1], vec![20.5])
+            .with_peptide(Some("PEPTIDE".to_string()))
+            .with_charge(Some(3));
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_spectrum(&spectrum).unwrap();
+        let content = writer.into_inner();
+        assert!(String::from_utf8(content.clone()).unwrap().starts_with("Name: PEPTIDE/3\n"));
+
+        let read_spectrum = reader::Reader::from_reader(content.as_slice()).next().unwrap().unwrap();
+        assert_eq!(read_spectrum.get_name(), "PEPTIDE/3");
+        assert_eq!(read_spectrum.get_peptide(), Some("PEPTIDE"));
+        assert_eq!(read_spectrum.get_charge(), Some(3));
+
+        // Names with modifications are kept unless peptide or charge change
+        let spectra: Vec<LibrarySpectrum> = reader::Reader::new(Path::new(SPTXT_FILE_PATH_STR), 1024)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_spectrum(&spectra[0]).unwrap();
+        writer.write_spectrum(&spectra[0].clone().with_charge(Some(3))).unwrap();
+        let content = String::from_utf8(writer.into_inner()).unwrap();
+        assert!(content.starts_with("Name: AAAAGPGAALS[167]PR/2\n"));
+        assert!(content.contains("\nName: AAAAGPGAALSPR/3\n"));
+    }
+
+    #[test]
+    /// Reads peaks marked as unannotated (`?`) without annotation.
+    fn test_unannotated_peaks_roundtrip() {
+        let sptxt_content = "Name: PEPTIDE/2\nPrecursorMZ: 400.5\nNumPeaks: 2\n100.1\t20.5\t?\n200.2\t40\tb2/0.01\n\n";
+        let spectra: Vec<LibrarySpectrum> = reader::Reader::from_reader(sptxt_content.as_bytes()).collect().unwrap();
+        assert_eq!(spectra[0].get_peak_annotation_list(), &vec![None, Some("b2/0.01".to_string())]);
+
+        let mut writer = writer::Writer::from_writer(Vec::new());
+        writer.write_all(spectra.iter()).unwrap();
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), sptxt_content);
+    }
+}
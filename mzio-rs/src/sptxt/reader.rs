@@ -0,0 +1,71 @@
+// std imports
+use std::io::prelude::*;
+use std::path::Path;
+
+// 3rd party imports
+use fallible_iterator::FallibleIterator;
+
+// internal imports
+use crate::error::{Error, Result};
+use crate::msp::reader::Reader as MspReader;
+use crate::msp::spectrum::LibrarySpectrum;
+
+/// Annotation column of unannotated peaks
+pub(crate) const UNANNOTATED_PEAK: &str = "?";
+
+/// Reader for SpectraST sptxt spectral libraries.
+/// Works on any buffered source, e.g. files, stdin or in-memory buffers.
+/// The entries share the structure of MSP entries, so reading is delegated to `crate::msp::reader::Reader`.
+/// Peak annotations include the columns following the annotation, e.g. `y1/0.01\t2/2 0.5`.
+/// Peaks marked as unannotated (`?`) have no annotation.
+pub struct Reader<R: BufRead> {
+    internal_reader: MspReader<R>
+}
+
+impl Reader<Box<dyn BufRead + Send>> {
+    /// Creates a new Reader for the given sptxt file.
+    /// Compressed files are decompressed transparently (see `crate::compression`).
+    ///
+    /// # Arguments
+    ///
+    /// * `sptxt_file_path` - Path to sptxt file
+    /// * `buffer_size` - Size of the internal read buffer
+    ///
+    pub fn new(sptxt_file_path: &Path, buffer_size: usize) -> Result<Self> {
+        Ok(Self {
+            internal_reader: MspReader::new(sptxt_file_path, buffer_size)?
+        })
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a new Reader from any buffered source
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader providing the sptxt content
+    ///
+    pub fn from_reader(internal_reader: R) -> Self {
+        Self {
+            internal_reader: MspReader::from_reader(internal_reader)
+        }
+    }
+}
+
+impl<R: BufRead> FallibleIterator for Reader<R> {
+    type Item = LibrarySpectrum;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>> {
+        Ok(self.internal_reader.next()?.map(|spectrum| {
+            let peak_annotation_list: Vec<Option<String>> = spectrum.get_peak_annotation_list().iter()
+                .map(|annotation| annotation.clone().filter(|annotation| annotation != UNANNOTATED_PEAK))
+                .collect();
+            if peak_annotation_list.iter().any(Option::is_some) {
+                spectrum.with_peak_annotations(peak_annotation_list)
+            } else {
+                spectrum.with_peak_annotations(Vec::new())
+            }
+        }))
+    }
+}
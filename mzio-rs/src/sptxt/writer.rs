@@ -0,0 +1,210 @@
+// std imports
+use std::fs::File;
+use std::iter::zip;
+use std::io::BufWriter;
+use std::io::prelude::*;
+use std::path::Path;
+
+// internal imports
+use crate::compression::{Compression, Encoder};
+use crate::error::{Error, Position, Result};
+use crate::msp::comment::{PARENT_KEY, build_comment, format_comment, get_entry, set_entry};
+use crate::msp::reader::parse_peptide_and_charge;
+use crate::msp::spectrum::LibrarySpectrum;
+use crate::sptxt::reader::UNANNOTATED_PEAK;
+
+/// Fields written in front of the others in this order, if present
+const LEADING_FIELDS: [&str; 1] = ["LibID"];
+
+/// Fields written after `PrecursorMZ:` in this order, if present
+const PRECURSOR_FIELDS: [&str; 2] = ["Status", "FullName"];
+
+/// Writer for SpectraST sptxt spectral libraries
+/// Works on any target implementing `Write`, e.g. files, stdout or `Vec<u8>`.
+/// Use flush() to make sure the buffer is written completely.
+pub struct Writer<W: Write> {
+    internal_writer: W
+}
+
+impl Writer<BufWriter<File>> {
+    /// Creates a new buffered Writer for the given sptxt file
+    ///
+    /// # Arguments
+    ///
+    /// * `sptxt_file_path` - Path to sptxt file
+    ///
+    pub fn new(sptxt_file_path: &Path) -> Result<Self> {
+        let sptxt_file: File = File::create(sptxt_file_path)
+            .map_err(|err| Error::io_with_path(err, sptxt_file_path))?;
+        Ok(Self::from_writer(BufWriter::new(sptxt_file)))
+    }
+}
+
+impl Writer<Encoder<BufWriter<File>>> {
+    /// Creates a new buffered Writer for the given sptxt file, compressing the output.
+    /// Use finish() to complete the compressed file.
+    ///
+    /// # Arguments
+    ///
+    /// * `sptxt_file_path` - Path to sptxt file
+    /// * `compression` - Compression of the written file, see `Compression::from_path()`
+    ///
+    pub fn with_compression(sptxt_file_path: &Path, compression: Compression) -> Result<Self> {
+        Ok(Self::from_writer(Encoder::create(sptxt_file_path, compression)?))
+    }
+}
+
+impl<W: Write> Writer<Encoder<W>> {
+    /// Flushes the buffer, completes the compressed stream and returns the underlying write target.
+    ///
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        self.internal_writer.finish()
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new Writer for any write target.
+    /// The target is used as is, so wrap unbuffered targets in a `BufWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_writer` - Write target
+    ///
+    pub fn from_writer(internal_writer: W) -> Self {
+        Self {
+            internal_writer
+        }
+    }
+
+    /// Returns the underlying write target, e.g. to access an in-memory buffer.
+    /// Make sure to call flush() first.
+    ///
+    pub fn into_inner(self) -> W {
+        self.internal_writer
+    }
+
+    /// Writes the complete byte slice.
+    /// Returns the number of written bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to write
+    ///
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.internal_writer.write_all(bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Writes the header lines, each prefixed with `### `, which must precede the first spectrum.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - Header lines, e.g. `SpectraST spectral library (text format)`
+    ///
+    pub fn write_header(&mut self, lines: &[&str]) -> Result<usize> {
+        let mut content = String::new();
+        for line in lines {
+            content.push_str(&format!("### {}\n", line));
+        }
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Writes a library spectrum followed by an empty line.
+    /// The modifications and retention time are written as `Mods=` and `RetentionTime=` of the comment,
+    /// the precursor m/z as `PrecursorMZ:` and, if present, `Parent=` of the comment.
+    /// The name is `<peptide>/<charge>` if peptide and charge are set and differ from the ones of the name.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectrum` - Library spectrum
+    ///
+    pub fn write_spectrum(&mut self, spectrum: &LibrarySpectrum) -> Result<usize> {
+        let mut comment = build_comment(spectrum);
+        let additional_fields = spectrum.get_additional_fields();
+        let mut lines: Vec<String> = vec![format!("Name: {}", build_name(spectrum))];
+        for key in LEADING_FIELDS {
+            for (_, value) in additional_fields.iter().filter(|(field_key, _)| field_key == key) {
+                lines.push(format!("{}: {}", key, value));
+            }
+        }
+        if let Some(molecular_weight) = spectrum.get_molecular_weight() {
+            lines.push(format!("MW: {}", molecular_weight));
+        }
+        if let Some(precursor_mz) = spectrum.get_precursor_mz() {
+            lines.push(format!("PrecursorMZ: {}", precursor_mz));
+            if get_entry(&comment, PARENT_KEY).is_some() {
+                set_entry(&mut comment, PARENT_KEY, precursor_mz.to_string());
+            }
+        }
+        for key in PRECURSOR_FIELDS {
+            for (_, value) in additional_fields.iter().filter(|(field_key, _)| field_key == key) {
+                lines.push(format!("{}: {}", key, value));
+            }
+        }
+        for (key, value) in additional_fields {
+            if !LEADING_FIELDS.contains(&key.as_str()) && !PRECURSOR_FIELDS.contains(&key.as_str()) {
+                lines.push(format!("{}: {}", key, value));
+            }
+        }
+        if !comment.is_empty() {
+            lines.push(format!("Comment: {}", format_comment(&comment)));
+        }
+        lines.push(format!("NumPeaks: {}", spectrum.get_mz_list().len()));
+        for (idx, (mz, intensity)) in zip(spectrum.get_mz_list(), spectrum.get_intensity_list()).enumerate() {
+            match spectrum.get_peak_annotation_list().get(idx) {
+                Some(Some(annotation)) => lines.push(format!("{}\t{}\t{}", mz, intensity, annotation)),
+                // SpectraST expects an annotation column
+                _ => lines.push(format!("{}\t{}\t{}", mz, intensity, UNANNOTATED_PEAK))
+            }
+        }
+        lines.push(String::new());
+
+        let mut content = lines.join("\n");
+        content.push('\n');
+        self.write_bytes(content.as_bytes())
+    }
+
+    /// Writes multiple library spectra to file.
+    ///
+    /// # Arguments
+    ///
+    /// * `spectra` - Iterator of library spectra
+    ///
+    pub fn write_all<'b, I>(&mut self, spectra: I) -> Result<usize>
+    where
+        I: Iterator<Item = &'b LibrarySpectrum>,
+    {
+        let mut written_bytes: usize = 0;
+        for spectrum in spectra {
+            written_bytes += self.write_spectrum(spectrum)?;
+        }
+        Ok(written_bytes)
+    }
+
+    /// Flushes the buffer
+    ///
+    pub fn flush(&mut self) -> Result<()> {
+        self.internal_writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Returns the name of the spectrum, rebuilt from peptide and charge if they are set and differ
+/// from the ones of the name. Names matching both are kept, as they may contain modifications,
+/// e.g. `PEPS[167]K/2`.
+///
+/// # Arguments
+///
+/// * `spectrum` - Library spectrum
+///
+fn build_name(spectrum: &LibrarySpectrum) -> String {
+    let name = spectrum.get_name();
+    if let (Some(peptide), Some(charge)) = (spectrum.get_peptide(), spectrum.get_charge()) {
+        let name_peptide_and_charge = parse_peptide_and_charge(name, &Position::default(), &[], &Position::default()).ok();
+        if name_peptide_and_charge != Some((Some(peptide.to_string()), Some(charge))) {
+            return format!("{}/{}", peptide, charge);
+        }
+    }
+    name.to_string()
+}
@@ -0,0 +1,192 @@
+//! Event based XML reading with position tracking for error reporting
+//! and coding of the binary content of XML based formats.
+
+// std imports
+use std::io::prelude::*;
+use std::path::PathBuf;
+
+// 3rd party imports
+#[cfg(any(feature = "mzml", feature = "mzxml"))]
+use base64::Engine;
+#[cfg(any(feature = "mzml", feature = "mzxml"))]
+use base64::engine::general_purpose::STANDARD as BASE64;
+#[cfg(any(feature = "mzml", feature = "mzxml"))]
+use flate2::read::ZlibDecoder;
+#[cfg(feature = "mzml")]
+use flate2::write::ZlibEncoder;
+use quick_xml::events::{BytesStart, Event};
+
+// internal imports
+use crate::error::{Error, Position, Result};
+
+/// Wraps a quick-xml reader, so errors report where they occurred.
+pub(crate) struct XmlReader<R: BufRead> {
+    internal_reader: quick_xml::Reader<R>,
+    file_path: Option<PathBuf>,
+    // Byte offset of the first read byte in the file, e.g. after seeking
+    offset: u64,
+    buffer: Vec<u8>
+}
+
+impl<R: BufRead> XmlReader<R> {
+    /// Creates a new XmlReader
+    ///
+    /// # Arguments
+    ///
+    /// * `internal_reader` - Buffered reader
+    /// * `file_path` - Path to the read file, if any
+    ///
+    pub fn new(internal_reader: R, file_path: Option<PathBuf>) -> Self {
+        let mut internal_reader = quick_xml::Reader::from_reader(internal_reader);
+        internal_reader.trim_text(true);
+        Self {
+            internal_reader,
+            file_path,
+            offset: 0,
+            buffer: Vec::new()
+        }
+    }
+
+    /// Sets the byte offset in the file the reader starts at, e.g. after seeking,
+    /// so positions refer to the whole file.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Byte offset of the first read byte
+    ///
+    #[cfg(feature = "mzxml")]
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns the current position, i.e. the byte offset after the last read event
+    ///
+    pub fn get_position(&self) -> Position {
+        Position::new(self.file_path.clone(), None, Some(self.offset + self.internal_reader.buffer_position() as u64))
+    }
+
+    /// Reads the next event
+    ///
+    pub fn read_event(&mut self) -> Result<Event<'static>> {
+        self.buffer.clear();
+        match self.internal_reader.read_event_into(&mut self.buffer) {
+            Ok(event) => Ok(event.into_owned()),
+            Err(err) => Err(self.create_error(err.to_string()))
+        }
+    }
+
+    /// Reads the text content up to the end of the current element,
+    /// e.g. `<binary>...</binary>` or `<peaks>...</peaks>`.
+    ///
+    pub fn read_text(&mut self) -> Result<String> {
+        let mut text = String::new();
+        loop {
+            match self.read_event()? {
+                Event::Text(content) => text.push_str(&content.unescape().map_err(|err| self.create_error(err.to_string()))?),
+                Event::CData(content) => text.push_str(&String::from_utf8_lossy(&content)),
+                Event::End(_) => return Ok(text),
+                Event::Eof => return Err(self.create_error("unexpected end of file".to_string())),
+                _ => ()
+            }
+        }
+    }
+
+    /// Creates an XML error at the current position
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Message describing the problem
+    ///
+    pub fn create_error(&self, message: String) -> Error {
+        Error::Xml {
+            message,
+            position: self.get_position()
+        }
+    }
+
+    /// Returns the value of the given attribute, None if the element does not have it.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Element
+    /// * `name` - Attribute name
+    ///
+    pub fn get_attribute(&self, element: &BytesStart, name: &str) -> Result<Option<String>> {
+        match element.try_get_attribute(name) {
+            Ok(Some(attribute)) => attribute.unescape_value()
+                .map(|value| Some(value.into_owned()))
+                .map_err(|err| self.create_error(err.to_string())),
+            Ok(None) => Ok(None),
+            Err(err) => Err(self.create_error(err.to_string()))
+        }
+    }
+
+    /// Returns the value of the given attribute, an error if the element does not have it.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - Element
+    /// * `name` - Attribute name
+    ///
+    pub fn get_required_attribute(&self, element: &BytesStart, name: &str) -> Result<String> {
+        self.get_attribute(element, name)?.ok_or_else(|| self.create_error(format!(
+            "attribute `{}` of `<{}>` is missing",
+            name,
+            String::from_utf8_lossy(element.local_name().as_ref())
+        )))
+    }
+}
+
+/// Decodes base64 encoded binary content, ignoring whitespace.
+/// Returns a message describing the problem on failure.
+///
+/// # Arguments
+///
+/// * `encoded` - Base64 encoded content
+///
+#[cfg(any(feature = "mzml", feature = "mzxml"))]
+pub(crate) fn decode_base64(encoded: &str) -> std::result::Result<Vec<u8>, String> {
+    let encoded: String = encoded.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    BASE64.decode(encoded).map_err(|err| format!("invalid base64: {}", err))
+}
+
+/// Encodes binary content as base64
+///
+/// # Arguments
+///
+/// * `data` - Binary content
+///
+#[cfg(feature = "mzml")]
+pub(crate) fn encode_base64(data: &[u8]) -> String {
+    BASE64.encode(data)
+}
+
+/// Decompresses a zlib stream.
+/// Returns a message describing the problem on failure.
+///
+/// # Arguments
+///
+/// * `data` - Compressed data
+///
+#[cfg(any(feature = "mzml", feature = "mzxml"))]
+pub(crate) fn zlib_decompress(data: &[u8]) -> std::result::Result<Vec<u8>, String> {
+    let mut decompressed: Vec<u8> = Vec::with_capacity(data.len() * 2);
+    ZlibDecoder::new(data).read_to_end(&mut decompressed)
+        .map_err(|err| format!("invalid zlib stream: {}", err))?;
+    Ok(decompressed)
+}
+
+/// Compresses the data as zlib stream
+///
+/// # Arguments
+///
+/// * `data` - Data
+///
+#[cfg(feature = "mzml")]
+pub(crate) fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::with_capacity(data.len()), flate2::Compression::default());
+    // Writing into a `Vec` cannot fail
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}